@@ -0,0 +1,97 @@
+use actix_web::{delete, get, HttpResponse, post, web};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::api_key::ApiKey;
+use crate::auth::api_key_exception::ApiKeyException;
+use crate::auth::api_key_registry::ApiKeyRegistry;
+use crate::prewarm::cache_prewarmer::CachePrewarmer;
+use crate::quota::translation_quota::TranslationQuota;
+use crate::store::translation_store::TranslationStore;
+use crate::translation_service::ShakespearemonException;
+
+#[derive(Serialize, Deserialize)]
+pub struct PurgeResponse {
+    pub purged: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub hourly_limit: Option<u32>,
+    pub daily_limit: Option<u32>,
+}
+
+/// The only response that carries the secret key; it cannot be read back later.
+#[derive(Serialize, Deserialize)]
+pub struct CreateApiKeyResponse {
+    pub key: String,
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+}
+
+/// Registers the admin routes. `main` only does so with `[auth]` configured,
+/// since nothing else keeps them from the public.
+pub fn configure_admin_api(config: &mut web::ServiceConfig) {
+    config
+        .service(list_stored_translations)
+        .service(purge_stored_translations)
+        .service(get_translation_quota)
+        .service(get_prewarm_progress)
+        .service(create_api_key)
+        .service(list_api_keys)
+        .service(revoke_api_key);
+}
+
+#[get("/admin/translations")]
+pub async fn list_stored_translations(translation_store: web::Data<TranslationStore>) -> Result<HttpResponse, ShakespearemonException> {
+    let translations = translation_store.list_translations().await
+        .map_err(|error| {
+            ShakespearemonException::TranslationStoreException(error)
+        })?;
+
+    Ok(HttpResponse::Ok().json(translations))
+}
+
+#[delete("/admin/translations")]
+pub async fn purge_stored_translations(translation_store: web::Data<TranslationStore>) -> Result<HttpResponse, ShakespearemonException> {
+    let purged = translation_store.purge_translations().await
+        .map_err(|error| {
+            ShakespearemonException::TranslationStoreException(error)
+        })?;
+
+    Ok(HttpResponse::Ok().json(PurgeResponse {
+        purged
+    }))
+}
+
+#[get("/admin/quota")]
+pub async fn get_translation_quota(translation_quota: web::Data<TranslationQuota>) -> HttpResponse {
+    HttpResponse::Ok().json(translation_quota.status())
+}
+
+#[get("/admin/prewarm")]
+pub async fn get_prewarm_progress(cache_prewarmer: web::Data<CachePrewarmer>) -> HttpResponse {
+    HttpResponse::Ok().json(cache_prewarmer.progress())
+}
+
+#[post("/admin/api-keys")]
+pub async fn create_api_key(api_key_registry: web::Data<ApiKeyRegistry>, request: web::Json<CreateApiKeyRequest>) -> Result<HttpResponse, ApiKeyException> {
+    let (key, api_key) = api_key_registry.create_api_key(&request.name, request.hourly_limit, request.daily_limit).await?;
+
+    Ok(HttpResponse::Created().json(CreateApiKeyResponse {
+        key,
+        api_key,
+    }))
+}
+
+#[get("/admin/api-keys")]
+pub async fn list_api_keys(api_key_registry: web::Data<ApiKeyRegistry>) -> Result<HttpResponse, ApiKeyException> {
+    Ok(HttpResponse::Ok().json(api_key_registry.list_api_keys().await?))
+}
+
+#[delete("/admin/api-keys/{id}")]
+pub async fn revoke_api_key(api_key_registry: web::Data<ApiKeyRegistry>, web::Path(id): web::Path<String>) -> Result<HttpResponse, ApiKeyException> {
+    api_key_registry.revoke_api_key(&id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,109 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use actix_web::{Error, web};
+use actix_web::body::{Body, ResponseBody};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{self, HeaderName, HeaderValue};
+use futures::future::{LocalBoxFuture, ok, Ready};
+
+use crate::daily_pokemon_service::get_pokemon_of_the_day;
+use crate::negotiation::negotiated_exception::NegotiatedException;
+use crate::negotiation::response_format::ResponseFormat;
+use crate::resource_translation_service::{translate_ability_description, translate_move_description};
+use crate::text_translation_service::configure_text_translation;
+use crate::translation_service::translate_pokemon_description_by_shakespeare;
+
+pub const API_V1_PREFIX: &str = "/v1";
+
+const DEPRECATION_HEADER: &str = "deprecation";
+
+/// How the unversioned aliases send what `/v1` sends as a JSON `ErrorResponse`.
+pub const LEGACY_ERROR_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+
+/// Registers the public API. `main` mounts it under [`API_V1_PREFIX`] and, for
+/// clients that predate versioning, again at the root behind [`Deprecated`].
+pub fn configure_public_api(max_text_length: usize) -> impl Fn(&mut web::ServiceConfig) {
+    move |config| {
+        config
+            // `/pokemon/daily` must come before `/pokemon/{name}`, which would otherwise match it.
+            .service(get_pokemon_of_the_day)
+            .service(translate_pokemon_description_by_shakespeare)
+            .service(translate_ability_description)
+            .service(translate_move_description);
+        configure_text_translation(max_text_length)(config);
+    }
+}
+
+/// Marks responses from unversioned routes with `Deprecation: true` and links
+/// to the same route under [`API_V1_PREFIX`].
+pub struct Deprecated;
+
+impl<S, B> Transform<S> for Deprecated
+    where
+        S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+        B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = DeprecatedMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(DeprecatedMiddleware {
+            service: Rc::new(RefCell::new(service)),
+        })
+    }
+}
+
+pub struct DeprecatedMiddleware<S> {
+    service: Rc<RefCell<S>>,
+}
+
+impl<S, B> Service for DeprecatedMiddleware<S>
+    where
+        S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+        B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, context: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.borrow_mut().poll_ready(context)
+    }
+
+    fn call(&mut self, request: ServiceRequest) -> Self::Future {
+        let response = self.service.borrow_mut().call(request);
+
+        Box::pin(async move {
+            let mut response = response.await?;
+
+            // Paths that match no route are plain 404s, not deprecated aliases.
+            if response.request().match_pattern().is_some() {
+                let successor = format!("<{}{}>; rel=\"successor-version\"", API_V1_PREFIX, response.request().path());
+                let headers = response.headers_mut();
+                headers.insert(HeaderName::from_static(DEPRECATION_HEADER), HeaderValue::from_static("true"));
+                if let Ok(successor) = HeaderValue::from_str(&successor) {
+                    headers.insert(header::LINK, successor);
+                }
+            }
+
+            // Clients that predate versioning read errors as a bare message, not an `ErrorResponse`.
+            let legacy_message = response.response().error()
+                .and_then(|error| error.as_error::<NegotiatedException>())
+                .filter(|error| error.format == ResponseFormat::Json)
+                .map(|error| error.exception.to_string());
+            if let Some(legacy_message) = legacy_message {
+                response.headers_mut().insert(header::CONTENT_TYPE, HeaderValue::from_static(LEGACY_ERROR_CONTENT_TYPE));
+                response = response.map_body(|_, _| ResponseBody::Other(Body::from(legacy_message)));
+            }
+
+            Ok(response)
+        })
+    }
+}
@@ -0,0 +1,61 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const KEY_PREFIX: &str = "smk_";
+const ID_LENGTH: usize = 12;
+
+/// A client allowed to call the API. Only a hash of the secret key is kept;
+/// `id` is derived from it and is safe to show in listings and logs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    pub hourly_limit: u32,
+    pub daily_limit: u32,
+    pub created_at: Option<i64>,
+    pub revoked_at: Option<i64>,
+}
+
+impl ApiKey {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+pub fn generate_api_key() -> String {
+    let bytes: [u8; 24] = rand::thread_rng().gen();
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}{}", KEY_PREFIX, hex)
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+pub fn api_key_id(key_hash: &str) -> String {
+    key_hash[..ID_LENGTH].to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_generate_distinct_prefixed_keys() {
+        let first = generate_api_key();
+        let second = generate_api_key();
+
+        assert!(first.starts_with(KEY_PREFIX));
+        assert_eq!(first.len(), KEY_PREFIX.len() + 48);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn should_derive_a_stable_id_from_the_hash() {
+        let key_hash = hash_api_key("secret");
+
+        assert_eq!(api_key_id(&key_hash), api_key_id(&hash_api_key("secret")));
+        assert_eq!(api_key_id(&key_hash).len(), ID_LENGTH);
+    }
+}
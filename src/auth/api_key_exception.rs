@@ -0,0 +1,79 @@
+use actix_web::{HttpResponse, ResponseError};
+use actix_web::dev::HttpResponseBuilder;
+use actix_web::http::{header, StatusCode};
+use derive_more::{Display, Error};
+
+use crate::auth::api_key_middleware::rate_limit_headers;
+use crate::error_response::ErrorResponse;
+use crate::quota::translation_quota::QuotaStatus;
+
+#[derive(Debug, Clone, Display, Error, PartialEq)]
+pub enum ApiKeyException {
+    #[display(fmt = "An API key is required in the X-Api-Key header")]
+    MissingApiKey,
+    #[display(fmt = "The API key is not recognised")]
+    InvalidApiKey,
+    #[display(fmt = "The API key has been revoked")]
+    RevokedApiKey,
+    #[display(fmt = "The API key is not allowed to use admin endpoints")]
+    AdminKeyRequired,
+    #[display(fmt = "The API key has used up its requests, retry in {} seconds", retry_after)]
+    RateLimited {
+        #[error(not(source))]
+        status: QuotaStatus,
+        #[error(not(source))]
+        retry_after: u64,
+    },
+    #[display(fmt = "The API key was not found")]
+    ApiKeyNotFound,
+    #[display(fmt = "API keys cannot be managed without a translation store")]
+    ApiKeyStoreUnavailable,
+}
+
+impl ApiKeyException {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiKeyException::MissingApiKey => "missing_api_key",
+            ApiKeyException::InvalidApiKey => "invalid_api_key",
+            ApiKeyException::RevokedApiKey => "revoked_api_key",
+            ApiKeyException::AdminKeyRequired => "admin_key_required",
+            ApiKeyException::RateLimited { .. } => "rate_limited",
+            ApiKeyException::ApiKeyNotFound => "api_key_not_found",
+            ApiKeyException::ApiKeyStoreUnavailable => "api_key_store_unavailable",
+        }
+    }
+}
+
+impl ResponseError for ApiKeyException {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiKeyException::MissingApiKey => StatusCode::UNAUTHORIZED,
+            ApiKeyException::InvalidApiKey => StatusCode::UNAUTHORIZED,
+            ApiKeyException::RevokedApiKey => StatusCode::FORBIDDEN,
+            ApiKeyException::AdminKeyRequired => StatusCode::FORBIDDEN,
+            ApiKeyException::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ApiKeyException::ApiKeyNotFound => StatusCode::NOT_FOUND,
+            ApiKeyException::ApiKeyStoreUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponseBuilder::new(self.status_code());
+
+        if let ApiKeyException::MissingApiKey | ApiKeyException::InvalidApiKey = self {
+            response.set_header(header::WWW_AUTHENTICATE, "ApiKey header=\"X-Api-Key\"");
+        }
+
+        if let ApiKeyException::RateLimited { status, retry_after } = self {
+            response.set_header(header::RETRY_AFTER, retry_after.to_string());
+            for (name, value) in rate_limit_headers(status) {
+                response.set_header(name, value);
+            }
+        }
+
+        response.json(ErrorResponse {
+            error: self.code().to_owned(),
+            message: self.to_string(),
+        })
+    }
+}
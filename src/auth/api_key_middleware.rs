@@ -0,0 +1,117 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use actix_web::{Error, HttpMessage, ResponseError};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::{HeaderName, HeaderValue};
+use futures::future::{LocalBoxFuture, ok, Ready};
+
+use crate::auth::api_key_registry::{ApiKeyRegistry, Caller, MeteredApiKey};
+use crate::quota::translation_quota::QuotaStatus;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+const ADMIN_PATH_PREFIX: &str = "/admin";
+
+pub fn rate_limit_headers(status: &QuotaStatus) -> Vec<(&'static str, String)> {
+    vec![
+        ("x-ratelimit-limit", status.hourly_limit.to_string()),
+        ("x-ratelimit-remaining", status.hourly_remaining.to_string()),
+        ("x-ratelimit-reset", status.hourly_reset_at.to_string()),
+        ("x-ratelimit-daily-limit", status.daily_limit.to_string()),
+        ("x-ratelimit-daily-remaining", status.daily_remaining.to_string()),
+        ("x-ratelimit-daily-reset", status.daily_reset_at.to_string()),
+    ]
+}
+
+/// Requires an `X-Api-Key` header on every request and meters clients
+/// against their own limits; routes under `/admin` take the admin key only.
+pub struct ApiKeyAuth {
+    registry: Arc<ApiKeyRegistry>,
+}
+
+impl ApiKeyAuth {
+    pub fn new(registry: Arc<ApiKeyRegistry>) -> Self {
+        ApiKeyAuth {
+            registry
+        }
+    }
+}
+
+impl<S, B> Transform<S> for ApiKeyAuth
+    where
+        S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+        B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = ApiKeyAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(ApiKeyAuthMiddleware {
+            service: Rc::new(RefCell::new(service)),
+            registry: self.registry.clone(),
+        })
+    }
+}
+
+pub struct ApiKeyAuthMiddleware<S> {
+    service: Rc<RefCell<S>>,
+    registry: Arc<ApiKeyRegistry>,
+}
+
+impl<S, B> Service for ApiKeyAuthMiddleware<S>
+    where
+        S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+        B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, context: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.borrow_mut().poll_ready(context)
+    }
+
+    fn call(&mut self, request: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let registry = self.registry.clone();
+
+        Box::pin(async move {
+            let key = request.headers().get(API_KEY_HEADER)
+                .and_then(|key| key.to_str().ok())
+                .map(str::to_owned);
+            let admin_route = request.path().starts_with(ADMIN_PATH_PREFIX);
+
+            let caller = match registry.authorize(key.as_deref(), admin_route).await {
+                Ok(caller) => caller,
+                Err(error) => return Ok(request.into_response(error.error_response().into_body())),
+            };
+
+            if let (Caller::Client(_), Some(key)) = (&caller, key) {
+                request.extensions_mut().insert(MeteredApiKey {
+                    api_key_registry: registry,
+                    key,
+                });
+            }
+
+            let response = service.borrow_mut().call(request);
+            let mut response = response.await?;
+
+            if let Caller::Client(status) = caller {
+                for (name, value) in rate_limit_headers(&status) {
+                    if let Ok(value) = HeaderValue::from_str(&value) {
+                        response.headers_mut().insert(HeaderName::from_static(name), value);
+                    }
+                }
+            }
+
+            Ok(response)
+        })
+    }
+}
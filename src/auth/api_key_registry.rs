@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::auth::api_key::{api_key_id, ApiKey, hash_api_key};
+use crate::auth::api_key_exception::ApiKeyException;
+use crate::quota::translation_quota::{QuotaStatus, TranslationQuota};
+use crate::settings::Auth;
+use crate::store::api_key_store::ApiKeyStore;
+
+/// What a request was let through as.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Caller {
+    Admin,
+    Client(QuotaStatus),
+}
+
+/// A client key the [`ApiKeyAuth`](crate::auth::api_key_middleware::ApiKeyAuth)
+/// middleware let through, left in the request extensions so a handler that
+/// fans the request out can charge the key for the rest of it.
+#[derive(Clone)]
+pub struct MeteredApiKey {
+    pub api_key_registry: Arc<ApiKeyRegistry>,
+    pub key: String,
+}
+
+impl MeteredApiKey {
+    /// Takes `requests` more requests from the key's allowance.
+    pub async fn charge(&self, requests: u32) -> Result<(), ApiKeyException> {
+        self.api_key_registry.authorize_many(Some(&self.key), false, requests).await
+            .map(|_| ())
+    }
+}
+
+/// Knows every key allowed to call the API, from the config file and from
+/// the store, and meters each one against its own rate limit and daily quota.
+#[derive(Default)]
+pub struct ApiKeyRegistry {
+    admin_key_hash: Option<String>,
+    configured_keys: HashMap<String, ApiKey>,
+    api_key_store: Option<Arc<ApiKeyStore>>,
+    default_hourly_limit: u32,
+    default_daily_limit: u32,
+    quotas: Mutex<HashMap<String, Arc<TranslationQuota>>>,
+}
+
+impl ApiKeyRegistry {
+    pub fn new(auth: &Auth) -> Self {
+        let configured_keys = auth.keys.iter()
+            .map(|configured| {
+                let key_hash = hash_api_key(&configured.key);
+                let api_key = ApiKey {
+                    id: api_key_id(&key_hash),
+                    name: configured.name.to_owned(),
+                    hourly_limit: configured.hourly_limit,
+                    daily_limit: configured.daily_limit,
+                    created_at: None,
+                    revoked_at: None,
+                };
+                (key_hash, api_key)
+            })
+            .collect();
+
+        ApiKeyRegistry {
+            admin_key_hash: Some(hash_api_key(&auth.admin_key)),
+            configured_keys,
+            api_key_store: None,
+            default_hourly_limit: auth.default_hourly_limit,
+            default_daily_limit: auth.default_daily_limit,
+            quotas: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_api_key_store(mut self, api_key_store: Arc<ApiKeyStore>) -> Self {
+        self.api_key_store = Some(api_key_store);
+        self
+    }
+
+    /// Checks the key sent with a request and, for clients, takes one request
+    /// from its allowance. Admin routes only accept the admin key.
+    pub async fn authorize(&self, key: Option<&str>, admin_route: bool) -> Result<Caller, ApiKeyException> {
+        self.authorize_many(key, admin_route, 1).await
+    }
+
+    /// Like [`authorize`](ApiKeyRegistry::authorize), but takes `requests` requests
+    /// at once, for calls that fan out into that many translations.
+    pub async fn authorize_many(&self, key: Option<&str>, admin_route: bool, requests: u32) -> Result<Caller, ApiKeyException> {
+        let key = key.ok_or(ApiKeyException::MissingApiKey)?;
+        let key_hash = hash_api_key(key);
+
+        if self.admin_key_hash.as_deref() == Some(key_hash.as_str()) {
+            return Ok(Caller::Admin);
+        }
+
+        let api_key = self.find_api_key(&key_hash).await?
+            .ok_or(ApiKeyException::InvalidApiKey)?;
+
+        if api_key.is_revoked() {
+            return Err(ApiKeyException::RevokedApiKey);
+        }
+
+        if admin_route {
+            return Err(ApiKeyException::AdminKeyRequired);
+        }
+
+        let quota = self.quota_for(&api_key);
+        match quota.try_acquire_many(requests) {
+            Ok(()) => Ok(Caller::Client(quota.status())),
+            Err(reset_at) => Err(ApiKeyException::RateLimited {
+                status: quota.status(),
+                retry_after: reset_at.saturating_sub(now()),
+            }),
+        }
+    }
+
+    pub async fn create_api_key(&self, name: &str, hourly_limit: Option<u32>, daily_limit: Option<u32>) -> Result<(String, ApiKey), ApiKeyException> {
+        self.api_key_store()?
+            .create_api_key(name, hourly_limit.unwrap_or(self.default_hourly_limit), daily_limit.unwrap_or(self.default_daily_limit)).await
+            .map_err(|_| ApiKeyException::ApiKeyStoreUnavailable)
+    }
+
+    /// Lists configured keys first, then stored ones.
+    pub async fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyException> {
+        let mut api_keys: Vec<ApiKey> = self.configured_keys.values().cloned().collect();
+        api_keys.sort_by(|first, second| first.name.cmp(&second.name));
+
+        if let Some(api_key_store) = &self.api_key_store {
+            api_keys.extend(api_key_store.list_api_keys().await.map_err(|_| ApiKeyException::ApiKeyStoreUnavailable)?);
+        }
+
+        Ok(api_keys)
+    }
+
+    /// Revokes a stored key. Configured keys are removed from the config file instead.
+    pub async fn revoke_api_key(&self, id: &str) -> Result<(), ApiKeyException> {
+        let revoked = self.api_key_store()?
+            .revoke_api_key(id).await
+            .map_err(|_| ApiKeyException::ApiKeyStoreUnavailable)?;
+
+        if revoked {
+            self.quotas.lock().unwrap().remove(id);
+            Ok(())
+        } else {
+            Err(ApiKeyException::ApiKeyNotFound)
+        }
+    }
+
+    async fn find_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>, ApiKeyException> {
+        if let Some(api_key) = self.configured_keys.get(key_hash) {
+            return Ok(Some(api_key.clone()));
+        }
+
+        match &self.api_key_store {
+            Some(api_key_store) => api_key_store.find_api_key(key_hash).await
+                .map_err(|_| ApiKeyException::ApiKeyStoreUnavailable),
+            None => Ok(None)
+        }
+    }
+
+    fn api_key_store(&self) -> Result<&ApiKeyStore, ApiKeyException> {
+        self.api_key_store.as_deref().ok_or(ApiKeyException::ApiKeyStoreUnavailable)
+    }
+
+    fn quota_for(&self, api_key: &ApiKey) -> Arc<TranslationQuota> {
+        self.quotas.lock().unwrap()
+            .entry(api_key.id.to_owned())
+            .or_insert_with(|| Arc::new(TranslationQuota::new(api_key.hourly_limit, api_key.daily_limit)))
+            .clone()
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
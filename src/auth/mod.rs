@@ -0,0 +1,4 @@
+pub mod api_key;
+pub mod api_key_exception;
+pub mod api_key_middleware;
+pub mod api_key_registry;
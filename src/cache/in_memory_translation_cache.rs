@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::cache::translation_cache::{cache_key, TranslationCache};
+
+/// Process-local cache with the same namespacing and expiry rules as the Redis
+/// backend, used where a shared cache is not needed and as a stand-in in tests.
+pub struct InMemoryTranslationCache {
+    namespace: String,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl InMemoryTranslationCache {
+    pub fn new(namespace: &str, ttl: Duration) -> Self {
+        InMemoryTranslationCache {
+            namespace: namespace.to_owned(),
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl TranslationCache for InMemoryTranslationCache {
+    async fn get_translation(&self, text: &str, style: &str) -> Option<String> {
+        let key = cache_key(&self.namespace, text, style);
+        let mut entries = self.entries.lock().unwrap();
+
+        match entries.get(&key) {
+            Some((translated, expires_at)) if *expires_at > Instant::now() => Some(translated.to_owned()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None
+        }
+    }
+
+    async fn set_translation(&self, text: &str, style: &str, translated: &str) {
+        let key = cache_key(&self.namespace, text, style);
+
+        self.entries.lock().unwrap()
+            .insert(key, (translated.to_owned(), Instant::now() + self.ttl));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn should_return_cached_translation_for_same_text_and_style() {
+        let cache = InMemoryTranslationCache::new("test", Duration::from_secs(60));
+
+        cache.set_translation("text", "shakespeare", "translated").await;
+
+        assert_eq!(cache.get_translation("text", "shakespeare").await, Some("translated".to_owned()));
+        assert_eq!(cache.get_translation("text", "yoda").await, None);
+    }
+
+    #[actix_rt::test]
+    async fn should_forget_expired_translation() {
+        let cache = InMemoryTranslationCache::new("test", Duration::from_secs(0));
+
+        cache.set_translation("text", "shakespeare", "translated").await;
+
+        assert_eq!(cache.get_translation("text", "shakespeare").await, None);
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod in_memory_translation_cache;
+pub mod redis_translation_cache;
+pub mod translation_cache;
@@ -0,0 +1,175 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use actix_web::web;
+use async_trait::async_trait;
+use log::warn;
+use redis::{Client, Commands, Connection, RedisResult};
+
+use crate::cache::translation_cache::{cache_key, TranslationCache};
+
+const CONNECTION_TIMEOUT: Duration = Duration::from_millis(500);
+const MIN_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_IDLE_CONNECTIONS: usize = 8;
+
+/// Idle connections, and when connecting may be tried again after a failure.
+/// Only held for bookkeeping, never while talking to Redis.
+#[derive(Default)]
+struct ConnectionPool {
+    idle: Vec<Connection>,
+    failed_connects: u32,
+    retry_at: Option<Instant>,
+}
+
+impl ConnectionPool {
+    /// An idle connection, `None` if a new one may be opened, or an error while
+    /// reconnecting is backing off.
+    fn checkout(&mut self, now: Instant) -> RedisResult<Option<Connection>> {
+        if let Some(connection) = self.idle.pop() {
+            return Ok(Some(connection));
+        }
+
+        match self.retry_at {
+            Some(retry_at) if now < retry_at => Err((redis::ErrorKind::IoError, "Redis is unreachable, waiting to reconnect").into()),
+            _ => Ok(None)
+        }
+    }
+
+    fn checkin(&mut self, connection: Connection) {
+        if self.idle.len() < MAX_IDLE_CONNECTIONS {
+            self.idle.push(connection);
+        }
+    }
+
+    fn connected(&mut self) {
+        self.failed_connects = 0;
+        self.retry_at = None;
+    }
+
+    /// Doubles the wait after each consecutive failure, up to [`MAX_RECONNECT_BACKOFF`].
+    fn connect_failed(&mut self, now: Instant) {
+        self.failed_connects += 1;
+        let backoff = MIN_RECONNECT_BACKOFF * 2u32.pow((self.failed_connects - 1).min(5));
+        self.retry_at = Some(now + backoff.min(MAX_RECONNECT_BACKOFF));
+    }
+}
+
+/// Shares translations between replicas through Redis. Connections are opened
+/// lazily and dropped on any error, and reconnecting backs off while Redis is
+/// down, so an outage only costs cache misses.
+pub struct RedisTranslationCache {
+    client: Client,
+    namespace: String,
+    ttl: Duration,
+    pool: Arc<Mutex<ConnectionPool>>,
+}
+
+impl RedisTranslationCache {
+    pub fn new(url: &str, namespace: &str, ttl: Duration) -> RedisResult<Self> {
+        Ok(RedisTranslationCache {
+            client: Client::open(url)?,
+            namespace: namespace.to_owned(),
+            ttl,
+            pool: Arc::new(Mutex::new(ConnectionPool::default())),
+        })
+    }
+
+    async fn with_connection<T, F>(&self, operation: F) -> RedisResult<T>
+        where T: Send + 'static,
+              F: FnOnce(&mut Connection) -> RedisResult<T> + Send + 'static {
+        let client = self.client.clone();
+        let pool = self.pool.clone();
+
+        web::block(move || {
+            let idle = pool.lock().unwrap().checkout(Instant::now())?;
+
+            let mut connection = match idle {
+                Some(connection) => connection,
+                None => match connect(&client) {
+                    Ok(connection) => {
+                        pool.lock().unwrap().connected();
+                        connection
+                    }
+                    Err(error) => {
+                        pool.lock().unwrap().connect_failed(Instant::now());
+                        return Err(error);
+                    }
+                }
+            };
+
+            let result = operation(&mut connection);
+            if result.is_ok() {
+                pool.lock().unwrap().checkin(connection);
+            }
+            result
+        }).await.map_err(|error| match error {
+            actix_web::error::BlockingError::Error(error) => error,
+            actix_web::error::BlockingError::Canceled => (redis::ErrorKind::IoError, "Redis operation was canceled").into(),
+        })
+    }
+}
+
+/// Times out reads and writes as well, so a stalled Redis cannot hold a blocking thread.
+fn connect(client: &Client) -> RedisResult<Connection> {
+    let connection = client.get_connection_with_timeout(CONNECTION_TIMEOUT)?;
+    connection.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    connection.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+    Ok(connection)
+}
+
+#[async_trait]
+impl TranslationCache for RedisTranslationCache {
+    async fn get_translation(&self, text: &str, style: &str) -> Option<String> {
+        let key = cache_key(&self.namespace, text, style);
+
+        self.with_connection(move |connection| connection.get::<_, Option<String>>(key)).await
+            .unwrap_or_else(|error| {
+                warn!("Translation cache is unreachable, skipping lookup: {:?}", error);
+                None
+            })
+    }
+
+    async fn set_translation(&self, text: &str, style: &str, translated: &str) {
+        let key = cache_key(&self.namespace, text, style);
+        let translated = translated.to_owned();
+        let ttl = self.ttl.as_secs().max(1) as usize;
+
+        if let Err(error) = self.with_connection(move |connection| connection.set_ex::<_, _, ()>(key, translated, ttl)).await {
+            warn!("Translation cache is unreachable, skipping write: {:?}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn should_behave_like_an_empty_cache_when_redis_is_unreachable() {
+        let cache = RedisTranslationCache::new("redis://127.0.0.1:1/", "test", Duration::from_secs(60)).unwrap();
+
+        cache.set_translation("text", "shakespeare", "translated").await;
+
+        assert_eq!(cache.get_translation("text", "shakespeare").await, None);
+    }
+
+    #[test]
+    fn should_back_off_between_failed_reconnects() {
+        let mut pool = ConnectionPool::default();
+        let start = Instant::now();
+
+        assert!(matches!(pool.checkout(start), Ok(None)));
+
+        pool.connect_failed(start);
+        assert!(pool.checkout(start + Duration::from_millis(500)).is_err());
+        assert!(matches!(pool.checkout(start + Duration::from_secs(1)), Ok(None)));
+
+        pool.connect_failed(start + Duration::from_secs(1));
+        assert!(pool.checkout(start + Duration::from_millis(2500)).is_err());
+        assert!(matches!(pool.checkout(start + Duration::from_secs(3)), Ok(None)));
+
+        pool.connected();
+        assert!(matches!(pool.checkout(start + Duration::from_secs(3)), Ok(None)));
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+
+use crate::store::translation_store::hash_source_text;
+
+/// A best-effort cache in front of the translator. Implementations swallow their
+/// own failures so that an unreachable cache behaves like an empty one.
+#[async_trait]
+pub trait TranslationCache: Send + Sync {
+    async fn get_translation(&self, text: &str, style: &str) -> Option<String>;
+
+    async fn set_translation(&self, text: &str, style: &str, translated: &str);
+}
+
+pub fn cache_key(namespace: &str, text: &str, style: &str) -> String {
+    format!("{}:translation:{}:{}", namespace, style, hash_source_text(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_namespace_key_by_style_and_text_hash() {
+        let key = cache_key("shakespearemon", "text", "shakespeare");
+
+        assert_eq!(key, format!("shakespearemon:translation:shakespeare:{}", hash_source_text("text")));
+    }
+}
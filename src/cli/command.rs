@@ -0,0 +1,146 @@
+pub const USAGE: &str = "Usage:
+    shakespearemon [serve]
+    shakespearemon translate <name>
+    shakespearemon export --from <id> --to <id> [--format csv|json|ndjson] [--output <path>]";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            "ndjson" => Some(ExportFormat::Ndjson),
+            _ => None
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    pub from: u16,
+    pub to: u16,
+    pub format: ExportFormat,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Serve,
+    Translate { name: String },
+    Export(ExportOptions),
+    Help,
+}
+
+impl Command {
+    /// Parses the arguments after the program name. No subcommand means `serve`.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+        let mut args = args.into_iter();
+
+        match args.next().as_deref() {
+            None | Some("serve") => Ok(Command::Serve),
+            Some("translate") => {
+                let name = args.next().ok_or("translate needs a Pokémon name")?;
+                match args.next() {
+                    Some(unexpected) => Err(format!("Unexpected argument: {}", unexpected)),
+                    None => Ok(Command::Translate { name: name.to_lowercase() })
+                }
+            }
+            Some("export") => parse_export_options(args).map(Command::Export),
+            Some("help") | Some("--help") | Some("-h") => Ok(Command::Help),
+            Some(unknown) => Err(format!("Unknown command: {}", unknown)),
+        }
+    }
+}
+
+fn parse_export_options<I: Iterator<Item = String>>(mut args: I) -> Result<ExportOptions, String> {
+    let mut from = None;
+    let mut to = None;
+    let mut format = ExportFormat::Json;
+    let mut output = None;
+
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or(format!("{} needs a value", flag))?;
+
+        match flag.as_str() {
+            "--from" => from = Some(parse_species_id(&value)?),
+            "--to" => to = Some(parse_species_id(&value)?),
+            "--format" => format = ExportFormat::parse(&value).ok_or(format!("Unsupported format: {}", value))?,
+            "--output" | "-o" => output = Some(value),
+            _ => return Err(format!("Unknown option: {}", flag)),
+        }
+    }
+
+    let from = from.ok_or("export needs --from")?;
+    let to = to.ok_or("export needs --to")?;
+    if from > to {
+        return Err(format!("--from {} is after --to {}", from, to));
+    }
+
+    Ok(ExportOptions {
+        from,
+        to,
+        format,
+        output: output.unwrap_or_else(|| format!("shakespearemon-{}-{}.{}", from, to, format.extension())),
+    })
+}
+
+fn parse_species_id(value: &str) -> Result<u16, String> {
+    value.parse().ok()
+        .filter(|id| *id >= 1)
+        .ok_or(format!("Invalid species id: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        Command::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn should_serve_without_a_subcommand() {
+        assert_eq!(parse(&[]), Ok(Command::Serve));
+        assert_eq!(parse(&["serve"]), Ok(Command::Serve));
+    }
+
+    #[test]
+    fn should_parse_translate_with_a_name() {
+        assert_eq!(parse(&["translate", "Pikachu"]), Ok(Command::Translate { name: "pikachu".to_owned() }));
+        assert!(parse(&["translate"]).is_err());
+    }
+
+    #[test]
+    fn should_parse_export_with_a_default_output_file() {
+        let command = parse(&["export", "--from", "1", "--to", "151", "--format", "csv"]);
+
+        assert_eq!(command, Ok(Command::Export(ExportOptions {
+            from: 1,
+            to: 151,
+            format: ExportFormat::Csv,
+            output: "shakespearemon-1-151.csv".to_owned(),
+        })));
+    }
+
+    #[test]
+    fn should_reject_invalid_export_options() {
+        assert!(parse(&["export", "--from", "10", "--to", "1"]).is_err());
+        assert!(parse(&["export", "--from", "1", "--to", "2", "--format", "xml"]).is_err());
+        assert!(parse(&["export", "--from", "1"]).is_err());
+        assert!(parse(&["export", "--from", "0", "--to", "2"]).is_err());
+    }
+}
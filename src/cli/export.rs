@@ -0,0 +1,127 @@
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::command::ExportFormat;
+use crate::poke::species_source::SpeciesSource;
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::translation_pipeline::TranslationPipeline;
+use crate::translation_service::{describe_species, ShakespearemonException, ShakespearemonQuery};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportRecord {
+    pub id: u16,
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ExportSummary {
+    pub failed: Vec<u16>,
+    pub stopped_on_quota: bool,
+}
+
+/// Translates species `from..=to` in id order. A spent quota ends the export
+/// early, keeping what was translated so far.
+pub async fn export_species(species_source: &dyn SpeciesSource, translation_pipeline: &TranslationPipeline, from: u16, to: u16) -> (Vec<ExportRecord>, ExportSummary) {
+    let mut records = Vec::new();
+    let mut summary = ExportSummary::default();
+
+    for id in from..=to {
+        match export_one(species_source, translation_pipeline, id).await {
+            Ok(record) => records.push(record),
+            Err(ShakespearemonException::ShakespeareClientException(ShakespeareClientException::QuotaExhausted { .. })) => {
+                summary.stopped_on_quota = true;
+                break;
+            }
+            Err(_) => summary.failed.push(id),
+        }
+    }
+
+    (records, summary)
+}
+
+async fn export_one(species_source: &dyn SpeciesSource, translation_pipeline: &TranslationPipeline, id: u16) -> Result<ExportRecord, ShakespearemonException> {
+    let poke_species_response = species_source.get_pokemon_species(&id.to_string()).await
+        .map_err(|error| {
+            ShakespearemonException::PokeClientException(error)
+        })?;
+
+    let name = poke_species_response.name.to_owned();
+    let shakespearemon_response = describe_species(translation_pipeline, poke_species_response, name, None, &ShakespearemonQuery::default()).await?;
+
+    Ok(ExportRecord {
+        id,
+        name: shakespearemon_response.name,
+        description: shakespearemon_response.description,
+    })
+}
+
+pub fn write_records<W: Write>(mut writer: W, format: ExportFormat, records: &[ExportRecord]) -> io::Result<()> {
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, records)?;
+            writeln!(writer)?;
+        }
+        ExportFormat::Ndjson => {
+            for record in records {
+                serde_json::to_writer(&mut writer, record)?;
+                writeln!(writer)?;
+            }
+        }
+        ExportFormat::Csv => {
+            writeln!(writer, "id,name,description")?;
+            for record in records {
+                writeln!(writer, "{},{},{}", record.id, csv_field(&record.name), csv_field(&record.description))?;
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<ExportRecord> {
+        vec![
+            ExportRecord { id: 1, name: "bulbasaur".to_owned(), description: "A strange seed, planted at birth.".to_owned() },
+            ExportRecord { id: 25, name: "pikachu".to_owned(), description: "It saith \"pika\".".to_owned() },
+        ]
+    }
+
+    fn write(format: ExportFormat) -> String {
+        let mut output = Vec::new();
+        write_records(&mut output, format, &records()).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn should_quote_csv_fields_with_commas_and_quotes() {
+        assert_eq!(write(ExportFormat::Csv), "id,name,description\n1,bulbasaur,\"A strange seed, planted at birth.\"\n25,pikachu,\"It saith \"\"pika\"\".\"\n");
+    }
+
+    #[test]
+    fn should_write_one_json_object_per_ndjson_line() {
+        let output = write(ExportFormat::Ndjson);
+        let lines: Vec<ExportRecord> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+        assert_eq!(lines, records());
+    }
+
+    #[test]
+    fn should_write_a_json_array() {
+        let output: Vec<ExportRecord> = serde_json::from_str(&write(ExportFormat::Json)).unwrap();
+
+        assert_eq!(output, records());
+    }
+}
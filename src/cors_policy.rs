@@ -0,0 +1,79 @@
+use actix_cors::Cors;
+
+use crate::settings;
+
+/// Builds the CORS middleware. Origins are matched with [`origin_matches`], so
+/// `https://*.example.com` admits every subdomain of example.com.
+pub fn cors_middleware(cors: &settings::Cors) -> Cors {
+    let mut middleware = Cors::default();
+
+    if cors.allowed_origins.iter().any(|origin| origin == "*") {
+        middleware = middleware.allow_any_origin();
+    } else {
+        let allowed_origins = cors.allowed_origins.clone();
+        middleware = middleware.allowed_origin_fn(move |origin, _| {
+            origin.to_str()
+                .map(|origin| allowed_origins.iter().any(|pattern| origin_matches(pattern, origin)))
+                .unwrap_or(false)
+        });
+    }
+
+    middleware = middleware
+        .allowed_methods(cors.allowed_methods.iter().map(String::as_str))
+        .allowed_headers(cors.allowed_headers.iter().map(String::as_str))
+        .expose_headers(cors.exposed_headers.iter().map(String::as_str))
+        .max_age(cors.max_age_seconds);
+
+    if cors.supports_credentials {
+        middleware = middleware.supports_credentials();
+    }
+
+    middleware
+}
+
+/// Compares an `Origin` header against a configured origin. A `*.` right after
+/// the scheme matches one or more subdomain labels, but never the bare domain.
+pub fn origin_matches(pattern: &str, origin: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/');
+
+    match pattern.split_once("://*.") {
+        Some((scheme, domain)) => {
+            origin.strip_prefix(scheme)
+                .and_then(|origin| origin.strip_prefix("://"))
+                .and_then(|host| host.strip_suffix(domain))
+                .and_then(|subdomain| subdomain.strip_suffix('.'))
+                .map(|subdomain| !subdomain.is_empty() && !subdomain.contains(['/', ':', '@']))
+                .unwrap_or(false)
+        }
+        None => pattern.eq_ignore_ascii_case(origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_match_exact_origins() {
+        assert!(origin_matches("https://shakespearemon.dev", "https://shakespearemon.dev"));
+        assert!(origin_matches("https://shakespearemon.dev/", "https://shakespearemon.dev"));
+        assert!(!origin_matches("https://shakespearemon.dev", "http://shakespearemon.dev"));
+        assert!(!origin_matches("https://shakespearemon.dev", "https://shakespearemon.dev:8443"));
+    }
+
+    #[test]
+    fn should_match_any_subdomain_of_a_wildcard_origin() {
+        assert!(origin_matches("https://*.shakespearemon.dev", "https://app.shakespearemon.dev"));
+        assert!(origin_matches("https://*.shakespearemon.dev", "https://eu.app.shakespearemon.dev"));
+        assert!(origin_matches("https://*.shakespearemon.dev:8443", "https://app.shakespearemon.dev:8443"));
+    }
+
+    #[test]
+    fn should_not_let_wildcards_match_lookalike_origins() {
+        assert!(!origin_matches("https://*.shakespearemon.dev", "https://shakespearemon.dev"));
+        assert!(!origin_matches("https://*.shakespearemon.dev", "https://evilshakespearemon.dev"));
+        assert!(!origin_matches("https://*.shakespearemon.dev", "http://app.shakespearemon.dev"));
+        assert!(!origin_matches("https://*.shakespearemon.dev", "https://app.shakespearemon.dev:8443"));
+        assert!(!origin_matches("https://*.shakespearemon.dev", "https://evil.com/.shakespearemon.dev"));
+    }
+}
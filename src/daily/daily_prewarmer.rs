@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::rt::{spawn, time::delay_for};
+use chrono::NaiveDate;
+use log::{info, warn};
+
+use crate::daily::daily_selection::{seconds_until_next_midnight, today};
+use crate::daily_pokemon_service::get_daily_pokemon;
+use crate::poke::species_source::SpeciesSource;
+use crate::settings::Daily;
+use crate::translation_pipeline::TranslationPipeline;
+use crate::translation_service::{ShakespearemonException, ShakespearemonQuery, ShakespearemonResponse};
+
+/// Translates each day's featured species right after midnight UTC, so the
+/// first visitor of the day is served from the translation cache.
+pub struct DailyPrewarmer {
+    species_source: Arc<dyn SpeciesSource>,
+    translation_pipeline: TranslationPipeline,
+    seed: u64,
+    species_count: u16,
+}
+
+impl DailyPrewarmer {
+    pub fn new(species_source: Arc<dyn SpeciesSource>, translation_pipeline: TranslationPipeline, daily: &Daily) -> Self {
+        DailyPrewarmer {
+            species_source,
+            translation_pipeline,
+            seed: daily.seed,
+            species_count: daily.species_count,
+        }
+    }
+
+    pub async fn prewarm(&self, date: NaiveDate) -> Result<ShakespearemonResponse, ShakespearemonException> {
+        get_daily_pokemon(self.species_source.as_ref(), &self.translation_pipeline, date, self.seed, self.species_count, &ShakespearemonQuery::default()).await
+    }
+
+    pub fn start(self) {
+        spawn(async move {
+            loop {
+                delay_for(Duration::from_secs(seconds_until_next_midnight())).await;
+
+                let date = today();
+                match self.prewarm(date).await {
+                    Ok(response) => info!("Pre-warmed the Pokémon of the day for {}: {}", date, response.name),
+                    Err(error) => warn!("Pre-warming the Pokémon of the day for {} failed: {:?}", date, error),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,77 @@
+use chrono::{NaiveDate, Utc};
+use sha2::{Digest, Sha256};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Picks the featured species id for a date, in `1..=species_count`.
+///
+/// The same date and seed always give the same species, so every replica
+/// agrees without coordinating.
+pub fn select_species_id(date: NaiveDate, seed: u64, species_count: u16) -> u16 {
+    let digest = Sha256::digest(format!("{}:{}", seed, date.format(DATE_FORMAT)).as_bytes());
+
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+
+    (u64::from_be_bytes(bytes) % u64::from(species_count.max(1))) as u16 + 1
+}
+
+pub fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, DATE_FORMAT).ok()
+}
+
+pub fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+pub fn seconds_until_next_midnight() -> u64 {
+    let now = Utc::now().timestamp();
+    (SECONDS_PER_DAY - now.rem_euclid(SECONDS_PER_DAY)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> NaiveDate {
+        parse_date(date).unwrap()
+    }
+
+    #[test]
+    fn should_select_the_same_species_for_the_same_date_and_seed() {
+        assert_eq!(select_species_id(date("2021-03-14"), 7, 898), select_species_id(date("2021-03-14"), 7, 898));
+    }
+
+    #[test]
+    fn should_stay_within_the_species_range() {
+        let mut day = date("2021-01-01");
+
+        for _ in 0..366 {
+            let species_id = select_species_id(day, 0, 151);
+            assert!((1..=151).contains(&species_id));
+            day = day.succ_opt().unwrap();
+        }
+    }
+
+    #[test]
+    fn should_reshuffle_when_the_seed_changes() {
+        let mut day = date("2021-01-01");
+        let mut differences = 0;
+
+        for _ in 0..30 {
+            if select_species_id(day, 1, 898) != select_species_id(day, 2, 898) {
+                differences += 1;
+            }
+            day = day.succ_opt().unwrap();
+        }
+
+        assert!(differences > 20);
+    }
+
+    #[test]
+    fn should_reject_malformed_dates() {
+        assert_eq!(parse_date("14/03/2021"), None);
+        assert_eq!(parse_date("2021-02-30"), None);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod daily_prewarmer;
+pub mod daily_selection;
@@ -0,0 +1,92 @@
+use actix_web::{get, HttpRequest, HttpResponse, web};
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::daily::daily_selection::{parse_date, seconds_until_next_midnight, select_species_id, today};
+use crate::http_caching::{compute_etag, etag_source, respond_if_not_modified, respond_with_caching, respond_without_caching};
+use crate::negotiation::negotiated_exception::NegotiatedException;
+use crate::negotiation::response_format::ResponseFormat;
+use crate::poke::poke_species_response::PokeSpeciesResponse;
+use crate::poke::species_source::SpeciesSource;
+use crate::settings::Settings;
+use crate::translation_pipeline::TranslationPipeline;
+use crate::translation_service::{describe_species, ShakespearemonException, ShakespearemonQuery, ShakespearemonResponse};
+
+#[derive(Default, Deserialize)]
+pub struct DailyPokemonQuery {
+    pub date: Option<String>,
+}
+
+/// Must be registered before `/pokemon/{name}`, which would otherwise match it.
+#[utoipa::path(
+    get,
+    path = "/v1/pokemon/daily",
+    tag = "pokemon",
+    params(
+        ("date" = Option<String>, Query, description = "The day to pick for, as YYYY-MM-DD; defaults to today"),
+        ("include" = Option<String>, Query, description = "Comma-separated extras: `metadata`"),
+        ("translate_genus" = Option<bool>, Query, description = "Also translate the genus when metadata is included"),
+    ),
+    responses(
+        (status = 200, description = "The pokemon of the day in Shakespearean English", body = ShakespearemonResponse),
+        (status = 304, description = "The client's copy, named by `If-None-Match`, is current"),
+        (status = 400, description = "The date is not formatted as YYYY-MM-DD", body = ErrorResponse),
+        (status = 406, description = "None of the accepted media types can be produced", body = ErrorResponse),
+        (status = 429, description = "The translation quota is spent", body = ErrorResponse),
+        (status = 500, description = "An upstream service failed", body = ErrorResponse),
+        (status = 502, description = "The translator rejected our credentials", body = ErrorResponse),
+    )
+)]
+#[get("/pokemon/daily")]
+pub async fn get_pokemon_of_the_day(request: HttpRequest, data: web::Data<Settings>, species_source: web::Data<dyn SpeciesSource>, translation_pipeline: TranslationPipeline, web::Query(daily_query): web::Query<DailyPokemonQuery>, web::Query(query): web::Query<ShakespearemonQuery>) -> Result<HttpResponse, NegotiatedException> {
+    let format = ResponseFormat::from_request(&request).ok_or_else(NegotiatedException::not_acceptable)?;
+
+    let date = match &daily_query.date {
+        Some(date) => parse_date(date).ok_or_else(|| NegotiatedException::new(ShakespearemonException::InvalidDate, format))?,
+        None => today(),
+    };
+
+    // Today's pick changes at midnight; past days never change.
+    let max_age_seconds = match daily_query.date {
+        Some(_) => data.application.cache_max_age_seconds,
+        None => data.application.cache_max_age_seconds.min(seconds_until_next_midnight()),
+    };
+
+    let poke_species_response = get_daily_species(species_source.as_ref(), date, data.daily.seed, data.daily.species_count).await
+        .map_err(|error| {
+            NegotiatedException::new(error, format)
+        })?;
+
+    let etag = compute_etag(format, &[&etag_source(&poke_species_response), &query.etag_source(), &data.application.translator_identity()]);
+    if let Some(not_modified) = respond_if_not_modified(&request, &etag, max_age_seconds) {
+        return Ok(not_modified);
+    }
+
+    let name = poke_species_response.name.to_owned();
+    let shakespearemon_response = describe_species(&translation_pipeline, poke_species_response, name, None, &query).await
+        .map_err(|error| {
+            NegotiatedException::new(error, format)
+        })?;
+
+    if translation_pipeline.served_stand_in() {
+        return Ok(respond_without_caching(format, &shakespearemon_response));
+    }
+
+    Ok(respond_with_caching(format, etag, &shakespearemon_response, max_age_seconds))
+}
+
+pub async fn get_daily_pokemon(species_source: &dyn SpeciesSource, translation_pipeline: &TranslationPipeline, date: NaiveDate, seed: u64, species_count: u16, query: &ShakespearemonQuery) -> Result<ShakespearemonResponse, ShakespearemonException> {
+    let poke_species_response = get_daily_species(species_source, date, seed, species_count).await?;
+
+    let name = poke_species_response.name.to_owned();
+    describe_species(translation_pipeline, poke_species_response, name, None, query).await
+}
+
+async fn get_daily_species(species_source: &dyn SpeciesSource, date: NaiveDate, seed: u64, species_count: u16) -> Result<PokeSpeciesResponse, ShakespearemonException> {
+    let species_id = select_species_id(date, seed, species_count);
+
+    species_source.get_pokemon_species(&species_id.to_string()).await
+        .map_err(|error| {
+            ShakespearemonException::PokeClientException(error)
+        })
+}
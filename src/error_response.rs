@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The JSON body of every error: a stable machine-readable code plus a message for people.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    #[schema(example = "pokemon_not_found")]
+    pub error: String,
+    #[schema(example = "Pokemon Not Found")]
+    pub message: String,
+}
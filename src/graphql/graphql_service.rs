@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use actix_web::{get, HttpRequest, HttpResponse, post, web};
+use async_graphql::dataloader::DataLoader;
+use async_graphql::http::{GraphQLPlaygroundConfig, playground_source};
+use async_graphql_actix_web::{Request, Response};
+
+use crate::auth::api_key_registry::MeteredApiKey;
+use crate::graphql::schema::ShakespearemonSchema;
+use crate::graphql::species_loader::SpeciesLoader;
+use crate::graphql::translation_loader::TranslationLoader;
+use crate::poke::species_source::SpeciesSource;
+use crate::translation_pipeline::TranslationPipeline;
+
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+/// Loaders are created per request, so batching never spans two clients. The
+/// caller's metered key rides along so `pokemons` can charge it per name.
+#[utoipa::path(
+    post,
+    path = "/graphql",
+    tag = "graphql",
+    responses(
+        (status = 200, description = "The GraphQL response; failed fields are listed under `errors`"),
+    )
+)]
+#[post("/graphql")]
+pub async fn execute_graphql(http_request: HttpRequest, schema: web::Data<ShakespearemonSchema>, species_source: web::Data<dyn SpeciesSource>, translation_pipeline: TranslationPipeline, request: Request) -> Response {
+    let mut request = request.into_inner()
+        .data(DataLoader::new(SpeciesLoader::new(Arc::clone(&species_source))))
+        .data(DataLoader::new(TranslationLoader::new(translation_pipeline)));
+    if let Some(metered_api_key) = http_request.extensions().get::<MeteredApiKey>().cloned() {
+        request = request.data(metered_api_key);
+    }
+
+    schema.execute(request).await.into()
+}
+
+#[utoipa::path(
+    get,
+    path = "/graphql",
+    tag = "graphql",
+    responses(
+        (status = 200, description = "The GraphQL playground", body = String, content_type = "text/html"),
+    )
+)]
+#[get("/graphql")]
+pub async fn get_graphql_playground() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(playground_source(GraphQLPlaygroundConfig::new(GRAPHQL_PATH)))
+}
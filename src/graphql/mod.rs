@@ -0,0 +1,4 @@
+pub mod graphql_service;
+pub mod schema;
+pub mod species_loader;
+pub mod translation_loader;
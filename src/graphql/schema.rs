@@ -0,0 +1,190 @@
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Error, ErrorExtensions, Object, Result, Schema, SimpleObject};
+use async_graphql::dataloader::DataLoader;
+use futures::future::try_join_all;
+
+use crate::auth::api_key_exception::ApiKeyException;
+use crate::auth::api_key_registry::MeteredApiKey;
+use crate::graphql::species_loader::SpeciesLoader;
+use crate::graphql::translation_loader::TranslationLoader;
+use crate::jobs::batch_job_exception::BatchJobException;
+use crate::poke::flavor_text_resource::extract_english_flavor_text;
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::poke_species_response::PokeSpeciesResponse;
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::species_metadata::SpeciesMetadata;
+use crate::text_translation_exception::TextTranslationException;
+use crate::translation_pipeline::SHAKESPEARE_STYLE;
+use crate::translation_service::ShakespearemonException;
+
+pub type ShakespearemonSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Deep enough for `pokemons { metadata { names { language } } }`.
+const MAX_QUERY_DEPTH: usize = 8;
+const MAX_QUERY_COMPLEXITY: usize = 256;
+
+/// Resolvers read a `DataLoader<SpeciesLoader>` and a `DataLoader<TranslationLoader>`
+/// from the request data, which `execute_graphql` attaches per request.
+/// `pokemons` takes at most `max_names` names, like a batch job, and charges
+/// the caller's [`MeteredApiKey`] one request per name when there is one.
+pub fn build_schema(max_names: usize) -> ShakespearemonSchema {
+    Schema::build(QueryRoot { max_names }, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Errors carry the same `code` extension as the REST error bodies.
+impl ErrorExtensions for ShakespearemonException {
+    fn extend(&self) -> Error {
+        let code = self.code();
+        Error::new(self.to_string()).extend_with(|_, extensions| extensions.set("code", code))
+    }
+}
+
+impl ErrorExtensions for ApiKeyException {
+    fn extend(&self) -> Error {
+        let code = self.code();
+        Error::new(self.to_string()).extend_with(|_, extensions| extensions.set("code", code))
+    }
+}
+
+pub struct QueryRoot {
+    max_names: usize,
+}
+
+#[Object]
+impl QueryRoot {
+    /// A species by name or national dex number.
+    async fn pokemon(&self, ctx: &Context<'_>, name: String) -> Result<Pokemon> {
+        load_pokemon(ctx, name).await
+    }
+
+    /// Several species at once, fetched from PokeAPI in a single batch.
+    async fn pokemons(&self, ctx: &Context<'_>, names: Vec<String>) -> Result<Vec<Pokemon>> {
+        if names.len() > self.max_names {
+            return Err(ShakespearemonException::BatchJobException(BatchJobException::TooManyNames { max_names: self.max_names }).extend());
+        }
+        if let Some(metered_api_key) = ctx.data_opt::<MeteredApiKey>() {
+            // The request itself already paid for one name on the way in.
+            metered_api_key.charge(names.len().saturating_sub(1) as u32).await
+                .map_err(|error| error.extend())?;
+        }
+
+        try_join_all(names.into_iter().map(|name| load_pokemon(ctx, name))).await
+    }
+}
+
+async fn load_pokemon(ctx: &Context<'_>, name: String) -> Result<Pokemon> {
+    let species = ctx.data::<DataLoader<SpeciesLoader>>()?
+        .load_one(name.to_owned()).await?
+        .unwrap_or(Err(PokeClientException::PokemonNotFound))
+        .map_err(|error| ShakespearemonException::PokeClientException(error).extend())?;
+
+    Ok(Pokemon {
+        name,
+        species,
+    })
+}
+
+/// Translates through the request's `TranslationLoader`, so the translator is
+/// only reached by fields that ask for a translation.
+async fn translate(ctx: &Context<'_>, text: String, style: Option<String>) -> Result<String> {
+    if style.as_deref().unwrap_or(SHAKESPEARE_STYLE) != SHAKESPEARE_STYLE {
+        return Err(ShakespearemonException::TextTranslationException(TextTranslationException::UnsupportedStyle).extend());
+    }
+
+    ctx.data::<DataLoader<TranslationLoader>>()?
+        .load_one(text).await?
+        .unwrap_or(Err(ShakespearemonException::ShakespeareClientException(ShakespeareClientException::ShakespeareClientWentWrong)))
+        .map_err(|error| error.extend())
+}
+
+pub struct Pokemon {
+    name: String,
+    species: PokeSpeciesResponse,
+}
+
+impl Pokemon {
+    fn original_description(&self) -> Result<String> {
+        extract_english_flavor_text(&self.species)
+            .ok_or_else(|| ShakespearemonException::PokeClientException(PokeClientException::PokemonDescriptionNotFound).extend())
+    }
+}
+
+#[Object]
+impl Pokemon {
+    async fn name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    /// The English description as PokeAPI has it.
+    async fn original(&self) -> Result<String> {
+        self.original_description()
+    }
+
+    /// The description translated to `style`, `shakespeare` by default. Alias
+    /// the field to ask for several styles at once.
+    async fn translated(&self, ctx: &Context<'_>, style: Option<String>) -> Result<String> {
+        translate(ctx, self.original_description()?, style).await
+    }
+
+    async fn metadata(&self) -> PokemonMetadata {
+        PokemonMetadata(SpeciesMetadata::from_poke_species_response(&self.species))
+    }
+}
+
+pub struct PokemonMetadata(SpeciesMetadata);
+
+#[derive(SimpleObject)]
+pub struct LocalizedName {
+    language: String,
+    name: String,
+}
+
+#[Object]
+impl PokemonMetadata {
+    async fn genus(&self) -> Option<String> {
+        self.0.genus.to_owned()
+    }
+
+    /// The genus translated to `style`, `shakespeare` by default.
+    async fn translated_genus(&self, ctx: &Context<'_>, style: Option<String>) -> Result<Option<String>> {
+        match &self.0.genus {
+            Some(genus) => Ok(Some(translate(ctx, genus.to_owned(), style).await?)),
+            None => Ok(None)
+        }
+    }
+
+    async fn generation(&self) -> Option<String> {
+        self.0.generation.to_owned()
+    }
+
+    async fn is_legendary(&self) -> bool {
+        self.0.is_legendary
+    }
+
+    async fn is_mythical(&self) -> bool {
+        self.0.is_mythical
+    }
+
+    async fn color(&self) -> Option<String> {
+        self.0.color.to_owned()
+    }
+
+    async fn habitat(&self) -> Option<String> {
+        self.0.habitat.to_owned()
+    }
+
+    async fn evolution_chain_url(&self) -> Option<String> {
+        self.0.evolution_chain_url.to_owned()
+    }
+
+    async fn names(&self) -> Vec<LocalizedName> {
+        self.0.names.iter()
+            .map(|(language, name)| LocalizedName {
+                language: language.to_owned(),
+                name: name.to_owned(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use async_graphql::dataloader::Loader;
+use async_trait::async_trait;
+use futures::future::join_all;
+
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::poke_species_response::PokeSpeciesResponse;
+use crate::poke::species_source::SpeciesSource;
+
+/// Fetches the species a GraphQL query asks for in one batch, once per distinct
+/// name. A species that fails only fails the fields that need it.
+pub struct SpeciesLoader {
+    species_source: Arc<dyn SpeciesSource>,
+}
+
+impl SpeciesLoader {
+    pub fn new(species_source: Arc<dyn SpeciesSource>) -> Self {
+        SpeciesLoader {
+            species_source
+        }
+    }
+}
+
+#[async_trait]
+impl Loader<String> for SpeciesLoader {
+    type Value = Result<PokeSpeciesResponse, PokeClientException>;
+    type Error = Infallible;
+
+    async fn load(&self, names: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let species = join_all(names.iter().map(|name| self.species_source.get_pokemon_species(name))).await;
+
+        Ok(names.iter().cloned().zip(species).collect())
+    }
+}
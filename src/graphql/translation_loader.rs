@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use async_graphql::dataloader::Loader;
+use async_trait::async_trait;
+use futures::future::join_all;
+
+use crate::translation_pipeline::TranslationPipeline;
+use crate::translation_service::ShakespearemonException;
+
+/// Translates the texts a GraphQL query selects in one batch, so species that
+/// share a description share one trip through the translation pipeline.
+pub struct TranslationLoader {
+    translation_pipeline: TranslationPipeline,
+}
+
+impl TranslationLoader {
+    pub fn new(translation_pipeline: TranslationPipeline) -> Self {
+        TranslationLoader {
+            translation_pipeline
+        }
+    }
+}
+
+#[async_trait]
+impl Loader<String> for TranslationLoader {
+    type Value = Result<String, ShakespearemonException>;
+    type Error = Infallible;
+
+    async fn load(&self, texts: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let translations = join_all(texts.iter().map(|text| self.translation_pipeline.translate(text))).await;
+
+        Ok(texts.iter().cloned().zip(translations).collect())
+    }
+}
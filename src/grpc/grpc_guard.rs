@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use tonic::{Request, Status};
+
+use crate::auth::api_key_middleware::API_KEY_HEADER;
+use crate::auth::api_key_registry::ApiKeyRegistry;
+use crate::rate_limit::ip_rate_limiter::IpRateLimiter;
+use crate::rate_limit::rate_limit_exception::RateLimitException;
+use crate::rate_limit::rate_limit_middleware::{FORWARDED_FOR_HEADER, join_forwarded_for};
+
+/// Holds gRPC calls to the same per-address rate limit and API keys as the
+/// REST API, reading the key from the `x-api-key` metadata. Each check is
+/// only applied once configured.
+#[derive(Clone, Default)]
+pub struct GrpcGuard {
+    ip_rate_limiter: Option<Arc<IpRateLimiter>>,
+    api_key_registry: Option<Arc<ApiKeyRegistry>>,
+}
+
+impl GrpcGuard {
+    pub fn with_ip_rate_limiter(mut self, ip_rate_limiter: Arc<IpRateLimiter>) -> Self {
+        self.ip_rate_limiter = Some(ip_rate_limiter);
+        self
+    }
+
+    pub fn with_api_key_registry(mut self, api_key_registry: Arc<ApiKeyRegistry>) -> Self {
+        self.api_key_registry = Some(api_key_registry);
+        self
+    }
+
+    /// Checks the caller's address first, so unauthenticated floods are limited too.
+    pub async fn admit<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        self.admit_many(request, 1).await
+    }
+
+    /// Like [`admit`](GrpcGuard::admit), but charges the API key `requests`
+    /// requests, for calls that fan out into that many translations.
+    pub async fn admit_many<T>(&self, request: &Request<T>, requests: usize) -> Result<(), Status> {
+        if let Some(ip_rate_limiter) = &self.ip_rate_limiter {
+            let forwarded_for = join_forwarded_for(request.metadata().get_all(FORWARDED_FOR_HEADER).iter()
+                .filter_map(|forwarded_for| forwarded_for.to_str().ok()));
+            let client = ip_rate_limiter.client_ip(request.remote_addr().map(|peer| peer.ip()), forwarded_for.as_deref());
+
+            if let Some(Err(retry_after)) = client.map(|client| ip_rate_limiter.check(client)) {
+                return Err(RateLimitException::TooManyRequests { retry_after }.into());
+            }
+        }
+
+        if let Some(api_key_registry) = &self.api_key_registry {
+            let key = request.metadata().get(API_KEY_HEADER)
+                .and_then(|key| key.to_str().ok());
+
+            api_key_registry.authorize_many(key, false, requests as u32).await?;
+        }
+
+        Ok(())
+    }
+}
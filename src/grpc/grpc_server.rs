@@ -0,0 +1,70 @@
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use log::{error, warn};
+use tokio::net::TcpListener;
+use tokio::runtime::Builder;
+use tokio::sync::oneshot;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+use crate::grpc::grpc_service::ShakespearemonGrpcService;
+use crate::grpc::proto::shakespearemon_server::ShakespearemonServer;
+
+/// Serves the gRPC API on `listener` until `shutdown` resolves.
+pub async fn serve_grpc<F: Future<Output = ()>>(listener: TcpListener, service: ShakespearemonGrpcService, shutdown: F) -> Result<(), tonic::transport::Error> {
+    Server::builder()
+        .add_service(ShakespearemonServer::new(service))
+        .serve_with_incoming_shutdown(TcpListenerStream::new(listener), shutdown)
+        .await
+}
+
+/// A gRPC server running on its own thread and tokio runtime, apart from the
+/// actix-web workers.
+pub struct GrpcServer {
+    shutdown: oneshot::Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl GrpcServer {
+    /// Binds before returning, so a taken port fails startup rather than the thread.
+    /// On stop, `TranslateBatch` streams get up to `shutdown_timeout` to finish
+    /// before the runtime, and with it their translations, is dropped.
+    pub fn start(addr: SocketAddr, service: ShakespearemonGrpcService, shutdown_timeout: Duration) -> io::Result<Self> {
+        let runtime = Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("shakespearemon-grpc")
+            .build()?;
+        let listener = runtime.block_on(TcpListener::bind(addr))?;
+
+        let (shutdown, stop) = oneshot::channel();
+        let background_tasks = service.background_tasks();
+        let thread = thread::spawn(move || {
+            let stop = async {
+                let _ = stop.await;
+            };
+            if let Err(error) = runtime.block_on(serve_grpc(listener, service, stop)) {
+                error!("gRPC server failed with an error: {:?}", error);
+            }
+            if !background_tasks.wait(shutdown_timeout) {
+                warn!("Dropping {} gRPC batch streams still running after the shutdown timeout", background_tasks.running());
+            }
+        });
+
+        Ok(GrpcServer {
+            shutdown,
+            thread,
+        })
+    }
+
+    /// Stops accepting calls and waits for in-flight ones, batch streams included, to finish.
+    pub fn stop(self) {
+        let _ = self.shutdown.send(());
+        if self.thread.join().is_err() {
+            error!("gRPC server thread panicked");
+        }
+    }
+}
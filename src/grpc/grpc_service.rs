@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+use crate::grpc::grpc_guard::GrpcGuard;
+use crate::grpc::proto::{BatchError, BatchItem, PokemonTranslation, TranslateBatchRequest, TranslatePokemonRequest, TranslateRequest, TranslateResponse};
+use crate::grpc::proto::batch_item::Result as BatchResult;
+use crate::grpc::proto::shakespearemon_server::Shakespearemon;
+use crate::jobs::background_tasks::BackgroundTasks;
+use crate::jobs::batch_job_exception::BatchJobException;
+use crate::poke::species_source::SpeciesSource;
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::text_translation_service::validate_text_translation_request;
+use crate::translation_pipeline::{SHAKESPEARE_STYLE, TranslationPipeline};
+use crate::translation_service::{describe_species_by_name, ShakespearemonException};
+
+/// How many translated batch items may wait for a slow client.
+const BATCH_BUFFER: usize = 16;
+
+/// Serves the gRPC API over the same species source and translation pipeline
+/// as the REST handlers, with the same text and batch size limits.
+#[derive(Clone)]
+pub struct ShakespearemonGrpcService {
+    species_source: Arc<dyn SpeciesSource>,
+    translation_pipeline: TranslationPipeline,
+    max_text_length: usize,
+    max_names: usize,
+    guard: GrpcGuard,
+    background_tasks: BackgroundTasks,
+}
+
+impl ShakespearemonGrpcService {
+    pub fn new(species_source: Arc<dyn SpeciesSource>, translation_pipeline: TranslationPipeline, max_text_length: usize, max_names: usize) -> Self {
+        ShakespearemonGrpcService {
+            species_source,
+            translation_pipeline,
+            max_text_length,
+            max_names,
+            guard: GrpcGuard::default(),
+            background_tasks: BackgroundTasks::new(),
+        }
+    }
+
+    pub fn with_guard(mut self, guard: GrpcGuard) -> Self {
+        self.guard = guard;
+        self
+    }
+
+    /// The `TranslateBatch` streams still translating, which [`GrpcServer`](crate::grpc::grpc_server::GrpcServer)
+    /// waits for on shutdown.
+    pub fn background_tasks(&self) -> BackgroundTasks {
+        self.background_tasks.clone()
+    }
+}
+
+#[tonic::async_trait]
+impl Shakespearemon for ShakespearemonGrpcService {
+    async fn translate(&self, request: Request<TranslateRequest>) -> Result<Response<TranslateResponse>, Status> {
+        self.guard.admit(&request).await?;
+        let TranslateRequest { text, style } = request.into_inner();
+        let style = if style.is_empty() { SHAKESPEARE_STYLE.to_owned() } else { style };
+
+        validate_text_translation_request(&text, &style, self.max_text_length)
+            .map_err(ShakespearemonException::TextTranslationException)?;
+
+        let translated = self.translation_pipeline.translate(&text).await?;
+
+        Ok(Response::new(TranslateResponse {
+            text,
+            style,
+            translated,
+        }))
+    }
+
+    async fn translate_pokemon(&self, request: Request<TranslatePokemonRequest>) -> Result<Response<PokemonTranslation>, Status> {
+        self.guard.admit(&request).await?;
+        let TranslatePokemonRequest { name } = request.into_inner();
+
+        let shakespearemon_response = describe_species_by_name(self.species_source.as_ref(), &self.translation_pipeline, name).await?;
+
+        Ok(Response::new(PokemonTranslation {
+            name: shakespearemon_response.name,
+            description: shakespearemon_response.description,
+        }))
+    }
+
+    type TranslateBatchStream = ReceiverStream<Result<BatchItem, Status>>;
+
+    /// Translates one species at a time, so the stream spends quota no faster
+    /// than the `export` command. It stops early if the client goes away, and
+    /// after the item that found the quota spent.
+    async fn translate_batch(&self, request: Request<TranslateBatchRequest>) -> Result<Response<Self::TranslateBatchStream>, Status> {
+        let requested_names = request.get_ref().names.len();
+        if requested_names > self.max_names {
+            return Err(ShakespearemonException::BatchJobException(BatchJobException::TooManyNames { max_names: self.max_names }).into());
+        }
+        // Each name is a translation, so each is charged to the caller's key.
+        self.guard.admit_many(&request, requested_names.max(1)).await?;
+
+        let TranslateBatchRequest { names } = request.into_inner();
+
+        let (sender, receiver) = mpsc::channel(BATCH_BUFFER);
+        let service = self.clone();
+
+        tokio::spawn(self.background_tasks.track(async move {
+            for name in names {
+                let described = describe_species_by_name(service.species_source.as_ref(), &service.translation_pipeline, name.to_owned()).await;
+                let quota_exhausted = matches!(described, Err(ShakespearemonException::ShakespeareClientException(ShakespeareClientException::QuotaExhausted { .. })));
+
+                let result = match described {
+                    Ok(shakespearemon_response) => BatchResult::Description(shakespearemon_response.description),
+                    Err(error) => BatchResult::Error(BatchError {
+                        code: error.code().to_owned(),
+                        message: error.to_string(),
+                    }),
+                };
+
+                let item = BatchItem {
+                    name,
+                    result: Some(result),
+                };
+                if sender.send(Ok(item)).await.is_err() || quota_exhausted {
+                    return;
+                }
+            }
+        }));
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
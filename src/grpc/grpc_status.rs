@@ -0,0 +1,73 @@
+use tonic::{Code, Status};
+use tonic::metadata::{MetadataMap, MetadataValue};
+
+use crate::auth::api_key_exception::ApiKeyException;
+use crate::jobs::batch_job_exception::BatchJobException;
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::rate_limit::rate_limit_exception::RateLimitException;
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::store::translation_store_exception::TranslationStoreException;
+use crate::text_translation_exception::TextTranslationException;
+use crate::translation_service::ShakespearemonException;
+
+pub const ERROR_CODE_METADATA: &str = "shakespearemon-error";
+
+impl From<ShakespearemonException> for Status {
+    fn from(error: ShakespearemonException) -> Self {
+        let code = match error {
+            ShakespearemonException::PokeClientException(PokeClientException::PokeClientWentWrong) => Code::Internal,
+            ShakespearemonException::PokeClientException(PokeClientException::PokemonNotFound) => Code::NotFound,
+            ShakespearemonException::PokeClientException(PokeClientException::PokemonDescriptionNotFound) => Code::NotFound,
+            ShakespearemonException::PokeClientException(PokeClientException::ResourceNotFound) => Code::NotFound,
+            ShakespearemonException::PokeClientException(PokeClientException::ResourceDescriptionNotFound) => Code::NotFound,
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::TranslationNotFound) => Code::NotFound,
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::ShakespeareClientWentWrong) => Code::Internal,
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::TranslatorMisconfigured) => Code::Unavailable,
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::QuotaExhausted { .. }) => Code::ResourceExhausted,
+            ShakespearemonException::TranslationStoreException(TranslationStoreException::TranslationStoreUnavailable) => Code::Unavailable,
+            ShakespearemonException::TranslationStoreException(TranslationStoreException::TranslationStoreWentWrong) => Code::Internal,
+            ShakespearemonException::TextTranslationException(TextTranslationException::EmptyText) => Code::InvalidArgument,
+            ShakespearemonException::TextTranslationException(TextTranslationException::UnsupportedStyle) => Code::InvalidArgument,
+            ShakespearemonException::TextTranslationException(TextTranslationException::InvalidRequest) => Code::InvalidArgument,
+            ShakespearemonException::TextTranslationException(TextTranslationException::TextTooLong { .. }) => Code::InvalidArgument,
+            ShakespearemonException::BatchJobException(BatchJobException::BatchJobNotFound) => Code::NotFound,
+            ShakespearemonException::BatchJobException(BatchJobException::EmptyBatchJob) => Code::InvalidArgument,
+            ShakespearemonException::BatchJobException(BatchJobException::TooManyNames { .. }) => Code::InvalidArgument,
+            ShakespearemonException::BatchJobException(BatchJobException::InvalidBatchJobRequest) => Code::InvalidArgument,
+            ShakespearemonException::BatchJobException(BatchJobException::TooManyRunningJobs { .. }) => Code::ResourceExhausted,
+            ShakespearemonException::NotAcceptable => Code::InvalidArgument,
+            ShakespearemonException::InvalidDate => Code::InvalidArgument,
+        };
+
+        with_error_code(code, error.to_string(), error.code())
+    }
+}
+
+impl From<ApiKeyException> for Status {
+    fn from(error: ApiKeyException) -> Self {
+        let code = match error {
+            ApiKeyException::MissingApiKey => Code::Unauthenticated,
+            ApiKeyException::InvalidApiKey => Code::Unauthenticated,
+            ApiKeyException::RevokedApiKey => Code::PermissionDenied,
+            ApiKeyException::AdminKeyRequired => Code::PermissionDenied,
+            ApiKeyException::RateLimited { .. } => Code::ResourceExhausted,
+            ApiKeyException::ApiKeyNotFound => Code::NotFound,
+            ApiKeyException::ApiKeyStoreUnavailable => Code::Unavailable,
+        };
+
+        with_error_code(code, error.to_string(), error.code())
+    }
+}
+
+impl From<RateLimitException> for Status {
+    fn from(error: RateLimitException) -> Self {
+        with_error_code(Code::ResourceExhausted, error.to_string(), error.code())
+    }
+}
+
+fn with_error_code(code: Code, message: String, error_code: &'static str) -> Status {
+    let mut metadata = MetadataMap::new();
+    metadata.insert(ERROR_CODE_METADATA, MetadataValue::from_static(error_code));
+
+    Status::with_metadata(code, message, metadata)
+}
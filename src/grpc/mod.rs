@@ -0,0 +1,8 @@
+pub mod grpc_guard;
+pub mod grpc_server;
+pub mod grpc_service;
+pub mod grpc_status;
+
+pub mod proto {
+    tonic::include_proto!("shakespearemon.v1");
+}
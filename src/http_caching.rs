@@ -0,0 +1,97 @@
+use actix_web::{HttpRequest, HttpResponse};
+use actix_web::http::{header, StatusCode};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::negotiation::representation::Representation;
+use crate::negotiation::response_format::ResponseFormat;
+
+/// Answers `If-None-Match` with 304 when it names `etag`, before the caller
+/// translates anything for the response.
+pub fn respond_if_not_modified(request: &HttpRequest, etag: &str, max_age_seconds: u64) -> Option<HttpResponse> {
+    if !matches_if_none_match(request, etag) {
+        return None;
+    }
+
+    Some(HttpResponse::build(StatusCode::NOT_MODIFIED)
+        .set_header(header::ETAG, etag)
+        .set_header(header::CACHE_CONTROL, cache_control(max_age_seconds))
+        .set_header(header::VARY, "Accept")
+        .finish())
+}
+
+/// Renders a cacheable representation with the `ETag` computed for it up front
+/// and `Cache-Control`.
+pub fn respond_with_caching<T: Representation>(format: ResponseFormat, etag: String, representation: &T, max_age_seconds: u64) -> HttpResponse {
+    HttpResponse::build(StatusCode::OK)
+        .set_header(header::ETAG, etag)
+        .set_header(header::CACHE_CONTROL, cache_control(max_age_seconds))
+        .set_header(header::VARY, "Accept")
+        .set_header(header::CONTENT_TYPE, format.content_type())
+        .body(format.render(representation))
+}
+
+/// Renders a representation that must not be cached, such as one built from
+/// stand-in translations: `no-store` and no `ETag` to revalidate it with.
+pub fn respond_without_caching<T: Representation>(format: ResponseFormat, representation: &T) -> HttpResponse {
+    HttpResponse::build(StatusCode::OK)
+        .set_header(header::CACHE_CONTROL, "no-store")
+        .set_header(header::VARY, "Accept")
+        .set_header(header::CONTENT_TYPE, format.content_type())
+        .body(format.render(representation))
+}
+
+/// Derives a strong validator from the representation's format and everything
+/// its content is derived from: the upstream resource, the options asked for and
+/// the translator. A translator's translations of the same text do not change, so
+/// no translation is needed.
+pub fn compute_etag(format: ResponseFormat, sources: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format.content_type().as_bytes());
+    for source in sources {
+        hasher.update(b"\n");
+        hasher.update(source.as_bytes());
+    }
+
+    let hex = format!("{:x}", hasher.finalize());
+    format!("\"{}\"", &hex[..32])
+}
+
+/// Serializes an upstream resource as a source for [`compute_etag`].
+pub fn etag_source<T: Serialize>(source: &T) -> String {
+    serde_json::to_string(source).unwrap_or_default()
+}
+
+fn cache_control(max_age_seconds: u64) -> String {
+    format!("public, max-age={}", max_age_seconds)
+}
+
+fn matches_if_none_match(request: &HttpRequest, etag: &str) -> bool {
+    request.headers().get_all(header::IF_NONE_MATCH)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_compute_same_etag_for_same_content() {
+        let first = compute_etag(ResponseFormat::Json, &["pikachu", "{}"]);
+        let second = compute_etag(ResponseFormat::Json, &["pikachu", "{}"]);
+
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 34);
+        assert!(first.starts_with('"') && first.ends_with('"'));
+    }
+
+    #[test]
+    fn should_compute_different_etags_for_different_representations() {
+        assert_ne!(compute_etag(ResponseFormat::Json, &["text"]), compute_etag(ResponseFormat::PlainText, &["text"]));
+        assert_ne!(compute_etag(ResponseFormat::Json, &["{}"]), compute_etag(ResponseFormat::Json, &["[]"]));
+        assert_ne!(compute_etag(ResponseFormat::Json, &["ab", "c"]), compute_etag(ResponseFormat::Json, &["a", "bc"]));
+    }
+}
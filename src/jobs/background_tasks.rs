@@ -0,0 +1,110 @@
+use std::future::Future;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use actix_web::rt::{Arbiter, spawn};
+
+/// Counts detached work, such as batch jobs, so shutdown can wait for it to
+/// finish before the translation store is flushed.
+#[derive(Clone, Default)]
+pub struct BackgroundTasks {
+    running: Arc<(Mutex<usize>, Condvar)>,
+    arbiter: Option<Arbiter>,
+}
+
+/// Marks its task finished when dropped, whether it completed or was cancelled.
+struct RunningTask(Arc<(Mutex<usize>, Condvar)>);
+
+impl Drop for RunningTask {
+    fn drop(&mut self) {
+        let (running, finished) = &*self.0;
+        *running.lock().unwrap() -= 1;
+        finished.notify_all();
+    }
+}
+
+impl BackgroundTasks {
+    pub fn new() -> Self {
+        BackgroundTasks::default()
+    }
+
+    /// Spawns tasks on `arbiter` rather than on the calling worker, whose event
+    /// loop stops with the HTTP server and would drop them mid-translation.
+    pub fn with_arbiter(mut self, arbiter: Arbiter) -> Self {
+        self.arbiter = Some(arbiter);
+        self
+    }
+
+    /// Counts `task` as running from now until it completes or is dropped.
+    pub fn track<F: Future>(&self, task: F) -> impl Future<Output = F::Output> {
+        *self.running.0.lock().unwrap() += 1;
+        let running_task = RunningTask(Arc::clone(&self.running));
+
+        async move {
+            let _running_task = running_task;
+            task.await
+        }
+    }
+
+    /// Tracks `task` and runs it on the arbiter, or on the current actix runtime without one.
+    pub fn spawn<F: Future<Output = ()> + Send + 'static>(&self, task: F) {
+        let task = self.track(task);
+        match &self.arbiter {
+            Some(arbiter) => arbiter.send(Box::pin(task)),
+            None => spawn(task),
+        }
+    }
+
+    pub fn running(&self) -> usize {
+        *self.running.0.lock().unwrap()
+    }
+
+    /// Blocks until every tracked task has finished or `timeout` has passed,
+    /// returning whether they all finished.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let (running, finished) = &*self.running;
+        let mut running = running.lock().unwrap();
+
+        while *running > 0 {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            running = finished.wait_timeout(running, deadline - now).unwrap().0;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use futures::executor::block_on;
+    use futures::future::pending;
+
+    use super::*;
+
+    #[test]
+    fn should_wait_for_tracked_tasks_to_finish() {
+        let background_tasks = BackgroundTasks::new();
+        let task = background_tasks.track(async {});
+        assert_eq!(background_tasks.running(), 1);
+
+        let finishing = thread::spawn(move || block_on(task));
+
+        assert!(background_tasks.wait(Duration::from_secs(5)));
+        assert_eq!(background_tasks.running(), 0);
+        finishing.join().unwrap();
+    }
+
+    #[test]
+    fn should_give_up_waiting_after_the_timeout() {
+        let background_tasks = BackgroundTasks::new();
+        let _task = background_tasks.track(pending::<()>());
+
+        assert!(!background_tasks.wait(Duration::from_millis(50)));
+        assert_eq!(background_tasks.running(), 1);
+    }
+}
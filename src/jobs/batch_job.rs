@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error_response::ErrorResponse;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchJobState {
+    Running,
+    WaitingForQuota,
+    Completed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BatchJobResult {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<ErrorResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BatchJobSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+/// What subscribers of a job see, in the order it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchJobEvent {
+    Result(BatchJobResult),
+    /// The translation quota is spent; the job retries `name` at `resume_at`.
+    QuotaWait { name: String, resume_at: u64 },
+    /// Always the last event of a job.
+    Summary(BatchJobSummary),
+}
+
+impl BatchJobEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BatchJobEvent::Result(_) => "result",
+            BatchJobEvent::QuotaWait { .. } => "quota_wait",
+            BatchJobEvent::Summary(_) => "summary",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BatchJob {
+    pub id: String,
+    pub state: BatchJobState,
+    pub names: Vec<String>,
+    pub results: Vec<BatchJobResult>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub resume_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub summary: Option<BatchJobSummary>,
+}
+
+impl BatchJob {
+    pub fn new(id: String, names: Vec<String>) -> Self {
+        BatchJob {
+            id,
+            state: BatchJobState::Running,
+            names,
+            results: Vec::new(),
+            resume_at: None,
+            summary: None,
+        }
+    }
+
+    pub fn apply(&mut self, event: &BatchJobEvent) {
+        match event {
+            BatchJobEvent::Result(result) => {
+                self.state = BatchJobState::Running;
+                self.resume_at = None;
+                self.results.push(result.clone());
+            }
+            BatchJobEvent::QuotaWait { resume_at, .. } => {
+                self.state = BatchJobState::WaitingForQuota;
+                self.resume_at = Some(*resume_at);
+            }
+            BatchJobEvent::Summary(summary) => {
+                self.state = BatchJobState::Completed;
+                self.resume_at = None;
+                self.summary = Some(summary.clone());
+            }
+        }
+    }
+}
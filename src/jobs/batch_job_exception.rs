@@ -0,0 +1,16 @@
+use derive_more::{Display, Error};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Display, Error, PartialEq, Serialize, Deserialize)]
+pub enum BatchJobException {
+    #[display(fmt = "Batch job not found")]
+    BatchJobNotFound,
+    #[display(fmt = "A batch job needs at least one name")]
+    EmptyBatchJob,
+    #[display(fmt = "No more than {} names can be requested at once", max_names)]
+    TooManyNames { max_names: usize },
+    #[display(fmt = "Request body is not valid")]
+    InvalidBatchJobRequest,
+    #[display(fmt = "No more than {} batch jobs can run at once", max_running_jobs)]
+    TooManyRunningJobs { max_running_jobs: usize },
+}
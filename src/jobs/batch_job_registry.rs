@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::stream::{self, Chain, Iter, StreamExt};
+use rand::Rng;
+
+use crate::jobs::batch_job::{BatchJob, BatchJobEvent};
+use crate::jobs::batch_job_exception::BatchJobException;
+
+/// A job's events from the requested one on, each with its sequence number.
+/// The stream ends after the job's summary.
+pub type BatchJobEvents = Chain<Iter<std::vec::IntoIter<(usize, BatchJobEvent)>>, UnboundedReceiver<(usize, BatchJobEvent)>>;
+
+struct BatchJobEntry {
+    job: BatchJob,
+    events: Vec<BatchJobEvent>,
+    subscribers: Vec<BatchJobSubscriber>,
+    finished_at: Option<Instant>,
+}
+
+/// A live subscriber only wants the events from sequence number `from` on.
+struct BatchJobSubscriber {
+    from: usize,
+    sender: UnboundedSender<(usize, BatchJobEvent)>,
+}
+
+/// Keeps batch jobs and every event they published in memory, so a client can
+/// replay a job after reconnecting or fetch its results once it is done.
+/// Finished jobs are forgotten after `retention`; no more than `max_running_jobs`
+/// run at once.
+pub struct BatchJobRegistry {
+    retention: Duration,
+    max_running_jobs: usize,
+    jobs: Mutex<HashMap<String, BatchJobEntry>>,
+}
+
+impl BatchJobRegistry {
+    pub fn new(retention: Duration) -> Self {
+        BatchJobRegistry {
+            retention,
+            max_running_jobs: usize::MAX,
+            jobs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_max_running_jobs(mut self, max_running_jobs: usize) -> Self {
+        self.max_running_jobs = max_running_jobs;
+        self
+    }
+
+    pub fn create(&self, names: Vec<String>) -> Result<BatchJob, BatchJobException> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let retention = self.retention;
+        jobs.retain(|_, entry| entry.finished_at.map(|finished_at| finished_at.elapsed() < retention).unwrap_or(true));
+
+        let max_running_jobs = self.max_running_jobs;
+        if jobs.values().filter(|entry| entry.finished_at.is_none()).count() >= max_running_jobs {
+            return Err(BatchJobException::TooManyRunningJobs { max_running_jobs });
+        }
+
+        let job = BatchJob::new(generate_batch_job_id(), names);
+        jobs.insert(job.id.to_owned(), BatchJobEntry {
+            job: job.clone(),
+            events: Vec::new(),
+            subscribers: Vec::new(),
+            finished_at: None,
+        });
+        Ok(job)
+    }
+
+    pub fn get(&self, id: &str) -> Option<BatchJob> {
+        self.jobs.lock().unwrap()
+            .get(id)
+            .map(|entry| entry.job.clone())
+    }
+
+    pub fn publish(&self, id: &str, event: BatchJobEvent) {
+        let mut jobs = self.jobs.lock().unwrap();
+        let entry = match jobs.get_mut(id) {
+            Some(entry) => entry,
+            None => return
+        };
+
+        entry.job.apply(&event);
+        let sequence = entry.events.len();
+        entry.subscribers.retain(|subscriber| {
+            sequence < subscriber.from || subscriber.sender.unbounded_send((sequence, event.clone())).is_ok()
+        });
+
+        if matches!(event, BatchJobEvent::Summary(_)) {
+            entry.finished_at = Some(Instant::now());
+            // Dropping the senders ends every subscriber's stream.
+            entry.subscribers.clear();
+        }
+        entry.events.push(event);
+    }
+
+    /// Replays the events from sequence number `from` on, then follows the job live.
+    pub fn subscribe(&self, id: &str, from: usize) -> Option<BatchJobEvents> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let entry = jobs.get_mut(id)?;
+
+        let past: Vec<_> = entry.events.iter().cloned()
+            .enumerate()
+            .skip(from)
+            .collect();
+
+        let (sender, events) = unbounded();
+        if entry.finished_at.is_none() {
+            entry.subscribers.push(BatchJobSubscriber {
+                from,
+                sender,
+            });
+        }
+
+        Some(stream::iter(past).chain(events))
+    }
+}
+
+fn generate_batch_job_id() -> String {
+    let bytes: [u8; 12] = rand::thread_rng().gen();
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jobs::batch_job::{BatchJobResult, BatchJobState, BatchJobSummary};
+
+    use super::*;
+
+    fn result(name: &str) -> BatchJobEvent {
+        BatchJobEvent::Result(BatchJobResult {
+            name: name.to_owned(),
+            description: Some("translated".to_owned()),
+            error: None,
+        })
+    }
+
+    fn summary() -> BatchJobEvent {
+        BatchJobEvent::Summary(BatchJobSummary {
+            total: 2,
+            succeeded: 2,
+            failed: 0,
+        })
+    }
+
+    #[actix_rt::test]
+    async fn should_replay_past_events_and_follow_until_the_summary() {
+        let registry = BatchJobRegistry::new(Duration::from_secs(60));
+        let job = registry.create(vec!["pikachu".to_owned(), "raichu".to_owned()]).unwrap();
+
+        registry.publish(&job.id, result("pikachu"));
+        let events = registry.subscribe(&job.id, 0).unwrap();
+        registry.publish(&job.id, result("raichu"));
+        registry.publish(&job.id, summary());
+
+        let events: Vec<_> = events.collect().await;
+
+        assert_eq!(events, vec![(0, result("pikachu")), (1, result("raichu")), (2, summary())]);
+    }
+
+    #[actix_rt::test]
+    async fn should_skip_live_events_before_the_requested_sequence_number() {
+        let registry = BatchJobRegistry::new(Duration::from_secs(60));
+        let job = registry.create(vec!["pikachu".to_owned(), "raichu".to_owned()]).unwrap();
+
+        let events = registry.subscribe(&job.id, 1).unwrap();
+        registry.publish(&job.id, result("pikachu"));
+        registry.publish(&job.id, result("raichu"));
+        registry.publish(&job.id, summary());
+
+        let events: Vec<_> = events.collect().await;
+
+        assert_eq!(events, vec![(1, result("raichu")), (2, summary())]);
+    }
+
+    #[actix_rt::test]
+    async fn should_resume_from_the_requested_sequence_number_of_a_finished_job() {
+        let registry = BatchJobRegistry::new(Duration::from_secs(60));
+        let job = registry.create(vec!["pikachu".to_owned(), "raichu".to_owned()]).unwrap();
+
+        registry.publish(&job.id, result("pikachu"));
+        registry.publish(&job.id, result("raichu"));
+        registry.publish(&job.id, summary());
+
+        let events: Vec<_> = registry.subscribe(&job.id, 1).unwrap().collect().await;
+
+        assert_eq!(events, vec![(1, result("raichu")), (2, summary())]);
+        assert_eq!(registry.get(&job.id).unwrap().state, BatchJobState::Completed);
+    }
+
+    #[test]
+    fn should_forget_finished_jobs_after_the_retention_period() {
+        let registry = BatchJobRegistry::new(Duration::from_secs(0));
+        let finished = registry.create(vec!["pikachu".to_owned()]).unwrap();
+        registry.publish(&finished.id, summary());
+        let running = registry.create(vec!["raichu".to_owned()]).unwrap();
+
+        assert!(registry.get(&finished.id).is_none());
+        assert!(registry.get(&running.id).is_some());
+    }
+
+    #[test]
+    fn should_refuse_new_jobs_while_too_many_are_running() {
+        let registry = BatchJobRegistry::new(Duration::from_secs(60)).with_max_running_jobs(1);
+        let running = registry.create(vec!["pikachu".to_owned()]).unwrap();
+
+        assert_eq!(registry.create(vec!["raichu".to_owned()]), Err(BatchJobException::TooManyRunningJobs { max_running_jobs: 1 }));
+
+        registry.publish(&running.id, summary());
+        assert!(registry.create(vec!["raichu".to_owned()]).is_ok());
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::rt::time::delay_for;
+
+use crate::error_response::ErrorResponse;
+use crate::jobs::batch_job::{BatchJobEvent, BatchJobResult, BatchJobSummary};
+use crate::jobs::batch_job_registry::BatchJobRegistry;
+use crate::poke::species_source::SpeciesSource;
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::translation_pipeline::TranslationPipeline;
+use crate::translation_service::{describe_species_by_name, seconds_until, ShakespearemonException};
+
+/// Translates the job's species one at a time, publishing each result as it
+/// completes. A spent quota pauses the job until the quota resets instead of
+/// failing the species, so every name ends up with a translation or an error.
+pub async fn run_batch_job(registry: Arc<BatchJobRegistry>, species_source: Arc<dyn SpeciesSource>, translation_pipeline: TranslationPipeline, id: String, names: Vec<String>) {
+    let mut summary = BatchJobSummary {
+        total: names.len(),
+        succeeded: 0,
+        failed: 0,
+    };
+
+    for name in names {
+        let response = loop {
+            match describe_species_by_name(species_source.as_ref(), &translation_pipeline, name.to_owned()).await {
+                Err(ShakespearemonException::ShakespeareClientException(ShakespeareClientException::QuotaExhausted { reset_at })) => {
+                    registry.publish(&id, BatchJobEvent::QuotaWait {
+                        name: name.to_owned(),
+                        resume_at: reset_at,
+                    });
+                    delay_for(Duration::from_secs(seconds_until(reset_at).max(1))).await;
+                }
+                response => break response
+            }
+        };
+
+        let result = match response {
+            Ok(shakespearemon_response) => {
+                summary.succeeded += 1;
+                BatchJobResult {
+                    name,
+                    description: Some(shakespearemon_response.description),
+                    error: None,
+                }
+            }
+            Err(error) => {
+                summary.failed += 1;
+                BatchJobResult {
+                    name,
+                    description: None,
+                    error: Some(ErrorResponse {
+                        error: error.code().to_owned(),
+                        message: error.to_string(),
+                    }),
+                }
+            }
+        };
+
+        registry.publish(&id, BatchJobEvent::Result(result));
+    }
+
+    registry.publish(&id, BatchJobEvent::Summary(summary));
+}
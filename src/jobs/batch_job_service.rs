@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use actix_web::{Error, get, HttpRequest, HttpResponse, post, web};
+use actix_web::http::header;
+use actix_web::rt::spawn;
+use actix_web_actors::ws;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api_versioning::API_V1_PREFIX;
+use crate::jobs::background_tasks::BackgroundTasks;
+use crate::jobs::batch_job::BatchJobEvent;
+use crate::jobs::batch_job_exception::BatchJobException;
+use crate::jobs::batch_job_registry::BatchJobRegistry;
+use crate::jobs::batch_job_runner::run_batch_job;
+use crate::jobs::batch_job_socket::BatchJobSocket;
+use crate::poke::species_source::SpeciesSource;
+use crate::settings::Settings;
+use crate::translation_pipeline::TranslationPipeline;
+use crate::translation_service::ShakespearemonException;
+
+/// Room for well over a thousand species names.
+const BATCH_JOB_BODY_LIMIT: usize = 64 * 1024;
+
+const LAST_EVENT_ID: &str = "last-event-id";
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct BatchJobRequest {
+    pub names: Vec<String>,
+}
+
+/// Browsers cannot set `Last-Event-ID` on a WebSocket, so it resumes from a query parameter.
+#[derive(Deserialize)]
+pub struct BatchJobSocketQuery {
+    #[serde(default)]
+    pub from: usize,
+}
+
+/// Registers the batch job routes. `main` mounts them under [`API_V1_PREFIX`]
+/// only, since no unversioned clients predate them.
+pub fn configure_batch_jobs(config: &mut web::ServiceConfig) {
+    config.service(web::scope("/jobs")
+        .app_data(batch_job_json_config())
+        .service(create_batch_job)
+        .service(get_batch_job)
+        .service(stream_batch_job_events)
+        .service(stream_batch_job_events_over_websocket));
+}
+
+fn batch_job_json_config() -> web::JsonConfig {
+    web::JsonConfig::default()
+        .limit(BATCH_JOB_BODY_LIMIT)
+        .error_handler(|_, _| {
+            ShakespearemonException::BatchJobException(BatchJobException::InvalidBatchJobRequest).into()
+        })
+}
+
+/// Starts translating the names in the background and answers straight away;
+/// the job's progress is followed through its `events` or `ws` route. With
+/// [`BackgroundTasks`] registered, shutdown waits for the job to finish.
+#[utoipa::path(
+    post,
+    path = "/v1/jobs",
+    tag = "jobs",
+    request_body = BatchJobRequest,
+    responses(
+        (status = 202, description = "The job is running; `Location` names it", body = BatchJob),
+        (status = 400, description = "The names are missing or the body malformed", body = ErrorResponse),
+        (status = 413, description = "More names than a job takes", body = ErrorResponse),
+        (status = 429, description = "Too many jobs are running", body = ErrorResponse),
+    )
+)]
+#[post("")]
+pub async fn create_batch_job(data: web::Data<Settings>, registry: web::Data<BatchJobRegistry>, species_source: web::Data<dyn SpeciesSource>, translation_pipeline: TranslationPipeline, background_tasks: Option<web::Data<BackgroundTasks>>, request: web::Json<BatchJobRequest>) -> Result<HttpResponse, ShakespearemonException> {
+    let BatchJobRequest { names } = request.into_inner();
+
+    validate_batch_job_request(&names, data.jobs.max_names)
+        .map_err(ShakespearemonException::BatchJobException)?;
+
+    let job = registry.create(names.clone())
+        .map_err(ShakespearemonException::BatchJobException)?;
+    let batch_job = run_batch_job(registry.into_inner(), Arc::clone(&species_source), translation_pipeline, job.id.to_owned(), names);
+    match background_tasks {
+        Some(background_tasks) => background_tasks.spawn(batch_job),
+        None => spawn(batch_job),
+    }
+
+    Ok(HttpResponse::Accepted()
+        .set_header(header::LOCATION, format!("{}/jobs/{}", API_V1_PREFIX, job.id))
+        .json(job))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/jobs/{id}",
+    tag = "jobs",
+    params(
+        ("id" = String, Path, description = "The job id"),
+    ),
+    responses(
+        (status = 200, description = "The job and the results so far", body = BatchJob),
+        (status = 404, description = "The job is unknown or expired", body = ErrorResponse),
+    )
+)]
+#[get("/{id}")]
+pub async fn get_batch_job(registry: web::Data<BatchJobRegistry>, web::Path(id): web::Path<String>) -> Result<HttpResponse, ShakespearemonException> {
+    let job = registry.get(&id)
+        .ok_or(ShakespearemonException::BatchJobException(BatchJobException::BatchJobNotFound))?;
+
+    Ok(HttpResponse::Ok().json(job))
+}
+
+/// Server-Sent Events; a reconnecting client resumes after its `Last-Event-ID`.
+#[utoipa::path(
+    get,
+    path = "/v1/jobs/{id}/events",
+    tag = "jobs",
+    params(
+        ("id" = String, Path, description = "The job id"),
+        ("Last-Event-ID" = Option<usize>, Header, description = "Resume after this event"),
+    ),
+    responses(
+        (status = 200, description = "The job's events as Server-Sent Events", body = String, content_type = "text/event-stream"),
+        (status = 404, description = "The job is unknown or expired", body = ErrorResponse),
+    )
+)]
+#[get("/{id}/events")]
+pub async fn stream_batch_job_events(request: HttpRequest, registry: web::Data<BatchJobRegistry>, web::Path(id): web::Path<String>) -> Result<HttpResponse, ShakespearemonException> {
+    let from = request.headers().get(LAST_EVENT_ID)
+        .and_then(|last_event_id| last_event_id.to_str().ok())
+        .and_then(|last_event_id| last_event_id.parse::<usize>().ok())
+        .map(|last_event_id| last_event_id + 1)
+        .unwrap_or(0);
+
+    let events = registry.subscribe(&id, from)
+        .ok_or(ShakespearemonException::BatchJobException(BatchJobException::BatchJobNotFound))?;
+
+    let body = events.map(|(sequence, event)| {
+        Ok::<_, Error>(web::Bytes::from(to_server_sent_event(sequence, &event)))
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .set_header(header::CACHE_CONTROL, "no-cache")
+        .streaming(body))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/jobs/{id}/ws",
+    tag = "jobs",
+    params(
+        ("id" = String, Path, description = "The job id"),
+        ("from" = Option<usize>, Query, description = "Resume from this event"),
+    ),
+    responses(
+        (status = 101, description = "The job's events, one JSON message each"),
+        (status = 404, description = "The job is unknown or expired", body = ErrorResponse),
+    )
+)]
+#[get("/{id}/ws")]
+pub async fn stream_batch_job_events_over_websocket(request: HttpRequest, stream: web::Payload, registry: web::Data<BatchJobRegistry>, web::Path(id): web::Path<String>, query: web::Query<BatchJobSocketQuery>) -> Result<HttpResponse, Error> {
+    let events = registry.subscribe(&id, query.from)
+        .ok_or(ShakespearemonException::BatchJobException(BatchJobException::BatchJobNotFound))?;
+
+    ws::start(BatchJobSocket::new(events), &request, stream)
+}
+
+fn validate_batch_job_request(names: &[String], max_names: usize) -> Result<(), BatchJobException> {
+    if names.iter().all(|name| name.trim().is_empty()) {
+        return Err(BatchJobException::EmptyBatchJob);
+    }
+
+    if names.len() > max_names {
+        return Err(BatchJobException::TooManyNames { max_names });
+    }
+
+    Ok(())
+}
+
+pub fn to_server_sent_event(sequence: usize, event: &BatchJobEvent) -> String {
+    format!(
+        "id: {}\nevent: {}\ndata: {}\n\n",
+        sequence,
+        event.kind(),
+        serde_json::to_string(event).unwrap_or_default(),
+    )
+}
@@ -0,0 +1,64 @@
+use actix::{Actor, ActorContext, AsyncContext, StreamHandler};
+use actix_web_actors::ws;
+use serde::Serialize;
+
+use crate::jobs::batch_job::BatchJobEvent;
+use crate::jobs::batch_job_registry::BatchJobEvents;
+
+/// Sends a job's events to a WebSocket client as JSON text messages and closes
+/// the socket after the summary. Each message carries the event's `sequence`, the
+/// same number SSE sends as `id:`; a client resumes with `?from={sequence + 1}`.
+pub struct BatchJobSocket {
+    events: Option<BatchJobEvents>,
+}
+
+impl BatchJobSocket {
+    pub fn new(events: BatchJobEvents) -> Self {
+        BatchJobSocket {
+            events: Some(events)
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct BatchJobSocketMessage<'a> {
+    sequence: usize,
+    #[serde(flatten)]
+    event: &'a BatchJobEvent,
+}
+
+impl Actor for BatchJobSocket {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        if let Some(events) = self.events.take() {
+            ctx.add_stream(events);
+        }
+    }
+}
+
+impl StreamHandler<(usize, BatchJobEvent)> for BatchJobSocket {
+    fn handle(&mut self, (sequence, event): (usize, BatchJobEvent), ctx: &mut Self::Context) {
+        ctx.text(serde_json::to_string(&BatchJobSocketMessage { sequence, event: &event }).unwrap_or_default());
+    }
+
+    fn finished(&mut self, ctx: &mut Self::Context) {
+        ctx.close(Some(ws::CloseCode::Normal.into()));
+        ctx.stop();
+    }
+}
+
+/// Clients only listen; their messages are answered but otherwise ignored.
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for BatchJobSocket {
+    fn handle(&mut self, message: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match message {
+            Ok(ws::Message::Ping(message)) => ctx.pong(&message),
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            Ok(_) => {}
+            Err(_) => ctx.stop(),
+        }
+    }
+}
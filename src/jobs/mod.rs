@@ -0,0 +1,7 @@
+pub mod background_tasks;
+pub mod batch_job;
+pub mod batch_job_exception;
+pub mod batch_job_registry;
+pub mod batch_job_runner;
+pub mod batch_job_service;
+pub mod batch_job_socket;
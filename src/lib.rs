@@ -1,4 +1,31 @@
+pub mod admin_service;
+pub mod api_versioning;
+pub mod auth;
+pub mod cache;
+pub mod cli;
+pub mod cors_policy;
+pub mod daily;
+pub mod daily_pokemon_service;
+pub mod error_response;
+pub mod graphql;
+pub mod grpc;
+pub mod http_caching;
+pub mod jobs;
+pub mod negotiation;
+pub mod openapi;
 pub mod poke;
+pub mod pokemon_media;
+pub mod prewarm;
+pub mod quota;
+pub mod rate_limit;
+pub mod resource_translation_service;
 pub mod shakespeare;
+pub mod store;
 pub mod settings;
-pub mod translation_service;
\ No newline at end of file
+pub mod single_flight;
+pub mod species_metadata;
+pub mod text_translation_exception;
+pub mod text_translation_service;
+pub mod tls;
+pub mod translation_pipeline;
+pub mod translation_service;
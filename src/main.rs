@@ -1,33 +1,459 @@
 extern crate log;
 
-use std::io::{Error, ErrorKind};
+use std::fs::File;
+use std::io::{BufWriter, Error};
+use std::sync::Arc;
+use std::time::Duration;
 
-use actix_web::{App, HttpServer};
-use actix_web::middleware::Logger;
+use actix_web::{App, HttpServer, web};
+use actix_web::middleware::{Condition, Logger};
+use actix_web::rt::Arbiter;
+use futures::try_join;
+use log::{info, warn};
 
-use crate::settings::Settings;
-use crate::translation_service::translate_pokemon_description_by_shakespeare;
+use shakespearemon::admin_service::configure_admin_api;
+use shakespearemon::api_versioning::{API_V1_PREFIX, configure_public_api, Deprecated};
+use shakespearemon::auth::api_key_middleware::ApiKeyAuth;
+use shakespearemon::auth::api_key_registry::ApiKeyRegistry;
+use shakespearemon::cache::redis_translation_cache::RedisTranslationCache;
+use shakespearemon::cache::translation_cache::TranslationCache;
+use shakespearemon::cli::command::{Command, ExportOptions, USAGE};
+use shakespearemon::cli::export::{export_species, write_records};
+use shakespearemon::cors_policy::cors_middleware;
+use shakespearemon::daily::daily_prewarmer::DailyPrewarmer;
+use shakespearemon::graphql::graphql_service::{execute_graphql, get_graphql_playground};
+use shakespearemon::graphql::schema::build_schema;
+use shakespearemon::grpc::grpc_guard::GrpcGuard;
+use shakespearemon::grpc::grpc_server::GrpcServer;
+use shakespearemon::grpc::grpc_service::ShakespearemonGrpcService;
+use shakespearemon::jobs::background_tasks::BackgroundTasks;
+use shakespearemon::jobs::batch_job_registry::BatchJobRegistry;
+use shakespearemon::jobs::batch_job_service::configure_batch_jobs;
+use shakespearemon::openapi::{get_openapi_spec, get_swagger_ui};
+use shakespearemon::poke::ability_response::AbilityResponse;
+use shakespearemon::poke::coalescing_species_source::CoalescingSpeciesSource;
+use shakespearemon::poke::move_response::MoveResponse;
+use shakespearemon::poke::poke_client::PokeClient;
+use shakespearemon::poke::pokemon_client::PokemonClient;
+use shakespearemon::poke::pokemon_source::PokemonSource;
+use shakespearemon::poke::resource_client::ResourceClient;
+use shakespearemon::poke::resource_source::ResourceSource;
+use shakespearemon::poke::species_source::SpeciesSource;
+use shakespearemon::prewarm::cache_prewarmer::CachePrewarmer;
+use shakespearemon::prewarm::prewarm_schedule::PrewarmSchedule;
+use shakespearemon::prewarm::prewarm_target::expand_prewarm_targets;
+use shakespearemon::quota::quota_translator::QuotaTranslator;
+use shakespearemon::quota::translation_quota::TranslationQuota;
+use shakespearemon::rate_limit::ip_network::parse_ip_networks;
+use shakespearemon::rate_limit::ip_rate_limiter::IpRateLimiter;
+use shakespearemon::rate_limit::rate_limit_middleware::IpRateLimit;
+use shakespearemon::settings::{Settings, TranslatorEngine};
+use shakespearemon::shakespeare::coalescing_translator::CoalescingTranslator;
+use shakespearemon::shakespeare::fallback_translator::FallbackTranslator;
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::shakespeare::shakespeare_client::ShakespeareClient;
+use shakespearemon::shakespeare::translator::Translator;
+use shakespearemon::store::api_key_store::ApiKeyStore;
+use shakespearemon::store::translation_store::TranslationStore;
+use shakespearemon::tls::https_redirect::{HttpsPort, redirect_to_https};
+use shakespearemon::tls::strict_transport_security::strict_transport_security;
+use shakespearemon::tls::tls_config::load_server_config;
+use shakespearemon::translation_pipeline::TranslationPipeline;
+use shakespearemon::translation_service::{describe_species, ShakespearemonQuery};
 
-mod poke;
-mod shakespeare;
-mod settings;
-mod translation_service;
+/// The clients and translation stages shared by every subcommand.
+struct Services {
+    species_source: Arc<dyn SpeciesSource>,
+    pokemon_source: Arc<dyn PokemonSource>,
+    ability_source: Arc<dyn ResourceSource<AbilityResponse>>,
+    move_source: Arc<dyn ResourceSource<MoveResponse>>,
+    translator: Arc<dyn Translator>,
+    translation_quota: Option<Arc<TranslationQuota>>,
+    translation_store: Option<Arc<TranslationStore>>,
+    translation_cache: Option<Arc<dyn TranslationCache>>,
+}
+
+impl Services {
+    fn new(settings: &Settings) -> std::io::Result<Self> {
+        let species_source: Arc<dyn SpeciesSource> = Arc::new(CoalescingSpeciesSource::new(
+            Arc::new(PokeClient::new(&settings.application.poke_api_base_url))
+        ));
+
+        let pokemon_source: Arc<dyn PokemonSource> = Arc::new(PokemonClient::new(&settings.application.poke_api_pokemon_base_url));
+
+        let ability_source: Arc<dyn ResourceSource<AbilityResponse>> = Arc::new(ResourceClient::new(&settings.application.poke_api_ability_base_url));
+        let move_source: Arc<dyn ResourceSource<MoveResponse>> = Arc::new(ResourceClient::new(&settings.application.poke_api_move_base_url));
+
+        let translation_quota = settings.quota.as_ref()
+            .map(|quota| Arc::new(TranslationQuota::new(quota.hourly_limit, quota.daily_limit)));
+
+        let api_secret = settings.funtranslations.api_secret().map_err(Error::other)?;
+        let remote_translator = ShakespeareClient::new(&settings.application.shakespeare_translator_api_base_url);
+        let remote_translator: Arc<dyn Translator> = Arc::new(match api_secret {
+            Some(api_secret) => remote_translator.with_api_secret(api_secret),
+            None => remote_translator
+        });
+        let remote_translator: Arc<dyn Translator> = match &translation_quota {
+            Some(quota) => Arc::new(QuotaTranslator::new(remote_translator, quota.clone())),
+            None => remote_translator
+        };
+        let local_translator: Arc<dyn Translator> = Arc::new(LocalTranslator::new(settings.application.local_translator_flourishes));
+
+        let translator: Arc<dyn Translator> = match settings.application.translator_engine {
+            TranslatorEngine::Remote => remote_translator,
+            TranslatorEngine::Local => local_translator,
+            TranslatorEngine::RemoteWithLocalFallback => Arc::new(FallbackTranslator::new(remote_translator, local_translator)),
+        };
+        let translator: Arc<dyn Translator> = Arc::new(CoalescingTranslator::new(translator));
+
+        let translation_store = match &settings.application.translation_store_path {
+            Some(path) => {
+                let store = TranslationStore::open(path).map_err(|error| {
+                    Error::other(format!("Translation store failed with an error: {:?}", error))
+                })?;
+                Some(Arc::new(store))
+            }
+            None => None
+        };
+
+        let translation_cache = match &settings.cache {
+            Some(cache) => {
+                let cache = RedisTranslationCache::new(&cache.redis_url, &cache.namespace, Duration::from_secs(cache.ttl_seconds)).map_err(|error| {
+                    Error::other(format!("Translation cache failed with an error: {:?}", error))
+                })?;
+                let cache: Arc<dyn TranslationCache> = Arc::new(cache);
+                Some(cache)
+            }
+            None => None
+        };
+
+        Ok(Services {
+            species_source,
+            pokemon_source,
+            ability_source,
+            move_source,
+            translator,
+            translation_quota,
+            translation_store,
+            translation_cache,
+        })
+    }
+
+    fn translation_pipeline(&self) -> TranslationPipeline {
+        let mut translation_pipeline = TranslationPipeline::new(self.translator.clone());
+        if let Some(cache) = &self.translation_cache {
+            translation_pipeline = translation_pipeline.with_translation_cache(cache.clone());
+        }
+        if let Some(store) = &self.translation_store {
+            translation_pipeline = translation_pipeline.with_translation_store(store.clone());
+        }
+        translation_pipeline
+    }
+
+    /// Pre-warming only pays off when a cache or store keeps what it translates.
+    fn keeps_translations(&self) -> bool {
+        self.translation_cache.is_some() || self.translation_store.is_some()
+    }
+
+    /// Waits for queued store writes, so a one-off command does not exit before they land.
+    async fn flush(&self) -> std::io::Result<()> {
+        flush_translation_store(self.translation_store.as_deref()).await
+    }
+}
+
+async fn flush_translation_store(translation_store: Option<&TranslationStore>) -> std::io::Result<()> {
+    match translation_store {
+        Some(store) => store.flush().await.map_err(|error| {
+            Error::other(format!("Translation store failed with an error: {:?}", error))
+        }),
+        None => Ok(())
+    }
+}
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    std::env::set_var("RUST_LOG", "actix_web=info");
-    env_logger::init();
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("actix_web=info,shakespearemon=info")).init();
+
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if command == Command::Help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
 
     let settings = Settings::new().map_err(|error| {
-        Error::new(ErrorKind::Other, format!("Config failed with an error: {:?}", error))
+        Error::other(format!("Config failed with an error: {:?}", error))
     })?;
 
+    let services = Services::new(&settings)?;
+
+    match command {
+        Command::Serve => serve(settings, services).await,
+        Command::Translate { name } => translate(services, name).await,
+        Command::Export(options) => export(services, options).await,
+        Command::Help => Ok(()),
+    }
+}
+
+async fn translate(services: Services, name: String) -> std::io::Result<()> {
+    let poke_species_response = services.species_source.get_pokemon_species(&name).await.map_err(|error| {
+        Error::other(format!("{}: {}", name, error))
+    })?;
+
+    let shakespearemon_response = describe_species(&services.translation_pipeline(), poke_species_response, name.to_owned(), None, &ShakespearemonQuery::default()).await
+        .map_err(|error| {
+            Error::other(format!("{}: {}", name, error))
+        })?;
+
+    println!("{}", shakespearemon_response.description);
+    services.flush().await
+}
+
+async fn export(services: Services, options: ExportOptions) -> std::io::Result<()> {
+    let (records, summary) = export_species(services.species_source.as_ref(), &services.translation_pipeline(), options.from, options.to).await;
+
+    write_records(BufWriter::new(File::create(&options.output)?), options.format, &records)?;
+    services.flush().await?;
+
+    eprintln!("Exported {} species to {}", records.len(), options.output);
+    if !summary.failed.is_empty() {
+        eprintln!("Failed to translate species ids {:?}", summary.failed);
+    }
+    if summary.stopped_on_quota {
+        eprintln!("Stopped early: the translation quota is spent");
+    }
+    Ok(())
+}
+
+async fn serve(settings: Settings, services: Services) -> std::io::Result<()> {
     let addr = format!("{}:{}", settings.application.host, settings.application.port);
 
-    HttpServer::new(|| App::new()
-        .data(Settings::new().expect("Config failed!"))
-        .wrap(Logger::default()).service(translate_pokemon_description_by_shakespeare))
-        .bind(addr)?
-        .run()
-        .await
-}
\ No newline at end of file
+    let translation_pipeline = services.translation_pipeline();
+    if services.keeps_translations() {
+        DailyPrewarmer::new(services.species_source.clone(), translation_pipeline.clone(), &settings.daily).start();
+    } else {
+        info!("No translation cache or store configured, not pre-warming the daily Pokemon");
+    }
+
+    let cache_prewarmer = match &settings.prewarm {
+        Some(prewarm) => {
+            let species = expand_prewarm_targets(&prewarm.species).map_err(Error::other)?;
+            let schedule = PrewarmSchedule::parse(&prewarm.run_at).ok_or_else(|| {
+                Error::other(format!("Pre-warm run_at must be formatted as HH:MM, got {}", prewarm.run_at))
+            })?;
+
+            if services.keeps_translations() {
+                let mut cache_prewarmer = CachePrewarmer::new(services.species_source.clone(), translation_pipeline, species);
+                if let Some(quota) = &services.translation_quota {
+                    cache_prewarmer = cache_prewarmer.with_translation_quota(quota.clone(), prewarm.reserved_quota);
+                }
+
+                let cache_prewarmer = Arc::new(cache_prewarmer);
+                cache_prewarmer.clone().start(schedule);
+                Some(web::Data::from(cache_prewarmer))
+            } else {
+                info!("No translation cache or store configured, not scheduling [prewarm]");
+                None
+            }
+        }
+        None => None
+    };
+
+    let api_key_registry = match &settings.auth {
+        Some(auth) => {
+            let mut api_key_registry = ApiKeyRegistry::new(auth);
+            if let Some(path) = &settings.application.translation_store_path {
+                let api_key_store = ApiKeyStore::open(path).map_err(|error| {
+                    Error::other(format!("API key store failed with an error: {:?}", error))
+                })?;
+                api_key_registry = api_key_registry.with_api_key_store(Arc::new(api_key_store));
+            }
+            Arc::new(api_key_registry)
+        }
+        None => Arc::new(ApiKeyRegistry::default())
+    };
+    let authentication_enabled = settings.auth.is_some();
+    if !authentication_enabled {
+        info!("No [auth] configured, the /admin routes are disabled");
+    }
+
+    let ip_rate_limiter = match &settings.rate_limit {
+        Some(rate_limit) => {
+            let trusted_proxies = parse_ip_networks(&rate_limit.trusted_proxies).map_err(Error::other)?;
+            let allowlist = parse_ip_networks(&rate_limit.allowlist).map_err(Error::other)?;
+            IpRateLimiter::new(rate_limit.requests_per_minute.get(), rate_limit.burst)
+                .with_trusted_proxies(trusted_proxies)
+                .with_allowlist(allowlist)
+        }
+        None => IpRateLimiter::new(0, 0)
+    };
+    let ip_rate_limiter = Arc::new(ip_rate_limiter);
+    let rate_limiting_enabled = settings.rate_limit.is_some();
+
+    let max_text_length = settings.application.max_text_length;
+
+    let shutdown_timeout = Duration::from_secs(settings.server.shutdown_timeout_seconds);
+
+    let grpc_server = match &settings.grpc {
+        Some(grpc) => {
+            let grpc_addr = format!("{}:{}", settings.application.host, grpc.port).parse().map_err(Error::other)?;
+            let mut grpc_guard = GrpcGuard::default();
+            if rate_limiting_enabled {
+                grpc_guard = grpc_guard.with_ip_rate_limiter(ip_rate_limiter.clone());
+            }
+            if authentication_enabled {
+                grpc_guard = grpc_guard.with_api_key_registry(api_key_registry.clone());
+            }
+
+            let grpc_service = ShakespearemonGrpcService::new(services.species_source.clone(), services.translation_pipeline(), max_text_length, settings.jobs.max_names)
+                .with_guard(grpc_guard);
+            Some(GrpcServer::start(grpc_addr, grpc_service, shutdown_timeout)?)
+        }
+        None => None
+    };
+
+    let cors = settings.cors.clone();
+
+    let graphql_schema = build_schema(settings.jobs.max_names);
+
+    let batch_job_registry = Arc::new(BatchJobRegistry::new(Duration::from_secs(settings.jobs.retention_seconds))
+        .with_max_running_jobs(settings.jobs.max_running_jobs));
+    // Batch jobs run on their own arbiter so they outlive the HTTP workers on shutdown.
+    let batch_jobs = BackgroundTasks::new().with_arbiter(Arbiter::new());
+    let app_batch_jobs = web::Data::new(batch_jobs.clone());
+
+    let hsts = settings.tls.as_ref()
+        .and_then(|tls| tls.hsts_max_age_seconds.map(|max_age| (max_age, tls.hsts_include_subdomains)));
+    let hsts_enabled = hsts.is_some();
+    let (hsts_max_age, hsts_include_subdomains) = hsts.unwrap_or_default();
+
+    let pending_writes = services.translation_store.clone();
+
+    let Services { species_source, pokemon_source, ability_source, move_source, translator, translation_quota, translation_store, translation_cache } = services;
+    let translation_quota = translation_quota.map(web::Data::from);
+    let translation_store = translation_store.map(web::Data::from);
+    let translation_cache = translation_cache.map(web::Data::from);
+
+    let server = HttpServer::new(move || {
+        let app = App::new()
+            .data(Settings::new().expect("Config failed!"))
+            .data(graphql_schema.clone())
+            .app_data(web::Data::from(species_source.clone()))
+            .app_data(web::Data::from(pokemon_source.clone()))
+            .app_data(web::Data::from(ability_source.clone()))
+            .app_data(web::Data::from(move_source.clone()))
+            .app_data(web::Data::from(translator.clone()))
+            .app_data(web::Data::from(api_key_registry.clone()))
+            .app_data(web::Data::from(batch_job_registry.clone()))
+            .app_data(app_batch_jobs.clone());
+
+        let app = match &translation_store {
+            Some(store) => app.app_data(store.clone()),
+            None => app
+        };
+
+        let app = match &translation_cache {
+            Some(cache) => app.app_data(cache.clone()),
+            None => app
+        };
+
+        let app = match &translation_quota {
+            Some(quota) => app.app_data(quota.clone()),
+            None => app
+        };
+
+        let app = match &cache_prewarmer {
+            Some(cache_prewarmer) => app.app_data(cache_prewarmer.clone()),
+            None => app
+        };
+
+        app.wrap(Condition::new(hsts_enabled, strict_transport_security(hsts_max_age, hsts_include_subdomains)))
+            .wrap(Condition::new(authentication_enabled, ApiKeyAuth::new(api_key_registry.clone())))
+            .wrap(Condition::new(rate_limiting_enabled, IpRateLimit::new(ip_rate_limiter.clone())))
+            .wrap(Condition::new(cors.is_some(), cors.as_ref().map(cors_middleware).unwrap_or_default()))
+            .wrap(Logger::default())
+            .service(web::scope(API_V1_PREFIX).configure(configure_public_api(max_text_length)).configure(configure_batch_jobs))
+            .service(get_openapi_spec)
+            .service(get_swagger_ui)
+            .service(execute_graphql)
+            .service(get_graphql_playground)
+            .configure(|config| {
+                if authentication_enabled {
+                    configure_admin_api(config);
+                }
+            })
+            // Matches every path, so it has to be registered last.
+            .service(web::scope("").wrap(Deprecated).configure(configure_public_api(max_text_length)))
+    });
+
+    let server_settings = &settings.server;
+    let keep_alive = match server_settings.keep_alive_seconds {
+        0 => None,
+        seconds => Some(seconds)
+    };
+    let server = server
+        .max_connections(server_settings.max_connections)
+        .keep_alive(keep_alive)
+        .client_timeout(server_settings.client_timeout_milliseconds)
+        .shutdown_timeout(server_settings.shutdown_timeout_seconds)
+        .backlog(server_settings.backlog);
+    let server = match server_settings.workers {
+        Some(workers) => server.workers(workers),
+        None => server
+    };
+
+    let tls = match &settings.tls {
+        Some(tls) => Some((tls, load_server_config(&tls.cert_path, &tls.key_path).map_err(Error::other)?)),
+        None => None
+    };
+
+    // SIGTERM and SIGINT stop accepting connections and give in-flight requests
+    // up to `shutdown_timeout_seconds` to finish before the servers resolve.
+    let stopped = match tls {
+        Some((tls, server_config)) => {
+            let https_addr = format!("{}:{}", settings.application.host, tls.port);
+            let server = server.bind_rustls(https_addr, server_config)?;
+
+            if tls.redirect_http {
+                let https_port = tls.port;
+                let redirect_server = HttpServer::new(move || {
+                    App::new()
+                        .data(HttpsPort(https_port))
+                        .wrap(Logger::default())
+                        .default_service(web::to(redirect_to_https))
+                })
+                    .workers(1)
+                    .shutdown_timeout(server_settings.shutdown_timeout_seconds)
+                    .bind(addr)?;
+
+                try_join!(server.run(), redirect_server.run()).map(|_| ())
+            } else if tls.allow_plain_http {
+                server.bind(addr)?.run().await
+            } else {
+                server.run().await
+            }
+        }
+        None => server.bind(addr)?.run().await
+    };
+
+    if let Some(grpc_server) = grpc_server {
+        info!("Server stopped, stopping the gRPC server");
+        grpc_server.stop();
+    }
+
+    info!("Server stopped, waiting for {} running batch jobs", batch_jobs.running());
+    if !batch_jobs.wait(shutdown_timeout) {
+        warn!("Dropping {} batch jobs still running after the shutdown timeout", batch_jobs.running());
+    }
+
+    info!("Server stopped, flushing pending translation store writes");
+    flush_translation_store(pending_writes.as_deref()).await?;
+
+    stopped
+}
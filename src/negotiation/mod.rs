@@ -0,0 +1,3 @@
+pub mod negotiated_exception;
+pub mod representation;
+pub mod response_format;
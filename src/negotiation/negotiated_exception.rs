@@ -0,0 +1,37 @@
+use actix_web::{HttpResponse, ResponseError};
+use actix_web::http::StatusCode;
+use derive_more::Display;
+
+use crate::negotiation::response_format::ResponseFormat;
+use crate::translation_service::ShakespearemonException;
+
+/// Carries the format negotiated for a request so errors are rendered in it too.
+#[derive(Debug, Display)]
+#[display(fmt = "{}", exception)]
+pub struct NegotiatedException {
+    pub exception: ShakespearemonException,
+    pub format: ResponseFormat,
+}
+
+impl NegotiatedException {
+    pub fn new(exception: ShakespearemonException, format: ResponseFormat) -> Self {
+        NegotiatedException {
+            exception,
+            format,
+        }
+    }
+
+    pub fn not_acceptable() -> Self {
+        NegotiatedException::new(ShakespearemonException::NotAcceptable, ResponseFormat::Json)
+    }
+}
+
+impl ResponseError for NegotiatedException {
+    fn status_code(&self) -> StatusCode {
+        self.exception.status_code()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        self.exception.error_response_as(self.format)
+    }
+}
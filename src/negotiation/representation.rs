@@ -0,0 +1,42 @@
+use serde::Serialize;
+
+/// A response body that can be rendered in every format the service negotiates.
+pub trait Representation: Serialize {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    fn to_plain_text(&self) -> String;
+
+    fn to_html(&self) -> String;
+
+    fn to_xml(&self) -> String;
+}
+
+/// Escapes text for use in HTML and XML content and attribute values.
+pub fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_escape_markup_characters() {
+        assert_eq!(escape_markup("<b>\"Tom\" & 'Jerry'</b>"), "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;");
+    }
+}
@@ -0,0 +1,98 @@
+use actix_web::{HttpRequest, HttpResponse};
+use actix_web::dev::HttpResponseBuilder;
+use actix_web::http::{header, StatusCode};
+
+use crate::negotiation::representation::Representation;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResponseFormat {
+    Json,
+    PlainText,
+    Html,
+    Xml,
+}
+
+impl ResponseFormat {
+    /// Picks the format the `Accept` header prefers most, JSON when there is no header.
+    pub fn from_request(request: &HttpRequest) -> Option<ResponseFormat> {
+        match request.headers().get(header::ACCEPT).and_then(|accept| accept.to_str().ok()) {
+            Some(accept) if !accept.trim().is_empty() => ResponseFormat::from_accept(accept),
+            _ => Some(ResponseFormat::Json),
+        }
+    }
+
+    pub fn from_accept(accept: &str) -> Option<ResponseFormat> {
+        let mut best: Option<(ResponseFormat, f32)> = None;
+
+        for media_range in accept.split(',') {
+            let mut parameters = media_range.split(';');
+            let media_type = parameters.next().unwrap_or_default().trim().to_lowercase();
+
+            let quality = parameters
+                .filter_map(|parameter| parameter.trim().strip_prefix("q="))
+                .find_map(|quality| quality.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            let format = match media_type.as_str() {
+                "application/json" | "application/*" | "*/*" => ResponseFormat::Json,
+                "text/plain" | "text/*" => ResponseFormat::PlainText,
+                "text/html" => ResponseFormat::Html,
+                "application/xml" | "text/xml" => ResponseFormat::Xml,
+                _ => continue,
+            };
+
+            if quality > 0.0 && best.map(|(_, best_quality)| quality > best_quality).unwrap_or(true) {
+                best = Some((format, quality));
+            }
+        }
+
+        best.map(|(format, _)| format)
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => "application/json; charset=utf-8",
+            ResponseFormat::PlainText => "text/plain; charset=utf-8",
+            ResponseFormat::Html => "text/html; charset=utf-8",
+            ResponseFormat::Xml => "application/xml; charset=utf-8",
+        }
+    }
+
+    pub fn render<T: Representation>(&self, representation: &T) -> String {
+        match self {
+            ResponseFormat::Json => representation.to_json(),
+            ResponseFormat::PlainText => representation.to_plain_text(),
+            ResponseFormat::Html => representation.to_html(),
+            ResponseFormat::Xml => representation.to_xml(),
+        }
+    }
+
+    pub fn respond<T: Representation>(&self, status: StatusCode, representation: &T) -> HttpResponse {
+        HttpResponseBuilder::new(status)
+            .set_header(header::CONTENT_TYPE, self.content_type())
+            .body(self.render(representation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pick_the_most_preferred_supported_format() {
+        assert_eq!(ResponseFormat::from_accept("text/html;q=0.5, application/xml"), Some(ResponseFormat::Xml));
+        assert_eq!(ResponseFormat::from_accept("image/png, text/plain;q=0.1"), Some(ResponseFormat::PlainText));
+        assert_eq!(ResponseFormat::from_accept("text/html, */*;q=0.8"), Some(ResponseFormat::Html));
+    }
+
+    #[test]
+    fn should_pick_the_first_format_among_equally_preferred_ones() {
+        assert_eq!(ResponseFormat::from_accept("text/plain, application/json"), Some(ResponseFormat::PlainText));
+    }
+
+    #[test]
+    fn should_return_none_if_nothing_acceptable_is_supported() {
+        assert_eq!(ResponseFormat::from_accept("image/png"), None);
+        assert_eq!(ResponseFormat::from_accept("application/json;q=0"), None);
+    }
+}
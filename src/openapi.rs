@@ -0,0 +1,129 @@
+use actix_web::{get, HttpResponse};
+use utoipa::OpenApi;
+use utoipa::openapi::{Content, Deprecated, Object, RefOr, SchemaType};
+
+use crate::api_versioning::{API_V1_PREFIX, LEGACY_ERROR_CONTENT_TYPE};
+use crate::error_response::ErrorResponse;
+use crate::jobs::batch_job::{BatchJob, BatchJobResult, BatchJobState, BatchJobSummary};
+use crate::jobs::batch_job_service::BatchJobRequest;
+use crate::pokemon_media::{PokemonMedia, PokemonSprites};
+use crate::resource_translation_service::ResourceTranslationResponse;
+use crate::species_metadata::SpeciesMetadata;
+use crate::text_translation_service::{TextTranslationRequest, TextTranslationResponse};
+use crate::translation_service::ShakespearemonResponse;
+
+const SWAGGER_UI: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Shakespearemon API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+  </script>
+</body>
+</html>
+"##;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Shakespearemon",
+        description = "Pokemon descriptions, abilities and moves, translated to Shakespearean English."
+    ),
+    paths(
+        crate::daily_pokemon_service::get_pokemon_of_the_day,
+        crate::translation_service::translate_pokemon_description_by_shakespeare,
+        crate::resource_translation_service::translate_ability_description,
+        crate::resource_translation_service::translate_move_description,
+        crate::text_translation_service::translate_text,
+    ),
+    components(schemas(
+        ShakespearemonResponse,
+        SpeciesMetadata,
+        PokemonMedia,
+        PokemonSprites,
+        ResourceTranslationResponse,
+        TextTranslationRequest,
+        TextTranslationResponse,
+        ErrorResponse,
+    )),
+    tags(
+        (name = "pokemon", description = "Translated pokemon species"),
+        (name = "abilities", description = "Translated abilities"),
+        (name = "moves", description = "Translated moves"),
+        (name = "translate", description = "Translation of arbitrary text"),
+    )
+)]
+struct ApiDoc;
+
+/// Routes served under [`API_V1_PREFIX`] only, or outside it, with no deprecated alias.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        crate::jobs::batch_job_service::create_batch_job,
+        crate::jobs::batch_job_service::get_batch_job,
+        crate::jobs::batch_job_service::stream_batch_job_events,
+        crate::jobs::batch_job_service::stream_batch_job_events_over_websocket,
+        crate::graphql::graphql_service::execute_graphql,
+        crate::graphql::graphql_service::get_graphql_playground,
+    ),
+    components(schemas(
+        BatchJobRequest,
+        BatchJob,
+        BatchJobState,
+        BatchJobResult,
+        BatchJobSummary,
+    )),
+    tags(
+        (name = "jobs", description = "Batch translation jobs"),
+        (name = "graphql", description = "The GraphQL API"),
+    )
+)]
+struct UnaliasedApiDoc;
+
+/// The OpenAPI document for the public API. Every `/v1` route of the public API
+/// is also listed at its unversioned path, marked deprecated and with the bare
+/// error messages those paths send.
+pub fn openapi_spec() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    // utoipa fills the license from Cargo.toml, which names none.
+    openapi.info.license = None;
+
+    let deprecated_aliases: Vec<_> = openapi.paths.paths.iter()
+        .filter_map(|(path, path_item)| {
+            let alias = path.strip_prefix(API_V1_PREFIX)?.to_owned();
+            let mut path_item = path_item.clone();
+            for operation in path_item.operations.values_mut() {
+                operation.deprecated = Some(Deprecated::True);
+                for (status, response) in operation.responses.responses.iter_mut() {
+                    if let (true, RefOr::T(response)) = (status.starts_with('4') || status.starts_with('5'), response) {
+                        response.content.clear();
+                        response.content.insert(LEGACY_ERROR_CONTENT_TYPE.to_owned(), Content::new(Object::with_type(SchemaType::String)));
+                    }
+                }
+            }
+            Some((alias, path_item))
+        })
+        .collect();
+    openapi.paths.paths.extend(deprecated_aliases);
+    openapi.merge(UnaliasedApiDoc::openapi());
+
+    openapi
+}
+
+#[get("/openapi.json")]
+pub async fn get_openapi_spec() -> HttpResponse {
+    HttpResponse::Ok().json(openapi_spec())
+}
+
+#[get("/docs")]
+pub async fn get_swagger_ui() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(SWAGGER_UI)
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::FutureExt;
+
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::poke_species_response::PokeSpeciesResponse;
+use crate::poke::species_source::SpeciesSource;
+use crate::single_flight::SingleFlight;
+
+/// Lets concurrent requests for the same species share one upstream fetch.
+pub struct CoalescingSpeciesSource {
+    species_source: Arc<dyn SpeciesSource>,
+    in_flight: SingleFlight<Result<PokeSpeciesResponse, PokeClientException>>,
+}
+
+impl CoalescingSpeciesSource {
+    pub fn new(species_source: Arc<dyn SpeciesSource>) -> Self {
+        CoalescingSpeciesSource {
+            species_source,
+            in_flight: SingleFlight::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl SpeciesSource for CoalescingSpeciesSource {
+    async fn get_pokemon_species(&self, name: &str) -> Result<PokeSpeciesResponse, PokeClientException> {
+        let species_source = self.species_source.clone();
+        let owned_name = name.to_owned();
+
+        self.in_flight.run(name, move || async move {
+            species_source.get_pokemon_species(&owned_name).await
+        }.boxed()).await
+    }
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+use crate::poke::poke_species_response::{PokeSpeciesResponse, TextFlavorEntry, TextFlavorEntryLanguage};
+
+const ENGLISH: &str = "en";
+const EFFECT_CHANCE_PLACEHOLDER: &str = "$effect_chance";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EffectEntry {
+    pub effect: String,
+    pub short_effect: String,
+    pub language: TextFlavorEntryLanguage,
+}
+
+/// A PokeAPI resource carrying localised flavor text and, optionally, effect text.
+pub trait FlavorTextResource {
+    fn flavor_text_entries(&self) -> &[TextFlavorEntry];
+
+    fn effect_entries(&self) -> &[EffectEntry] {
+        &[]
+    }
+
+    /// Substituted for `$effect_chance` in the effect text of moves.
+    fn effect_chance(&self) -> Option<u8> {
+        None
+    }
+}
+
+impl FlavorTextResource for PokeSpeciesResponse {
+    fn flavor_text_entries(&self) -> &[TextFlavorEntry] {
+        &self.flavor_text_entries
+    }
+}
+
+pub fn extract_english_flavor_text<R: FlavorTextResource + ?Sized>(resource: &R) -> Option<String> {
+    resource.flavor_text_entries().iter()
+        .find(|entry| {
+            entry.language.name == ENGLISH
+        })
+        .map(|entry| entry.flavor_text.to_owned())
+}
+
+pub fn extract_english_effect<R: FlavorTextResource + ?Sized>(resource: &R) -> Option<String> {
+    let short_effect = resource.effect_entries().iter()
+        .find(|entry| {
+            entry.language.name == ENGLISH
+        })
+        .map(|entry| entry.short_effect.to_owned())?;
+
+    Some(match resource.effect_chance() {
+        Some(effect_chance) => short_effect.replace(EFFECT_CHANCE_PLACEHOLDER, &effect_chance.to_string()),
+        None => short_effect
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Resource {
+        flavor_text_entries: Vec<TextFlavorEntry>,
+        effect_entries: Vec<EffectEntry>,
+        effect_chance: Option<u8>,
+    }
+
+    impl FlavorTextResource for Resource {
+        fn flavor_text_entries(&self) -> &[TextFlavorEntry] {
+            &self.flavor_text_entries
+        }
+
+        fn effect_entries(&self) -> &[EffectEntry] {
+            &self.effect_entries
+        }
+
+        fn effect_chance(&self) -> Option<u8> {
+            self.effect_chance
+        }
+    }
+
+    fn effect_entry(short_effect: &str, language_name: &str) -> EffectEntry {
+        EffectEntry {
+            effect: String::new(),
+            short_effect: short_effect.to_owned(),
+            language: TextFlavorEntryLanguage {
+                name: language_name.to_owned()
+            },
+        }
+    }
+
+    #[test]
+    fn should_return_the_first_english_flavor_text() {
+        let resource = Resource {
+            flavor_text_entries: vec![
+                TextFlavorEntry::new("Texte".to_owned(), "fr".to_owned()),
+                TextFlavorEntry::new("Flavor text".to_owned(), "en".to_owned()),
+            ],
+            effect_entries: Vec::new(),
+            effect_chance: None,
+        };
+
+        assert_eq!(extract_english_flavor_text(&resource), Some("Flavor text".to_owned()));
+    }
+
+    #[test]
+    fn should_substitute_the_effect_chance_into_the_english_effect() {
+        let resource = Resource {
+            flavor_text_entries: Vec::new(),
+            effect_entries: vec![
+                effect_entry("Hat eine Chance von $effect_chance%.", "de"),
+                effect_entry("Has a $effect_chance% chance to burn the target.", "en"),
+            ],
+            effect_chance: Some(10),
+        };
+
+        assert_eq!(extract_english_effect(&resource), Some("Has a 10% chance to burn the target.".to_owned()));
+    }
+
+    #[test]
+    fn should_return_none_without_english_effect_entries() {
+        let resource = Resource {
+            flavor_text_entries: Vec::new(),
+            effect_entries: vec![effect_entry("Effekt", "de")],
+            effect_chance: None,
+        };
+
+        assert_eq!(extract_english_effect(&resource), None);
+    }
+}
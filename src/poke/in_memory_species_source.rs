@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::poke_species_response::{PokeSpeciesResponse, TextFlavorEntry};
+use crate::poke::species_source::SpeciesSource;
+
+/// Serves species from memory so handlers can be exercised without PokeAPI.
+#[derive(Default)]
+pub struct InMemorySpeciesSource {
+    species: HashMap<String, PokeSpeciesResponse>,
+}
+
+impl InMemorySpeciesSource {
+    pub fn new() -> Self {
+        InMemorySpeciesSource::default()
+    }
+
+    pub fn with_flavor_text(mut self, name: &str, flavor_text: &str, language_name: &str) -> Self {
+        let next_id = self.species.len() as u16 + 1;
+
+        self.species.entry(name.to_owned())
+            .or_insert_with(|| PokeSpeciesResponse::new(next_id, name.to_owned(), Vec::new()))
+            .flavor_text_entries
+            .push(TextFlavorEntry::new(flavor_text.to_owned(), language_name.to_owned()));
+        self
+    }
+
+    pub fn with_species(mut self, name: &str) -> Self {
+        let next_id = self.species.len() as u16 + 1;
+
+        self.species.entry(name.to_owned())
+            .or_insert_with(|| PokeSpeciesResponse::new(next_id, name.to_owned(), Vec::new()));
+        self
+    }
+
+    pub fn with_species_response(mut self, response: PokeSpeciesResponse) -> Self {
+        self.species.insert(response.name.to_owned(), response);
+        self
+    }
+}
+
+#[async_trait]
+impl SpeciesSource for InMemorySpeciesSource {
+    async fn get_pokemon_species(&self, name: &str) -> Result<PokeSpeciesResponse, PokeClientException> {
+        self.species.get(name)
+            .cloned()
+            .ok_or(PokeClientException::PokemonNotFound)
+    }
+}
@@ -1,3 +1,14 @@
+pub mod ability_response;
+pub mod coalescing_species_source;
+pub mod flavor_text_resource;
+pub mod in_memory_species_source;
+pub mod move_response;
 pub mod poke_client;
 pub mod poke_client_exception;
-pub mod poke_species_response;
\ No newline at end of file
+pub mod poke_species_response;
+pub mod pokemon_client;
+pub mod pokemon_response;
+pub mod pokemon_source;
+pub mod resource_client;
+pub mod resource_source;
+pub mod species_source;
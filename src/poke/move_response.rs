@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::poke::flavor_text_resource::{EffectEntry, FlavorTextResource};
+use crate::poke::poke_species_response::TextFlavorEntry;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MoveResponse {
+    pub id: u16,
+    pub name: String,
+    #[serde(default)]
+    pub effect_chance: Option<u8>,
+    #[serde(default)]
+    pub flavor_text_entries: Vec<TextFlavorEntry>,
+    #[serde(default)]
+    pub effect_entries: Vec<EffectEntry>,
+}
+
+impl FlavorTextResource for MoveResponse {
+    fn flavor_text_entries(&self) -> &[TextFlavorEntry] {
+        &self.flavor_text_entries
+    }
+
+    fn effect_entries(&self) -> &[EffectEntry] {
+        &self.effect_entries
+    }
+
+    fn effect_chance(&self) -> Option<u8> {
+        self.effect_chance
+    }
+}
@@ -1,55 +1,47 @@
-use surf::{get, StatusCode};
+use async_trait::async_trait;
 
 use crate::poke::poke_client_exception::PokeClientException;
 use crate::poke::poke_species_response::PokeSpeciesResponse;
+use crate::poke::resource_client::get_resource;
+use crate::poke::species_source::{extract_english_flavor_text_from_poke_species_response, SpeciesSource};
 
-pub async fn get_pokemon_description(base_url: &str, name: &str) -> Result<String, PokeClientException> {
-    let mut url = base_url.to_owned();
-    url.push_str("/");
-    url.push_str(name);
-
-    let mut response = get(url).await.map_err(|_| {
-        PokeClientException::PokeClientWentWrong
-    })?;
-
-    match response.status() {
-        StatusCode::Ok => {
-            let poke_species_response: PokeSpeciesResponse = response.body_json().await.map_err(|_| {
-                PokeClientException::PokeClientWentWrong
-            })?;
-            let flavor_text = extract_english_flavor_text_from_poke_species_response(poke_species_response);
-
-            match flavor_text {
-                Some(text) => Ok(text.to_owned()),
-                None => Err(PokeClientException::PokemonDescriptionNotFound)
-            }
-        }
-        StatusCode::NotFound => {
-            Err(PokeClientException::PokemonNotFound)
-        }
-        _ => {
-            Err(PokeClientException::PokeClientWentWrong)
+pub struct PokeClient {
+    base_url: String,
+}
+
+impl PokeClient {
+    pub fn new(base_url: &str) -> Self {
+        PokeClient {
+            base_url: base_url.to_owned()
         }
     }
 }
 
-fn extract_english_flavor_text_from_poke_species_response(response: PokeSpeciesResponse) -> Option<String> {
-    let first_text_flavor_entry_in_english = response.flavor_text_entries.iter()
-        .find(|entry| {
-            entry.language.name == "en"
-        });
+#[async_trait]
+impl SpeciesSource for PokeClient {
+    async fn get_pokemon_species(&self, name: &str) -> Result<PokeSpeciesResponse, PokeClientException> {
+        get_pokemon_species(&self.base_url, name).await
+    }
+}
 
-    match first_text_flavor_entry_in_english {
-        Some(entry) => {
-            Some(entry.flavor_text.to_owned())
-        }
-        None => None
+pub async fn get_pokemon_species(base_url: &str, name: &str) -> Result<PokeSpeciesResponse, PokeClientException> {
+    get_resource(base_url, name).await
+}
+
+pub async fn get_pokemon_description(base_url: &str, name: &str) -> Result<String, PokeClientException> {
+    let poke_species_response = get_pokemon_species(base_url, name).await?;
+    let flavor_text = extract_english_flavor_text_from_poke_species_response(poke_species_response);
+
+    match flavor_text {
+        Some(text) => Ok(text),
+        None => Err(PokeClientException::PokemonDescriptionNotFound)
     }
 }
 
 #[cfg(test)]
 #[allow(unused_imports)]
 mod tests {
+    use surf::StatusCode;
     use wiremock::{Mock, MockServer, ResponseTemplate};
     use wiremock::matchers::{method, path, path_regex};
 
@@ -65,24 +57,6 @@ mod tests {
         PokeSpeciesResponse::new(id, name, flavor_text)
     }
 
-    #[test]
-    fn should_return_none_if_there_is_not_any_english_flavor_text() {
-        let language_name = "qwerty".to_owned();
-        let response = generate_poke_species_response(language_name);
-        let result = extract_english_flavor_text_from_poke_species_response(response);
-
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn should_return_some_description_if_there_is_an_english_flavor_text() {
-        let language_name = "en".to_owned();
-        let response = generate_poke_species_response(language_name);
-        let result = extract_english_flavor_text_from_poke_species_response(response);
-
-        assert_eq!(result, Some("Flavor text".to_owned()));
-    }
-
     #[actix_rt::test]
     #[allow(unused_must_use)]
     async fn should_throw_pokemon_not_found_error() {
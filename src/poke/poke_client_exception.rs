@@ -1,12 +1,16 @@
 use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Display, Error, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Display, Error, PartialEq, Serialize, Deserialize)]
 pub enum PokeClientException {
     #[display(fmt = "Pokemon Not Found")]
     PokemonNotFound,
     #[display(fmt = "Pokemon Description Not Found")]
     PokemonDescriptionNotFound,
+    #[display(fmt = "Ability or Move Not Found")]
+    ResourceNotFound,
+    #[display(fmt = "Ability or Move Description Not Found")]
+    ResourceDescriptionNotFound,
     #[display(fmt = "Unable to process the request")]
     PokeClientWentWrong,
 }
\ No newline at end of file
@@ -1,21 +1,61 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TextFlavorEntryLanguage {
     pub name: String
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TextFlavorEntry {
     pub flavor_text: String,
     pub language: TextFlavorEntryLanguage,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NamedApiResource {
+    pub name: String,
+    #[serde(default)]
+    pub url: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ApiResource {
+    pub url: String
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Genus {
+    pub genus: String,
+    pub language: TextFlavorEntryLanguage,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Name {
+    pub name: String,
+    pub language: TextFlavorEntryLanguage,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PokeSpeciesResponse {
     pub id: u16,
     pub name: String,
     pub flavor_text_entries: Vec<TextFlavorEntry>,
+    #[serde(default)]
+    pub genera: Vec<Genus>,
+    #[serde(default)]
+    pub generation: Option<NamedApiResource>,
+    #[serde(default)]
+    pub is_legendary: bool,
+    #[serde(default)]
+    pub is_mythical: bool,
+    #[serde(default)]
+    pub color: Option<NamedApiResource>,
+    #[serde(default)]
+    pub habitat: Option<NamedApiResource>,
+    #[serde(default)]
+    pub evolution_chain: Option<ApiResource>,
+    #[serde(default)]
+    pub names: Vec<Name>,
 }
 
 impl TextFlavorEntry {
@@ -38,6 +78,14 @@ impl PokeSpeciesResponse {
             id,
             name,
             flavor_text_entries,
+            genera: Vec::new(),
+            generation: None,
+            is_legendary: false,
+            is_mythical: false,
+            color: None,
+            habitat: None,
+            evolution_chain: None,
+            names: Vec::new(),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::pokemon_response::PokemonResponse;
+use crate::poke::pokemon_source::PokemonSource;
+use crate::poke::resource_client::get_resource;
+
+pub struct PokemonClient {
+    base_url: String,
+}
+
+impl PokemonClient {
+    pub fn new(base_url: &str) -> Self {
+        PokemonClient {
+            base_url: base_url.to_owned()
+        }
+    }
+}
+
+#[async_trait]
+impl PokemonSource for PokemonClient {
+    async fn get_pokemon(&self, name_or_id: &str) -> Result<PokemonResponse, PokeClientException> {
+        get_pokemon(&self.base_url, name_or_id).await
+    }
+}
+
+pub async fn get_pokemon(base_url: &str, name_or_id: &str) -> Result<PokemonResponse, PokeClientException> {
+    get_resource(base_url, name_or_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use surf::StatusCode;
+    use serde_json::json;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use wiremock::matchers::{method, path};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn should_return_sprites_types_and_abilities() {
+        let mock_server = MockServer::start().await;
+
+        let body = json!({
+            "id": 25,
+            "name": "pikachu",
+            "sprites": {
+                "front_default": "https://img/front.png",
+                "back_default": null,
+                "front_shiny": null,
+                "back_shiny": null,
+                "other": {
+                    "official-artwork": { "front_default": "https://img/artwork.png" }
+                }
+            },
+            "types": [{ "slot": 1, "type": { "name": "electric", "url": "" } }],
+            "abilities": [{ "slot": 1, "is_hidden": false, "ability": { "name": "static", "url": "" } }]
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/pikachu"))
+            .respond_with(ResponseTemplate::new(StatusCode::Ok).set_body_json(body))
+            .mount(&mock_server)
+            .await;
+
+        let pokemon = get_pokemon(&mock_server.uri(), "pikachu").await.unwrap();
+
+        assert_eq!(pokemon.id, 25);
+        assert_eq!(pokemon.sprites.front_default, Some("https://img/front.png".to_owned()));
+        assert_eq!(pokemon.sprites.other["official-artwork"].front_default, Some("https://img/artwork.png".to_owned()));
+        assert_eq!(pokemon.types[0].pokemon_type.name, "electric");
+        assert_eq!(pokemon.abilities[0].ability.name, "static");
+    }
+
+    #[actix_rt::test]
+    async fn should_throw_pokemon_not_found_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/ozer"))
+            .respond_with(ResponseTemplate::new(StatusCode::NotFound))
+            .mount(&mock_server)
+            .await;
+
+        let result = get_pokemon(&mock_server.uri(), "ozer").await;
+
+        assert_eq!(result.err(), Some(PokeClientException::PokemonNotFound));
+    }
+}
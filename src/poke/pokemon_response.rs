@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::poke::poke_species_response::NamedApiResource;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Artwork {
+    pub front_default: Option<String>
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Sprites {
+    pub front_default: Option<String>,
+    pub back_default: Option<String>,
+    pub front_shiny: Option<String>,
+    pub back_shiny: Option<String>,
+    #[serde(default)]
+    pub other: HashMap<String, Artwork>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PokemonType {
+    pub slot: u8,
+    #[serde(rename = "type")]
+    pub pokemon_type: NamedApiResource,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PokemonAbility {
+    pub slot: u8,
+    pub is_hidden: bool,
+    pub ability: NamedApiResource,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PokemonResponse {
+    pub id: u16,
+    pub name: String,
+    #[serde(default)]
+    pub sprites: Sprites,
+    #[serde(default)]
+    pub types: Vec<PokemonType>,
+    #[serde(default)]
+    pub abilities: Vec<PokemonAbility>,
+}
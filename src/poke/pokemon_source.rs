@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::pokemon_response::PokemonResponse;
+
+#[async_trait]
+pub trait PokemonSource: Send + Sync {
+    /// Looks a Pokémon up by name or by its numeric id.
+    async fn get_pokemon(&self, name_or_id: &str) -> Result<PokemonResponse, PokeClientException>;
+}
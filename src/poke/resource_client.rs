@@ -0,0 +1,58 @@
+use std::marker::PhantomData;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use surf::{get, StatusCode};
+
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::resource_source::ResourceSource;
+
+pub struct ResourceClient<R> {
+    base_url: String,
+    resource: PhantomData<fn() -> R>,
+}
+
+impl<R> ResourceClient<R> {
+    pub fn new(base_url: &str) -> Self {
+        ResourceClient {
+            base_url: base_url.to_owned(),
+            resource: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: DeserializeOwned + Send + 'static> ResourceSource<R> for ResourceClient<R> {
+    async fn get_resource(&self, name: &str) -> Result<R, PokeClientException> {
+        // The shared lookup speaks of Pokémon; abilities and moves get errors of their own.
+        get_resource(&self.base_url, name).await
+            .map_err(|error| match error {
+                PokeClientException::PokemonNotFound => PokeClientException::ResourceNotFound,
+                error => error,
+            })
+    }
+}
+
+pub async fn get_resource<R: DeserializeOwned>(base_url: &str, name: &str) -> Result<R, PokeClientException> {
+    let mut url = base_url.to_owned();
+    url.push('/');
+    url.push_str(name);
+
+    let mut response = get(url).await.map_err(|_| {
+        PokeClientException::PokeClientWentWrong
+    })?;
+
+    match response.status() {
+        StatusCode::Ok => {
+            response.body_json().await.map_err(|_| {
+                PokeClientException::PokeClientWentWrong
+            })
+        }
+        StatusCode::NotFound => {
+            Err(PokeClientException::PokemonNotFound)
+        }
+        _ => {
+            Err(PokeClientException::PokeClientWentWrong)
+        }
+    }
+}
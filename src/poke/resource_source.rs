@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+use crate::poke::poke_client_exception::PokeClientException;
+
+/// Looks up PokeAPI resources of a single type, such as abilities or moves.
+#[async_trait]
+pub trait ResourceSource<R>: Send + Sync {
+    async fn get_resource(&self, name: &str) -> Result<R, PokeClientException>;
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+
+use crate::poke::flavor_text_resource::extract_english_flavor_text;
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::poke_species_response::PokeSpeciesResponse;
+
+#[async_trait]
+pub trait SpeciesSource: Send + Sync {
+    async fn get_pokemon_species(&self, name: &str) -> Result<PokeSpeciesResponse, PokeClientException>;
+
+    async fn get_pokemon_description(&self, name: &str) -> Result<String, PokeClientException> {
+        let poke_species_response = self.get_pokemon_species(name).await?;
+
+        extract_english_flavor_text_from_poke_species_response(poke_species_response)
+            .ok_or(PokeClientException::PokemonDescriptionNotFound)
+    }
+}
+
+pub fn extract_english_flavor_text_from_poke_species_response(response: PokeSpeciesResponse) -> Option<String> {
+    extract_english_flavor_text(&response)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::poke::poke_species_response::TextFlavorEntry;
+
+    use super::*;
+
+    fn generate_poke_species_response(language_name: String) -> PokeSpeciesResponse {
+        let flavor_text = "Flavor text".to_owned();
+        let flavor_text = vec![TextFlavorEntry::new(flavor_text, language_name)];
+        let id = 16;
+        let name = "pikachu".to_owned();
+        PokeSpeciesResponse::new(id, name, flavor_text)
+    }
+
+    #[test]
+    fn should_return_none_if_there_is_not_any_english_flavor_text() {
+        let language_name = "qwerty".to_owned();
+        let response = generate_poke_species_response(language_name);
+        let result = extract_english_flavor_text_from_poke_species_response(response);
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn should_return_some_description_if_there_is_an_english_flavor_text() {
+        let language_name = "en".to_owned();
+        let response = generate_poke_species_response(language_name);
+        let result = extract_english_flavor_text_from_poke_species_response(response);
+
+        assert_eq!(result, Some("Flavor text".to_owned()));
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::negotiation::representation::escape_markup;
+use crate::poke::pokemon_response::PokemonResponse;
+
+const OFFICIAL_ARTWORK: &str = "official-artwork";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PokemonSprites {
+    pub front_default: Option<String>,
+    pub back_default: Option<String>,
+    pub front_shiny: Option<String>,
+    pub back_shiny: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PokemonMedia {
+    pub sprites: PokemonSprites,
+    pub official_artwork: Option<String>,
+    pub types: Vec<String>,
+    pub abilities: Vec<String>,
+}
+
+impl PokemonMedia {
+    pub fn from_pokemon_response(response: &PokemonResponse) -> Self {
+        let mut types = response.types.to_vec();
+        types.sort_by_key(|pokemon_type| pokemon_type.slot);
+
+        let mut abilities = response.abilities.to_vec();
+        abilities.sort_by_key(|pokemon_ability| pokemon_ability.slot);
+
+        PokemonMedia {
+            sprites: PokemonSprites {
+                front_default: response.sprites.front_default.to_owned(),
+                back_default: response.sprites.back_default.to_owned(),
+                front_shiny: response.sprites.front_shiny.to_owned(),
+                back_shiny: response.sprites.back_shiny.to_owned(),
+            },
+            official_artwork: response.sprites.other.get(OFFICIAL_ARTWORK)
+                .and_then(|artwork| artwork.front_default.to_owned()),
+            types: types.into_iter().map(|pokemon_type| pokemon_type.pokemon_type.name).collect(),
+            abilities: abilities.into_iter().map(|pokemon_ability| pokemon_ability.ability.name).collect(),
+        }
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<div class=\"shakespearemon-media\">");
+
+        if let Some(image) = self.official_artwork.as_ref().or(self.sprites.front_default.as_ref()) {
+            html.push_str(&format!("<img class=\"shakespearemon-artwork\" src=\"{}\" alt=\"\">", escape_markup(image)));
+        }
+
+        html.push_str("<ul class=\"shakespearemon-types\">");
+        for pokemon_type in &self.types {
+            html.push_str(&format!("<li>{}</li>", escape_markup(pokemon_type)));
+        }
+        html.push_str("</ul><ul class=\"shakespearemon-abilities\">");
+        for ability in &self.abilities {
+            html.push_str(&format!("<li>{}</li>", escape_markup(ability)));
+        }
+        html.push_str("</ul></div>");
+        html
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<media><sprites>");
+
+        let sprites = vec![
+            ("front_default", &self.sprites.front_default),
+            ("back_default", &self.sprites.back_default),
+            ("front_shiny", &self.sprites.front_shiny),
+            ("back_shiny", &self.sprites.back_shiny),
+        ];
+        for (element, url) in sprites {
+            if let Some(url) = url {
+                xml.push_str(&format!("<{0}>{1}</{0}>", element, escape_markup(url)));
+            }
+        }
+        xml.push_str("</sprites>");
+
+        if let Some(artwork) = &self.official_artwork {
+            xml.push_str(&format!("<official_artwork>{}</official_artwork>", escape_markup(artwork)));
+        }
+
+        xml.push_str("<types>");
+        for pokemon_type in &self.types {
+            xml.push_str(&format!("<type>{}</type>", escape_markup(pokemon_type)));
+        }
+        xml.push_str("</types><abilities>");
+        for ability in &self.abilities {
+            xml.push_str(&format!("<ability>{}</ability>", escape_markup(ability)));
+        }
+        xml.push_str("</abilities></media>");
+        xml
+    }
+}
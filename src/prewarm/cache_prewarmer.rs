@@ -0,0 +1,129 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use actix_web::rt::{spawn, time::delay_for};
+use chrono::Utc;
+use log::{info, warn};
+
+use crate::poke::species_source::SpeciesSource;
+use crate::prewarm::prewarm_progress::{PrewarmProgress, PrewarmState};
+use crate::prewarm::prewarm_schedule::PrewarmSchedule;
+use crate::quota::translation_quota::TranslationQuota;
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::translation_pipeline::TranslationPipeline;
+use crate::translation_service::{describe_species, ShakespearemonException, ShakespearemonQuery};
+
+/// Translates a fixed list of species ahead of time so that their first
+/// requests of the day are served from the cache.
+///
+/// When a quota is attached, a run stops as soon as the remaining hourly or
+/// daily calls drop to the reserve left for user traffic.
+pub struct CachePrewarmer {
+    species_source: Arc<dyn SpeciesSource>,
+    translation_pipeline: TranslationPipeline,
+    translation_quota: Option<Arc<TranslationQuota>>,
+    reserved_quota: u32,
+    species: Vec<String>,
+    progress: Mutex<PrewarmProgress>,
+}
+
+impl CachePrewarmer {
+    pub fn new(species_source: Arc<dyn SpeciesSource>, translation_pipeline: TranslationPipeline, species: Vec<String>) -> Self {
+        CachePrewarmer {
+            species_source,
+            translation_pipeline,
+            translation_quota: None,
+            reserved_quota: 0,
+            progress: Mutex::new(PrewarmProgress::new(species.len())),
+            species,
+        }
+    }
+
+    pub fn with_translation_quota(mut self, translation_quota: Arc<TranslationQuota>, reserved_quota: u32) -> Self {
+        self.translation_quota = Some(translation_quota);
+        self.reserved_quota = reserved_quota;
+        self
+    }
+
+    pub fn progress(&self) -> PrewarmProgress {
+        self.progress.lock().unwrap().clone()
+    }
+
+    pub async fn run(&self) -> PrewarmProgress {
+        self.update_progress(|progress| {
+            progress.state = PrewarmState::Running;
+            progress.warmed = 0;
+            progress.failed = 0;
+            progress.started_at = Some(Utc::now().timestamp());
+            progress.finished_at = None;
+        });
+
+        let mut state = PrewarmState::Completed;
+
+        for name in &self.species {
+            if self.is_quota_low() {
+                state = PrewarmState::StoppedOnLowQuota;
+                break;
+            }
+
+            match self.warm(name).await {
+                Ok(()) => self.update_progress(|progress| progress.warmed += 1),
+                Err(ShakespearemonException::ShakespeareClientException(ShakespeareClientException::QuotaExhausted { .. })) => {
+                    state = PrewarmState::StoppedOnLowQuota;
+                    break;
+                }
+                Err(error) => {
+                    warn!("Pre-warming {} failed: {:?}", name, error);
+                    self.update_progress(|progress| progress.failed += 1);
+                }
+            }
+        }
+
+        self.update_progress(|progress| {
+            progress.state = state;
+            progress.finished_at = Some(Utc::now().timestamp());
+        });
+
+        let progress = self.progress();
+        info!("Pre-warming finished as {:?}: {} of {} warmed, {} failed", progress.state, progress.warmed, progress.total, progress.failed);
+        progress
+    }
+
+    pub fn start(self: Arc<Self>, schedule: PrewarmSchedule) {
+        spawn(async move {
+            loop {
+                let now = Utc::now().naive_utc();
+                let next_run_at = schedule.next_run_after(now);
+                self.update_progress(|progress| progress.next_run_at = Some(next_run_at.and_utc().timestamp()));
+
+                let wait = (next_run_at - now).to_std().unwrap_or_default();
+                delay_for(wait.max(Duration::from_secs(1))).await;
+
+                self.run().await;
+            }
+        });
+    }
+
+    async fn warm(&self, name: &str) -> Result<(), ShakespearemonException> {
+        let poke_species_response = self.species_source.get_pokemon_species(name).await
+            .map_err(|error| {
+                ShakespearemonException::PokeClientException(error)
+            })?;
+
+        describe_species(&self.translation_pipeline, poke_species_response, name.to_owned(), None, &ShakespearemonQuery::default()).await?;
+        Ok(())
+    }
+
+    fn is_quota_low(&self) -> bool {
+        self.translation_quota.as_ref()
+            .map(|quota| {
+                let status = quota.status();
+                status.hourly_remaining <= self.reserved_quota || status.daily_remaining <= self.reserved_quota
+            })
+            .unwrap_or(false)
+    }
+
+    fn update_progress<F: FnOnce(&mut PrewarmProgress)>(&self, update: F) {
+        update(&mut self.progress.lock().unwrap());
+    }
+}
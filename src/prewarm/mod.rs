@@ -0,0 +1,4 @@
+pub mod cache_prewarmer;
+pub mod prewarm_progress;
+pub mod prewarm_schedule;
+pub mod prewarm_target;
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrewarmState {
+    Idle,
+    Running,
+    Completed,
+    StoppedOnLowQuota,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrewarmProgress {
+    pub state: PrewarmState,
+    pub total: usize,
+    pub warmed: usize,
+    pub failed: usize,
+    pub started_at: Option<i64>,
+    pub finished_at: Option<i64>,
+    pub next_run_at: Option<i64>,
+}
+
+impl PrewarmProgress {
+    pub fn new(total: usize) -> Self {
+        PrewarmProgress {
+            state: PrewarmState::Idle,
+            total,
+            warmed: 0,
+            failed: 0,
+            started_at: None,
+            finished_at: None,
+            next_run_at: None,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+
+/// Runs once a day at a fixed UTC time of day, given as `HH:MM`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrewarmSchedule {
+    run_at: NaiveTime,
+}
+
+impl PrewarmSchedule {
+    pub fn parse(run_at: &str) -> Option<Self> {
+        NaiveTime::parse_from_str(run_at, "%H:%M").ok()
+            .map(|run_at| PrewarmSchedule { run_at })
+    }
+
+    pub fn next_run_after(&self, now: NaiveDateTime) -> NaiveDateTime {
+        let today = now.date().and_time(self.run_at);
+
+        if today > now {
+            today
+        } else {
+            today + Duration::days(1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2021, 3, 14).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn should_run_later_today_before_the_scheduled_time() {
+        let schedule = PrewarmSchedule::parse("04:30").unwrap();
+
+        assert_eq!(schedule.next_run_after(at(1, 0)), at(4, 30));
+    }
+
+    #[test]
+    fn should_run_tomorrow_once_the_scheduled_time_has_passed() {
+        let schedule = PrewarmSchedule::parse("04:30").unwrap();
+
+        assert_eq!(schedule.next_run_after(at(4, 30)), at(4, 30) + Duration::days(1));
+    }
+
+    #[test]
+    fn should_reject_malformed_times() {
+        assert_eq!(PrewarmSchedule::parse("25:00"), None);
+        assert_eq!(PrewarmSchedule::parse("4pm"), None);
+    }
+}
@@ -0,0 +1,54 @@
+/// Expands configured targets into species to fetch, where each target is
+/// either a name or id (`pikachu`, `25`) or an inclusive id range (`1-151`).
+pub fn expand_prewarm_targets(targets: &[String]) -> Result<Vec<String>, String> {
+    let mut species = Vec::new();
+
+    for target in targets {
+        let target = target.trim().to_lowercase();
+
+        match parse_id_range(&target) {
+            Some((start, end)) if start >= 1 && start <= end => {
+                species.extend((start..=end).map(|id| id.to_string()));
+            }
+            Some(_) => return Err(format!("Invalid species id range: {}", target)),
+            None if target.is_empty() => return Err("Empty pre-warm target".to_owned()),
+            None => species.push(target),
+        }
+    }
+
+    Ok(species)
+}
+
+fn parse_id_range(target: &str) -> Option<(u16, u16)> {
+    let (start, end) = target.split_once('-')?;
+    Some((start.parse().ok()?, end.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(targets: &[&str]) -> Vec<String> {
+        targets.iter().map(|target| target.to_string()).collect()
+    }
+
+    #[test]
+    fn should_expand_names_ids_and_ranges_in_order() {
+        let species = expand_prewarm_targets(&targets(&["Pikachu", "4-6", "150"])).unwrap();
+
+        assert_eq!(species, vec!["pikachu", "4", "5", "6", "150"]);
+    }
+
+    #[test]
+    fn should_keep_hyphenated_names() {
+        let species = expand_prewarm_targets(&targets(&["mr-mime", "ho-oh"])).unwrap();
+
+        assert_eq!(species, vec!["mr-mime", "ho-oh"]);
+    }
+
+    #[test]
+    fn should_reject_reversed_or_zero_based_ranges() {
+        assert!(expand_prewarm_targets(&targets(&["151-1"])).is_err());
+        assert!(expand_prewarm_targets(&targets(&["0-10"])).is_err());
+    }
+}
@@ -0,0 +1,2 @@
+pub mod quota_translator;
+pub mod translation_quota;
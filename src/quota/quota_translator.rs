@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::warn;
+
+use crate::quota::translation_quota::TranslationQuota;
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::shakespeare::translator::Translator;
+
+/// Refuses translator calls up front once our own quota tracking says the
+/// upstream quota is spent.
+pub struct QuotaTranslator {
+    translator: Arc<dyn Translator>,
+    quota: Arc<TranslationQuota>,
+}
+
+impl QuotaTranslator {
+    pub fn new(translator: Arc<dyn Translator>, quota: Arc<TranslationQuota>) -> Self {
+        QuotaTranslator {
+            translator,
+            quota,
+        }
+    }
+}
+
+#[async_trait]
+impl Translator for QuotaTranslator {
+    async fn get_shakespearean_translation(&self, text: &str) -> Result<String, ShakespeareClientException> {
+        self.quota.try_acquire().map_err(|reset_at| {
+            warn!("Translation quota exhausted until {}", reset_at);
+            ShakespeareClientException::QuotaExhausted { reset_at }
+        })?;
+
+        self.translator.get_shakespearean_translation(text).await
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_HOUR: f64 = 3600.0;
+const SECONDS_PER_DAY: u64 = 86400;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuotaStatus {
+    pub hourly_limit: u32,
+    pub hourly_remaining: u32,
+    pub hourly_reset_at: u64,
+    pub daily_limit: u32,
+    pub daily_remaining: u32,
+    pub daily_reset_at: u64,
+}
+
+struct QuotaState {
+    tokens: f64,
+    refilled_at: f64,
+    day: u64,
+    used_today: u32,
+}
+
+/// Tracks our own use of the translator: a token bucket refilled over the hour
+/// and a counter that resets at midnight UTC.
+pub struct TranslationQuota {
+    hourly_limit: u32,
+    daily_limit: u32,
+    state: Mutex<QuotaState>,
+}
+
+impl TranslationQuota {
+    pub fn new(hourly_limit: u32, daily_limit: u32) -> Self {
+        TranslationQuota::new_at(hourly_limit, daily_limit, now())
+    }
+
+    fn new_at(hourly_limit: u32, daily_limit: u32, now: f64) -> Self {
+        TranslationQuota {
+            hourly_limit,
+            daily_limit,
+            state: Mutex::new(QuotaState {
+                tokens: hourly_limit as f64,
+                refilled_at: now,
+                day: now as u64 / SECONDS_PER_DAY,
+                used_today: 0,
+            }),
+        }
+    }
+
+    /// Takes one call from the quota, or returns when the quota will allow the next one.
+    pub fn try_acquire(&self) -> Result<(), u64> {
+        self.try_acquire_at(now())
+    }
+
+    /// Takes `calls` calls from the quota at once, or none of them.
+    pub fn try_acquire_many(&self, calls: u32) -> Result<(), u64> {
+        self.try_acquire_many_at(calls, now())
+    }
+
+    pub fn status(&self) -> QuotaStatus {
+        self.status_at(now())
+    }
+
+    fn try_acquire_at(&self, now: f64) -> Result<(), u64> {
+        self.try_acquire_many_at(1, now)
+    }
+
+    fn try_acquire_many_at(&self, calls: u32, now: f64) -> Result<(), u64> {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state, now);
+
+        let hourly_exhausted = state.tokens < calls as f64;
+        let daily_exhausted = state.used_today.saturating_add(calls) > self.daily_limit;
+
+        if hourly_exhausted || daily_exhausted {
+            let mut reset_at = 0;
+            if hourly_exhausted {
+                reset_at = reset_at.max(self.next_tokens_at(&state, calls, now));
+            }
+            if daily_exhausted {
+                reset_at = reset_at.max(next_midnight(now));
+            }
+            return Err(reset_at);
+        }
+
+        state.tokens -= calls as f64;
+        state.used_today += calls;
+        Ok(())
+    }
+
+    fn status_at(&self, now: f64) -> QuotaStatus {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state, now);
+
+        QuotaStatus {
+            hourly_limit: self.hourly_limit,
+            hourly_remaining: state.tokens.floor() as u32,
+            hourly_reset_at: self.next_token_at(&state, now),
+            daily_limit: self.daily_limit,
+            daily_remaining: self.daily_limit.saturating_sub(state.used_today),
+            daily_reset_at: next_midnight(now),
+        }
+    }
+
+    fn refill(&self, state: &mut QuotaState, now: f64) {
+        let elapsed = (now - state.refilled_at).max(0.0);
+        state.tokens = (state.tokens + elapsed * self.refill_rate()).min(self.hourly_limit as f64);
+        state.refilled_at = now;
+
+        let today = now as u64 / SECONDS_PER_DAY;
+        if today != state.day {
+            state.day = today;
+            state.used_today = 0;
+        }
+    }
+
+    fn next_token_at(&self, state: &QuotaState, now: f64) -> u64 {
+        self.next_tokens_at(state, 1, now)
+    }
+
+    fn next_tokens_at(&self, state: &QuotaState, calls: u32, now: f64) -> u64 {
+        if state.tokens >= calls as f64 || self.hourly_limit == 0 {
+            return now.ceil() as u64;
+        }
+        (now + (calls as f64 - state.tokens) / self.refill_rate()).ceil() as u64
+    }
+
+    fn refill_rate(&self) -> f64 {
+        self.hourly_limit as f64 / SECONDS_PER_HOUR
+    }
+}
+
+fn next_midnight(now: f64) -> u64 {
+    (now as u64 / SECONDS_PER_DAY + 1) * SECONDS_PER_DAY
+}
+
+fn now() -> f64 {
+    SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs_f64())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: f64 = 1_600_000_000.0;
+
+    #[test]
+    fn should_refuse_once_hourly_tokens_are_spent_and_refill_over_time() {
+        let quota = TranslationQuota::new_at(2, 100, START);
+
+        assert_eq!(quota.try_acquire_at(START), Ok(()));
+        assert_eq!(quota.try_acquire_at(START), Ok(()));
+        assert_eq!(quota.try_acquire_at(START), Err(START as u64 + 1800));
+
+        assert_eq!(quota.try_acquire_at(START + 1800.0), Ok(()));
+    }
+
+    #[test]
+    fn should_refuse_until_midnight_once_daily_limit_is_reached() {
+        let quota = TranslationQuota::new_at(10, 1, START);
+
+        assert_eq!(quota.try_acquire_at(START), Ok(()));
+        assert_eq!(quota.try_acquire_at(START + 3600.0), Err(next_midnight(START)));
+
+        assert_eq!(quota.try_acquire_at(next_midnight(START) as f64), Ok(()));
+    }
+
+    #[test]
+    fn should_take_several_calls_at_once_or_none() {
+        let quota = TranslationQuota::new_at(10, 100, START);
+
+        assert_eq!(quota.try_acquire_many_at(8, START), Ok(()));
+        assert_eq!(quota.try_acquire_many_at(3, START), Err(START as u64 + 360));
+        assert_eq!(quota.try_acquire_many_at(2, START), Ok(()));
+        assert_eq!(quota.status_at(START).daily_remaining, 90);
+    }
+
+    #[test]
+    fn should_report_remaining_quota() {
+        let quota = TranslationQuota::new_at(5, 60, START);
+
+        quota.try_acquire_at(START).unwrap();
+
+        let status = quota.status_at(START);
+        assert_eq!(status.hourly_remaining, 4);
+        assert_eq!(status.daily_remaining, 59);
+        assert_eq!(status.daily_reset_at, next_midnight(START));
+    }
+}
@@ -0,0 +1,75 @@
+use std::net::IpAddr;
+
+use crate::rate_limit::ip_network::{IpNetwork, normalize};
+
+/// Works out which address a request came from. `X-Forwarded-For` is only
+/// believed when the peer is a trusted proxy, and is then read right to left
+/// up to the first hop that is not itself a trusted proxy.
+pub fn resolve_client_ip(peer: Option<IpAddr>, forwarded_for: Option<&str>, trusted_proxies: &[IpNetwork]) -> Option<IpAddr> {
+    let peer = normalize(peer?);
+
+    if !is_trusted(peer, trusted_proxies) {
+        return Some(peer);
+    }
+
+    let mut client = peer;
+    for hop in forwarded_for.unwrap_or_default().rsplit(',') {
+        match hop.trim().parse::<IpAddr>() {
+            Ok(hop) => {
+                client = normalize(hop);
+                if !is_trusted(client, trusted_proxies) {
+                    break;
+                }
+            }
+            // A hop we cannot read cannot be trusted either; stop at the last good one.
+            Err(_) => break,
+        }
+    }
+
+    Some(client)
+}
+
+fn is_trusted(ip: IpAddr, trusted_proxies: &[IpNetwork]) -> bool {
+    trusted_proxies.iter().any(|network| network.contains(ip))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(ip: &str) -> Option<IpAddr> {
+        Some(ip.parse().unwrap())
+    }
+
+    fn trusted_proxies() -> Vec<IpNetwork> {
+        vec![IpNetwork::parse("10.0.0.0/8").unwrap()]
+    }
+
+    #[test]
+    fn should_ignore_forwarded_for_from_untrusted_peers() {
+        let client = resolve_client_ip(ip("203.0.113.9"), Some("198.51.100.1"), &trusted_proxies());
+
+        assert_eq!(client, ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn should_take_the_first_untrusted_hop_from_the_right() {
+        let client = resolve_client_ip(ip("10.0.0.2"), Some("198.51.100.1, 203.0.113.9, 10.0.0.1"), &trusted_proxies());
+
+        assert_eq!(client, ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn should_fall_back_to_the_peer_without_forwarded_for() {
+        let client = resolve_client_ip(ip("10.0.0.2"), None, &trusted_proxies());
+
+        assert_eq!(client, ip("10.0.0.2"));
+    }
+
+    #[test]
+    fn should_stop_at_unreadable_hops() {
+        let client = resolve_client_ip(ip("10.0.0.2"), Some("203.0.113.9, garbage, 10.0.0.1"), &trusted_proxies());
+
+        assert_eq!(client, ip("10.0.0.1"));
+    }
+}
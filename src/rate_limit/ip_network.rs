@@ -0,0 +1,104 @@
+use std::net::IpAddr;
+
+/// An IPv4 or IPv6 network in CIDR notation; a bare address is a single host.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    pub fn parse(network: &str) -> Option<Self> {
+        let (address, prefix) = match network.trim().split_once('/') {
+            Some((address, prefix)) => (address.parse::<IpAddr>().ok()?, Some(prefix.parse::<u8>().ok()?)),
+            None => (network.trim().parse::<IpAddr>().ok()?, None),
+        };
+
+        let max_prefix = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        let prefix = prefix.unwrap_or(max_prefix);
+
+        if prefix > max_prefix {
+            return None;
+        }
+
+        Some(IpNetwork {
+            address,
+            prefix,
+        })
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.address, normalize(ip)) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.prefix)).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(self.prefix)).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false
+        }
+    }
+}
+
+pub fn parse_ip_networks(networks: &[String]) -> Result<Vec<IpNetwork>, String> {
+    networks.iter()
+        .map(|network| IpNetwork::parse(network).ok_or(format!("Invalid IP network: {}", network)))
+        .collect()
+}
+
+/// Treats IPv4-mapped IPv6 addresses (`::ffff:10.0.0.1`) as the IPv4 address
+/// they carry, as dual-stack listeners report them that way.
+pub fn normalize(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(ip) => ip.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(IpAddr::V6(ip)),
+        ip => ip
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    #[test]
+    fn should_match_addresses_inside_an_ipv4_network() {
+        let network = IpNetwork::parse("10.1.0.0/16").unwrap();
+
+        assert!(network.contains(ip("10.1.255.7")));
+        assert!(network.contains(ip("::ffff:10.1.0.1")));
+        assert!(!network.contains(ip("10.2.0.1")));
+    }
+
+    #[test]
+    fn should_match_addresses_inside_an_ipv6_network() {
+        let network = IpNetwork::parse("fd00::/8").unwrap();
+
+        assert!(network.contains(ip("fd12:3456::1")));
+        assert!(!network.contains(ip("2001:db8::1")));
+        assert!(!network.contains(ip("10.0.0.1")));
+    }
+
+    #[test]
+    fn should_treat_a_bare_address_as_a_single_host() {
+        let network = IpNetwork::parse("127.0.0.1").unwrap();
+
+        assert!(network.contains(ip("127.0.0.1")));
+        assert!(!network.contains(ip("127.0.0.2")));
+        assert!(IpNetwork::parse("0.0.0.0/0").unwrap().contains(ip("203.0.113.9")));
+    }
+
+    #[test]
+    fn should_reject_malformed_networks() {
+        assert_eq!(IpNetwork::parse("10.0.0.0/33"), None);
+        assert_eq!(IpNetwork::parse("localhost"), None);
+        assert!(parse_ip_networks(&["10.0.0.0/8".to_owned(), "nope".to_owned()]).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv6Addr};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::rate_limit::client_ip::resolve_client_ip;
+use crate::rate_limit::ip_network::{IpNetwork, normalize};
+
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+/// Evicting down to this leaves room for new clients before the next eviction.
+const TRACKED_CLIENTS_AFTER_EVICTION: usize = MAX_TRACKED_CLIENTS * 9 / 10;
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// A token bucket per client address: `burst` requests at once, refilled at
+/// `requests_per_minute`. IPv6 clients share a bucket per /64, the smallest
+/// network a host is usually given. Allowlisted networks are never limited.
+pub struct IpRateLimiter {
+    burst: f64,
+    refill_per_second: f64,
+    trusted_proxies: Vec<IpNetwork>,
+    allowlist: Vec<IpNetwork>,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl IpRateLimiter {
+    pub fn new(requests_per_minute: u32, burst: u32) -> Self {
+        IpRateLimiter {
+            burst: f64::from(burst.max(1)),
+            refill_per_second: f64::from(requests_per_minute.max(1)) / 60.0,
+            trusted_proxies: Vec::new(),
+            allowlist: Vec::new(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_trusted_proxies(mut self, trusted_proxies: Vec<IpNetwork>) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+
+    pub fn with_allowlist(mut self, allowlist: Vec<IpNetwork>) -> Self {
+        self.allowlist = allowlist;
+        self
+    }
+
+    pub fn client_ip(&self, peer: Option<IpAddr>, forwarded_for: Option<&str>) -> Option<IpAddr> {
+        resolve_client_ip(peer, forwarded_for, &self.trusted_proxies)
+    }
+
+    /// Takes a token for the client, or returns how many seconds until one is free.
+    pub fn check(&self, client: IpAddr) -> Result<(), u64> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: IpAddr, now: Instant) -> Result<(), u64> {
+        if self.allowlist.iter().any(|network| network.contains(client)) {
+            return Ok(());
+        }
+
+        let client = bucket_key(client);
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(&client) {
+            self.make_room(&mut buckets, now);
+        }
+
+        let bucket = buckets.entry(client).or_insert(Bucket {
+            tokens: self.burst,
+            refilled_at: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_second).min(self.burst);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / self.refill_per_second).ceil() as u64)
+        }
+    }
+
+    /// Drops clients whose buckets would be full again by now, then the least
+    /// recently seen ones down to [`TRACKED_CLIENTS_AFTER_EVICTION`], so the
+    /// sweep runs once per many new clients rather than for each of them.
+    fn make_room(&self, buckets: &mut HashMap<IpAddr, Bucket>, now: Instant) {
+        buckets.retain(|_, bucket| {
+            let elapsed = now.saturating_duration_since(bucket.refilled_at).as_secs_f64();
+            bucket.tokens + elapsed * self.refill_per_second < self.burst
+        });
+
+        if buckets.len() > TRACKED_CLIENTS_AFTER_EVICTION {
+            let excess = buckets.len() - TRACKED_CLIENTS_AFTER_EVICTION;
+            let mut seen: Vec<(Instant, IpAddr)> = buckets.iter()
+                .map(|(client, bucket)| (bucket.refilled_at, *client))
+                .collect();
+            seen.select_nth_unstable(excess - 1);
+
+            for (_, client) in &seen[..excess] {
+                buckets.remove(client);
+            }
+        }
+    }
+}
+
+/// IPv4 clients are limited per address and IPv6 clients per /64.
+fn bucket_key(client: IpAddr) -> IpAddr {
+    match normalize(client) {
+        IpAddr::V6(client) => IpAddr::V6(Ipv6Addr::from(u128::from(client) & !(u128::from(u64::MAX)))),
+        client => client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::time::Duration;
+
+    use super::*;
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    #[test]
+    fn should_allow_a_burst_then_refill_over_time() {
+        let limiter = IpRateLimiter::new(60, 2);
+        let start = Instant::now();
+
+        assert_eq!(limiter.check_at(ip("203.0.113.9"), start), Ok(()));
+        assert_eq!(limiter.check_at(ip("203.0.113.9"), start), Ok(()));
+        assert_eq!(limiter.check_at(ip("203.0.113.9"), start), Err(1));
+        assert_eq!(limiter.check_at(ip("198.51.100.1"), start), Ok(()));
+
+        assert_eq!(limiter.check_at(ip("203.0.113.9"), start + Duration::from_secs(1)), Ok(()));
+    }
+
+    #[test]
+    fn should_never_limit_allowlisted_networks() {
+        let limiter = IpRateLimiter::new(60, 1)
+            .with_allowlist(vec![IpNetwork::parse("10.0.0.0/8").unwrap()]);
+        let start = Instant::now();
+
+        for _ in 0..10 {
+            assert_eq!(limiter.check_at(ip("10.3.2.1"), start), Ok(()));
+        }
+    }
+
+    #[test]
+    fn should_share_a_bucket_within_an_ipv6_64() {
+        let limiter = IpRateLimiter::new(60, 1);
+        let start = Instant::now();
+
+        assert_eq!(limiter.check_at(ip("2001:db8:0:1::1"), start), Ok(()));
+        assert_eq!(limiter.check_at(ip("2001:db8:0:1:ffff::2"), start), Err(1));
+        assert_eq!(limiter.check_at(ip("2001:db8:0:2::1"), start), Ok(()));
+    }
+
+    #[test]
+    fn should_evict_the_least_recently_seen_clients_past_the_cap() {
+        let limiter = IpRateLimiter::new(1, 2);
+        let start = Instant::now();
+        let client = |index: usize| IpAddr::V4(Ipv4Addr::from(0x0a00_0000 + index as u32));
+
+        for index in 0..=MAX_TRACKED_CLIENTS {
+            assert_eq!(limiter.check_at(client(index), start + Duration::from_millis(index as u64)), Ok(()));
+        }
+
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), TRACKED_CLIENTS_AFTER_EVICTION + 1);
+        assert!(!buckets.contains_key(&client(0)));
+        assert!(buckets.contains_key(&client(MAX_TRACKED_CLIENTS - 1)));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod client_ip;
+pub mod ip_network;
+pub mod ip_rate_limiter;
+pub mod rate_limit_exception;
+pub mod rate_limit_middleware;
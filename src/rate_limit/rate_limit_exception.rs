@@ -0,0 +1,42 @@
+use actix_web::{HttpResponse, ResponseError};
+use actix_web::dev::HttpResponseBuilder;
+use actix_web::http::{header, StatusCode};
+use derive_more::{Display, Error};
+
+use crate::error_response::ErrorResponse;
+
+#[derive(Debug, Clone, Display, Error, PartialEq)]
+pub enum RateLimitException {
+    #[display(fmt = "Too many requests, retry in {} seconds", retry_after)]
+    TooManyRequests {
+        #[error(not(source))]
+        retry_after: u64,
+    },
+}
+
+impl RateLimitException {
+    pub fn code(&self) -> &'static str {
+        match self {
+            RateLimitException::TooManyRequests { .. } => "rate_limited",
+        }
+    }
+}
+
+impl ResponseError for RateLimitException {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            RateLimitException::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let RateLimitException::TooManyRequests { retry_after } = self;
+
+        HttpResponseBuilder::new(self.status_code())
+            .set_header(header::RETRY_AFTER, retry_after.to_string())
+            .json(ErrorResponse {
+                error: self.code().to_owned(),
+                message: self.to_string(),
+            })
+    }
+}
@@ -0,0 +1,113 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use actix_web::{Error, ResponseError};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::{header, HeaderMap, HeaderValue};
+use futures::future::{LocalBoxFuture, ok, Ready};
+
+use crate::rate_limit::ip_rate_limiter::IpRateLimiter;
+use crate::rate_limit::rate_limit_exception::RateLimitException;
+
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Limits how fast each client address may call the API.
+pub struct IpRateLimit {
+    limiter: Arc<IpRateLimiter>,
+}
+
+impl IpRateLimit {
+    pub fn new(limiter: Arc<IpRateLimiter>) -> Self {
+        IpRateLimit {
+            limiter
+        }
+    }
+}
+
+impl<S, B> Transform<S> for IpRateLimit
+    where
+        S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+        B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = IpRateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(IpRateLimitMiddleware {
+            service: Rc::new(RefCell::new(service)),
+            limiter: self.limiter.clone(),
+        })
+    }
+}
+
+pub struct IpRateLimitMiddleware<S> {
+    service: Rc<RefCell<S>>,
+    limiter: Arc<IpRateLimiter>,
+}
+
+impl<S, B> Service for IpRateLimitMiddleware<S>
+    where
+        S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+        B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, context: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.borrow_mut().poll_ready(context)
+    }
+
+    fn call(&mut self, request: ServiceRequest) -> Self::Future {
+        let forwarded_for = join_forwarded_for(forwarded_for_lines(request.headers()).into_iter()
+            .filter_map(|forwarded_for| forwarded_for.to_str().ok()));
+        let client = self.limiter.client_ip(request.peer_addr().map(|peer| peer.ip()), forwarded_for.as_deref());
+
+        if let Some(Err(retry_after)) = client.map(|client| self.limiter.check(client)) {
+            let error = RateLimitException::TooManyRequests { retry_after };
+            return Box::pin(async move {
+                Ok(request.into_response(error.error_response().into_body()))
+            });
+        }
+
+        let response = self.service.borrow_mut().call(request);
+        Box::pin(response)
+    }
+}
+
+/// The `X-Forwarded-For` lines in the order received. actix-http 2.2 stores the
+/// second value of a header ahead of the first, so that swap is undone whenever
+/// the header map is found to make it.
+fn forwarded_for_lines(headers: &HeaderMap) -> Vec<&HeaderValue> {
+    let mut lines: Vec<&HeaderValue> = headers.get_all(FORWARDED_FOR_HEADER).collect();
+    if lines.len() > 1 && header_map_swaps_appended_values() {
+        lines.swap(0, 1);
+    }
+    lines
+}
+
+fn header_map_swaps_appended_values() -> bool {
+    let mut headers = HeaderMap::new();
+    headers.append(header::VIA, HeaderValue::from_static("first"));
+    headers.append(header::VIA, HeaderValue::from_static("second"));
+    headers.get_all(header::VIA).next().is_some_and(|value| value == "second")
+}
+
+/// Joins every `X-Forwarded-For` line in the order received. A client can send a
+/// line of its own ahead of the one its proxy appends, so reading only the first
+/// would hand the client the address the trusted-proxy walk picks.
+pub fn join_forwarded_for<'a>(lines: impl Iterator<Item = &'a str>) -> Option<String> {
+    let lines: Vec<&str> = lines.collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(","))
+    }
+}
@@ -0,0 +1,152 @@
+use actix_web::{get, HttpRequest, HttpResponse, web};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::http_caching::{compute_etag, etag_source, respond_if_not_modified, respond_with_caching, respond_without_caching};
+use crate::negotiation::negotiated_exception::NegotiatedException;
+use crate::negotiation::representation::{escape_markup, Representation};
+use crate::negotiation::response_format::ResponseFormat;
+use crate::poke::ability_response::AbilityResponse;
+use crate::poke::flavor_text_resource::{extract_english_effect, extract_english_flavor_text, FlavorTextResource};
+use crate::poke::move_response::MoveResponse;
+use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::resource_source::ResourceSource;
+use crate::settings::Settings;
+use crate::translation_pipeline::TranslationPipeline;
+use crate::translation_service::{ShakespearemonException, ShakespearemonQuery};
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ResourceTranslationResponse {
+    pub name: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub effect: Option<String>,
+}
+
+impl Representation for ResourceTranslationResponse {
+    fn to_plain_text(&self) -> String {
+        self.description.to_owned()
+    }
+
+    fn to_html(&self) -> String {
+        format!(
+            "<article class=\"shakespearemon-card\"><h2 class=\"shakespearemon-name\">{}</h2><p class=\"shakespearemon-description\">{}</p>{}</article>",
+            escape_markup(&self.name),
+            escape_markup(&self.description),
+            self.effect.as_ref()
+                .map(|effect| format!("<p class=\"shakespearemon-effect\">{}</p>", escape_markup(effect)))
+                .unwrap_or_default(),
+        )
+    }
+
+    fn to_xml(&self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><shakespearemon><name>{}</name><description>{}</description>{}</shakespearemon>",
+            escape_markup(&self.name),
+            escape_markup(&self.description),
+            self.effect.as_ref()
+                .map(|effect| format!("<effect>{}</effect>", escape_markup(effect)))
+                .unwrap_or_default(),
+        )
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/ability/{name}",
+    tag = "abilities",
+    params(
+        ("name" = String, Path, description = "Ability name or id"),
+        ("include" = Option<String>, Query, description = "Comma-separated extras: `effect`"),
+    ),
+    responses(
+        (status = 200, description = "The ability description in Shakespearean English", body = ResourceTranslationResponse),
+        (status = 304, description = "The client's copy, named by `If-None-Match`, is current"),
+        (status = 404, description = "The ability is unknown or has no English description", body = ErrorResponse),
+        (status = 406, description = "None of the accepted media types can be produced", body = ErrorResponse),
+        (status = 429, description = "The translation quota is spent", body = ErrorResponse),
+        (status = 500, description = "An upstream service failed", body = ErrorResponse),
+        (status = 502, description = "The translator rejected our credentials", body = ErrorResponse),
+    )
+)]
+#[get("/ability/{name}")]
+pub async fn translate_ability_description(request: HttpRequest, data: web::Data<Settings>, ability_source: web::Data<dyn ResourceSource<AbilityResponse>>, translation_pipeline: TranslationPipeline, web::Path(name): web::Path<String>, web::Query(query): web::Query<ShakespearemonQuery>) -> Result<HttpResponse, NegotiatedException> {
+    translate_resource(&request, &data, ability_source.as_ref(), &translation_pipeline, name, &query).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/move/{name}",
+    tag = "moves",
+    params(
+        ("name" = String, Path, description = "Move name or id"),
+        ("include" = Option<String>, Query, description = "Comma-separated extras: `effect`"),
+    ),
+    responses(
+        (status = 200, description = "The move description in Shakespearean English", body = ResourceTranslationResponse),
+        (status = 304, description = "The client's copy, named by `If-None-Match`, is current"),
+        (status = 404, description = "The move is unknown or has no English description", body = ErrorResponse),
+        (status = 406, description = "None of the accepted media types can be produced", body = ErrorResponse),
+        (status = 429, description = "The translation quota is spent", body = ErrorResponse),
+        (status = 500, description = "An upstream service failed", body = ErrorResponse),
+        (status = 502, description = "The translator rejected our credentials", body = ErrorResponse),
+    )
+)]
+#[get("/move/{name}")]
+pub async fn translate_move_description(request: HttpRequest, data: web::Data<Settings>, move_source: web::Data<dyn ResourceSource<MoveResponse>>, translation_pipeline: TranslationPipeline, web::Path(name): web::Path<String>, web::Query(query): web::Query<ShakespearemonQuery>) -> Result<HttpResponse, NegotiatedException> {
+    translate_resource(&request, &data, move_source.as_ref(), &translation_pipeline, name, &query).await
+}
+
+async fn translate_resource<R: FlavorTextResource + Serialize>(request: &HttpRequest, data: &Settings, resource_source: &dyn ResourceSource<R>, translation_pipeline: &TranslationPipeline, name: String, query: &ShakespearemonQuery) -> Result<HttpResponse, NegotiatedException> {
+    let format = ResponseFormat::from_request(request).ok_or_else(NegotiatedException::not_acceptable)?;
+    let max_age_seconds = data.application.cache_max_age_seconds;
+
+    let resource = resource_source.get_resource(&name).await
+        .map_err(|error| {
+            NegotiatedException::new(ShakespearemonException::PokeClientException(error), format)
+        })?;
+
+    let etag = compute_etag(format, &[&name, &etag_source(&resource), &query.etag_source(), &data.application.translator_identity()]);
+    if let Some(not_modified) = respond_if_not_modified(request, &etag, max_age_seconds) {
+        return Ok(not_modified);
+    }
+
+    let resource_translation_response = describe_resource(resource, translation_pipeline, name, query).await
+        .map_err(|error| {
+            NegotiatedException::new(error, format)
+        })?;
+
+    if translation_pipeline.served_stand_in() {
+        return Ok(respond_without_caching(format, &resource_translation_response));
+    }
+
+    Ok(respond_with_caching(format, etag, &resource_translation_response, max_age_seconds))
+}
+
+async fn describe_resource<R: FlavorTextResource>(resource: R, translation_pipeline: &TranslationPipeline, name: String, query: &ShakespearemonQuery) -> Result<ResourceTranslationResponse, ShakespearemonException> {
+    // Some newer abilities and moves have no flavor text yet, only an effect.
+    let description = extract_english_flavor_text(&resource)
+        .or_else(|| extract_english_effect(&resource))
+        .ok_or(ShakespearemonException::PokeClientException(PokeClientException::ResourceDescriptionNotFound))?;
+
+    let translation = translation_pipeline.translate(&description).await?;
+
+    // The effect is an optional extra; losing it must not cost the client the description.
+    let effect = match extract_english_effect(&resource).filter(|_| query.includes("effect")) {
+        Some(effect) => match translation_pipeline.translate(&effect).await {
+            Ok(effect) => Some(effect),
+            Err(error) => {
+                warn!("Effect could not be translated for {}: {:?}", name, error);
+                None
+            }
+        },
+        None => None
+    };
+
+    Ok(ResourceTranslationResponse {
+        name,
+        description: translation,
+        effect,
+    })
+}
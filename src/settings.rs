@@ -1,17 +1,293 @@
+use std::num::NonZeroU32;
+
 use config::{Config, ConfigError, File};
 use serde::Deserialize;
 
+use crate::shakespeare::api_secret::{ApiSecret, load_api_secret};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TranslatorEngine {
+    #[default]
+    Remote,
+    Local,
+    RemoteWithLocalFallback,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Application {
     pub host: String,
     pub port: u16,
     pub poke_api_base_url: String,
+    #[serde(default = "default_poke_api_pokemon_base_url")]
+    pub poke_api_pokemon_base_url: String,
+    #[serde(default = "default_poke_api_ability_base_url")]
+    pub poke_api_ability_base_url: String,
+    #[serde(default = "default_poke_api_move_base_url")]
+    pub poke_api_move_base_url: String,
     pub shakespeare_translator_api_base_url: String,
+    pub translation_store_path: Option<String>,
+    #[serde(default)]
+    pub translator_engine: TranslatorEngine,
+    #[serde(default)]
+    pub local_translator_flourishes: bool,
+    #[serde(default = "default_max_text_length")]
+    pub max_text_length: usize,
+    #[serde(default = "default_cache_max_age_seconds")]
+    pub cache_max_age_seconds: u64,
+}
+
+impl Application {
+    /// Names the translator these settings build. Stand-ins from the local fallback
+    /// are never cached, so only the remote translator names a fallback engine.
+    pub fn translator_identity(&self) -> String {
+        match self.translator_engine {
+            TranslatorEngine::Remote | TranslatorEngine::RemoteWithLocalFallback => format!("remote;{}", self.shakespeare_translator_api_base_url),
+            TranslatorEngine::Local => format!("local;flourishes={}", self.local_translator_flourishes),
+        }
+    }
+}
+
+fn default_poke_api_pokemon_base_url() -> String {
+    "https://pokeapi.co/api/v2/pokemon".to_owned()
+}
+
+fn default_poke_api_ability_base_url() -> String {
+    "https://pokeapi.co/api/v2/ability".to_owned()
+}
+
+fn default_poke_api_move_base_url() -> String {
+    "https://pokeapi.co/api/v2/move".to_owned()
+}
+
+fn default_max_text_length() -> usize {
+    1000
+}
+
+fn default_cache_max_age_seconds() -> u64 {
+    86400
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Server {
+    /// Defaults to one worker per CPU core.
+    pub workers: Option<usize>,
+    pub max_connections: usize,
+    /// Zero disables keep-alive.
+    pub keep_alive_seconds: usize,
+    pub client_timeout_milliseconds: u64,
+    pub shutdown_timeout_seconds: u64,
+    pub backlog: i32,
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Server {
+            workers: None,
+            max_connections: 25_000,
+            keep_alive_seconds: 5,
+            client_timeout_milliseconds: 5000,
+            shutdown_timeout_seconds: 30,
+            backlog: 2048,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Cache {
+    pub redis_url: String,
+    pub namespace: String,
+    pub ttl_seconds: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Quota {
+    pub hourly_limit: u32,
+    pub daily_limit: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Daily {
+    pub seed: u64,
+    pub species_count: u16,
+}
+
+impl Default for Daily {
+    fn default() -> Self {
+        Daily {
+            seed: 0,
+            species_count: 1025,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Jobs {
+    pub max_names: usize,
+    /// How many batch jobs may run at once; each holds its events in memory.
+    pub max_running_jobs: usize,
+    /// How long a finished batch job's results can still be fetched.
+    pub retention_seconds: u64,
+}
+
+impl Default for Jobs {
+    fn default() -> Self {
+        Jobs {
+            max_names: 1025,
+            max_running_jobs: 16,
+            retention_seconds: 86400,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Prewarm {
+    pub species: Vec<String>,
+    #[serde(default = "default_prewarm_run_at")]
+    pub run_at: String,
+    #[serde(default)]
+    pub reserved_quota: u32,
+}
+
+fn default_prewarm_run_at() -> String {
+    "04:00".to_owned()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfiguredApiKey {
+    pub key: String,
+    pub name: String,
+    pub hourly_limit: u32,
+    pub daily_limit: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Auth {
+    pub admin_key: String,
+    #[serde(default)]
+    pub keys: Vec<ConfiguredApiKey>,
+    #[serde(default = "default_api_key_hourly_limit")]
+    pub default_hourly_limit: u32,
+    #[serde(default = "default_api_key_daily_limit")]
+    pub default_daily_limit: u32,
+}
+
+fn default_api_key_hourly_limit() -> u32 {
+    100
+}
+
+fn default_api_key_daily_limit() -> u32 {
+    1000
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RateLimit {
+    /// A limiter that never refills would lock clients out for good, so 0 is refused.
+    pub requests_per_minute: NonZeroU32,
+    pub burst: u32,
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Cors {
+    pub allowed_origins: Vec<String>,
+    #[serde(default = "default_cors_allowed_methods")]
+    pub allowed_methods: Vec<String>,
+    #[serde(default = "default_cors_allowed_headers")]
+    pub allowed_headers: Vec<String>,
+    #[serde(default = "default_cors_exposed_headers")]
+    pub exposed_headers: Vec<String>,
+    #[serde(default = "default_cors_max_age_seconds")]
+    pub max_age_seconds: Option<usize>,
+    #[serde(default)]
+    pub supports_credentials: bool,
+}
+
+fn default_cors_allowed_methods() -> Vec<String> {
+    vec!["GET".to_owned(), "POST".to_owned(), "DELETE".to_owned()]
+}
+
+fn default_cors_allowed_headers() -> Vec<String> {
+    vec!["accept".to_owned(), "content-type".to_owned(), "if-none-match".to_owned(), "x-api-key".to_owned()]
+}
+
+fn default_cors_exposed_headers() -> Vec<String> {
+    ["etag", "retry-after", "x-ratelimit-limit", "x-ratelimit-remaining", "x-ratelimit-reset"].iter()
+        .map(|header| header.to_string())
+        .collect()
+}
+
+fn default_cors_max_age_seconds() -> Option<usize> {
+    Some(3600)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Tls {
+    pub cert_path: String,
+    pub key_path: String,
+    #[serde(default = "default_https_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub redirect_http: bool,
+    #[serde(default)]
+    pub allow_plain_http: bool,
+    pub hsts_max_age_seconds: Option<u64>,
+    #[serde(default)]
+    pub hsts_include_subdomains: bool,
+}
+
+fn default_https_port() -> u16 {
+    8443
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Grpc {
+    #[serde(default = "default_grpc_port")]
+    pub port: u16,
+}
+
+fn default_grpc_port() -> u16 {
+    50051
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Funtranslations {
+    pub api_secret_env: Option<String>,
+    pub api_secret_file: Option<String>,
+}
+
+impl Funtranslations {
+    pub fn api_secret(&self) -> Result<Option<ApiSecret>, String> {
+        load_api_secret(self.api_secret_env.as_deref(), self.api_secret_file.as_deref())
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Settings {
-    pub application: Application
+    pub application: Application,
+    #[serde(default)]
+    pub server: Server,
+    #[serde(default)]
+    pub daily: Daily,
+    #[serde(default)]
+    pub funtranslations: Funtranslations,
+    #[serde(default)]
+    pub jobs: Jobs,
+    pub cache: Option<Cache>,
+    pub quota: Option<Quota>,
+    pub prewarm: Option<Prewarm>,
+    pub auth: Option<Auth>,
+    pub rate_limit: Option<RateLimit>,
+    pub tls: Option<Tls>,
+    pub cors: Option<Cors>,
+    pub grpc: Option<Grpc>,
 }
 
 impl Settings {
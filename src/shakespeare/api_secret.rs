@@ -0,0 +1,89 @@
+use std::fmt;
+
+/// The funtranslations API secret. It never prints, so settings and errors
+/// can be logged with `{:?}` without leaking it.
+#[derive(Clone, PartialEq)]
+pub struct ApiSecret(String);
+
+impl ApiSecret {
+    pub fn new(secret: &str) -> Self {
+        ApiSecret(secret.trim().to_owned())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ApiSecret {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("ApiSecret(<redacted>)")
+    }
+}
+
+/// Reads the secret from the named environment variable, falling back to the
+/// file when the variable is unset. Having neither configured is not an error:
+/// the free funtranslations plan needs no secret.
+pub fn load_api_secret(env: Option<&str>, file: Option<&str>) -> Result<Option<ApiSecret>, String> {
+    if let Some(secret) = env.and_then(|env| std::env::var(env).ok()) {
+        return non_empty(ApiSecret::new(&secret)).map(Some);
+    }
+
+    match file {
+        Some(file) => {
+            let secret = std::fs::read_to_string(file).map_err(|error| {
+                format!("Unable to read the funtranslations API secret from {}: {}", file, error)
+            })?;
+            non_empty(ApiSecret::new(&secret)).map(Some)
+        }
+        None => Ok(None)
+    }
+}
+
+fn non_empty(secret: ApiSecret) -> Result<ApiSecret, String> {
+    if secret.expose().is_empty() {
+        Err("The funtranslations API secret is empty".to_owned())
+    } else {
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_never_print_the_secret() {
+        let secret = ApiSecret::new("hunter2");
+
+        assert!(!format!("{:?}", Some(secret)).contains("hunter2"));
+    }
+
+    #[test]
+    fn should_prefer_the_environment_over_the_file() {
+        std::env::set_var("SHAKESPEAREMON_TEST_API_SECRET_PREFERRED", " from-env\n");
+
+        let secret = load_api_secret(Some("SHAKESPEAREMON_TEST_API_SECRET_PREFERRED"), Some("/does/not/exist")).unwrap();
+
+        assert_eq!(secret, Some(ApiSecret::new("from-env")));
+    }
+
+    #[test]
+    fn should_read_the_file_when_the_environment_variable_is_unset() {
+        let file = std::env::temp_dir().join("shakespearemon-test-api-secret");
+        std::fs::write(&file, "from-file\n").unwrap();
+
+        let secret = load_api_secret(Some("SHAKESPEAREMON_TEST_API_SECRET_UNSET"), file.to_str()).unwrap();
+
+        assert_eq!(secret.unwrap().expose(), "from-file");
+    }
+
+    #[test]
+    fn should_fail_on_missing_files_and_empty_secrets() {
+        std::env::set_var("SHAKESPEAREMON_TEST_API_SECRET_EMPTY", "  ");
+
+        assert!(load_api_secret(None, Some("/does/not/exist")).is_err());
+        assert!(load_api_secret(Some("SHAKESPEAREMON_TEST_API_SECRET_EMPTY"), None).is_err());
+        assert_eq!(load_api_secret(None, None), Ok(None));
+    }
+}
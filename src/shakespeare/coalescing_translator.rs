@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::FutureExt;
+
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::shakespeare::translator::{Translation, Translator};
+use crate::single_flight::SingleFlight;
+
+/// Lets concurrent requests for the same text share one translator call.
+pub struct CoalescingTranslator {
+    translator: Arc<dyn Translator>,
+    in_flight: SingleFlight<Result<Translation, ShakespeareClientException>>,
+}
+
+impl CoalescingTranslator {
+    pub fn new(translator: Arc<dyn Translator>) -> Self {
+        CoalescingTranslator {
+            translator,
+            in_flight: SingleFlight::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Translator for CoalescingTranslator {
+    async fn get_shakespearean_translation(&self, text: &str) -> Result<String, ShakespeareClientException> {
+        self.get_translation(text).await.map(|translation| translation.text)
+    }
+
+    async fn get_translation(&self, text: &str) -> Result<Translation, ShakespeareClientException> {
+        let translator = self.translator.clone();
+        let owned_text = text.to_owned();
+
+        self.in_flight.run(text, move || async move {
+            translator.get_translation(&owned_text).await
+        }.boxed()).await
+    }
+}
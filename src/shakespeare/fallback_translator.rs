@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::warn;
+
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::shakespeare::translator::{Translation, Translator};
+
+/// Asks the primary translator first and the fallback whenever the primary fails.
+/// The fallback's translations are stand-ins.
+pub struct FallbackTranslator {
+    primary: Arc<dyn Translator>,
+    fallback: Arc<dyn Translator>,
+}
+
+impl FallbackTranslator {
+    pub fn new(primary: Arc<dyn Translator>, fallback: Arc<dyn Translator>) -> Self {
+        FallbackTranslator {
+            primary,
+            fallback,
+        }
+    }
+}
+
+#[async_trait]
+impl Translator for FallbackTranslator {
+    async fn get_shakespearean_translation(&self, text: &str) -> Result<String, ShakespeareClientException> {
+        self.get_translation(text).await.map(|translation| translation.text)
+    }
+
+    async fn get_translation(&self, text: &str) -> Result<Translation, ShakespeareClientException> {
+        match self.primary.get_translation(text).await {
+            Ok(translation) => Ok(translation),
+            Err(error) => {
+                warn!("Primary translator failed, falling back: {:?}", error);
+                self.fallback.get_translation(text).await.map(|translation| Translation {
+                    stand_in: true,
+                    ..translation
+                })
+            }
+        }
+    }
+}
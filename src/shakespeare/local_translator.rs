@@ -0,0 +1,367 @@
+use async_trait::async_trait;
+
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::shakespeare::translator::Translator;
+
+/// Multi-word substitutions, tried longest first before single words.
+const PHRASES: &[(&[&str], &[&str])] = &[
+    (&["how", "are", "you"], &["how", "art", "thou"]),
+    (&["you", "are"], &["thou", "art"]),
+    (&["are", "you"], &["art", "thou"]),
+    (&["do", "you"], &["dost", "thou"]),
+    (&["have", "you"], &["hast", "thou"]),
+    (&["you", "have"], &["thou", "hast"]),
+    (&["it", "is"], &["'tis"]),
+    (&["is", "it"], &["is't"]),
+    (&["i", "think"], &["methinks"]),
+    (&["over", "there"], &["yonder"]),
+    (&["good", "morning"], &["good", "morrow"]),
+];
+
+const WORDS: &[(&str, &str)] = &[
+    ("your", "thy"),
+    ("yours", "thine"),
+    ("yourself", "thyself"),
+    ("hello", "good morrow"),
+    ("hi", "hail"),
+    ("yes", "aye"),
+    ("no", "nay"),
+    ("before", "ere"),
+    ("often", "oft"),
+    ("perhaps", "perchance"),
+    ("maybe", "perchance"),
+    ("why", "wherefore"),
+    ("nothing", "naught"),
+    ("anything", "aught"),
+    ("enemy", "foe"),
+    ("enemies", "foes"),
+    ("soon", "anon"),
+    ("between", "betwixt"),
+    ("among", "amongst"),
+    ("until", "till"),
+    ("very", "most"),
+    ("has", "hath"),
+    ("does", "doth"),
+];
+
+/// Verb forms after "thou".
+const SECOND_PERSON_VERBS: &[(&str, &str)] = &[
+    ("are", "art"),
+    ("were", "wert"),
+    ("have", "hast"),
+    ("do", "dost"),
+    ("can", "canst"),
+    ("will", "wilt"),
+    ("shall", "shalt"),
+    ("would", "wouldst"),
+    ("could", "couldst"),
+    ("should", "shouldst"),
+    ("may", "mayst"),
+];
+
+/// Words ending in "s" after "he", "she" or "it" that are not verbs to conjugate.
+const THIRD_PERSON_EXCEPTIONS: &[&str] = &[
+    "is", "was", "has", "does", "its", "this", "as", "us", "thus", "always", "perhaps", "sometimes", "yes",
+];
+
+/// Adverbs that may sit between a pronoun and its verb.
+const ADVERBS: &[&str] = &["also", "often", "oft", "always", "never", "sometimes", "then", "still", "even"];
+
+/// Words after which "you" is the subject of a new clause.
+const CLAUSE_OPENERS: &[&str] = &["if", "when", "and", "but", "because", "while", "unless", "that", "so", "or", "as"];
+
+const OPENING_FLOURISHES: &[&str] = &["Hark! ", "Lo! ", "Attend! "];
+const CLOSING_FLOURISHES: &[&str] = &[" Verily.", " So it is writ.", " Mark my words."];
+
+/// Translates text to Shakespearean English with fixed substitution and conjugation
+/// rules, so the same text always yields the same translation.
+#[derive(Default)]
+pub struct LocalTranslator {
+    flourishes: bool,
+}
+
+struct Word {
+    text: String,
+    separator: String,
+}
+
+impl LocalTranslator {
+    pub fn new(flourishes: bool) -> Self {
+        LocalTranslator {
+            flourishes
+        }
+    }
+
+    pub fn translate(&self, text: &str) -> String {
+        let (prefix, words) = tokenize(text);
+
+        let words = substitute_phrases(words);
+        let words = substitute_words(&prefix, words);
+        let words = conjugate_verbs(words);
+
+        let mut translated = prefix;
+        for word in words {
+            translated.push_str(&word.text);
+            translated.push_str(&word.separator);
+        }
+
+        if self.flourishes && !translated.trim().is_empty() {
+            translated = add_flourishes(translated);
+        }
+
+        translated
+    }
+}
+
+#[async_trait]
+impl Translator for LocalTranslator {
+    async fn get_shakespearean_translation(&self, text: &str) -> Result<String, ShakespeareClientException> {
+        Ok(self.translate(text))
+    }
+}
+
+fn tokenize(text: &str) -> (String, Vec<Word>) {
+    let mut prefix = String::new();
+    let mut words: Vec<Word> = Vec::new();
+
+    for character in text.chars() {
+        let is_word_character = character.is_alphanumeric() || character == '\'';
+
+        match words.last_mut() {
+            Some(word) if is_word_character && word.separator.is_empty() => word.text.push(character),
+            Some(word) if !is_word_character => word.separator.push(character),
+            None if !is_word_character => prefix.push(character),
+            _ => words.push(Word {
+                text: character.to_string(),
+                separator: String::new(),
+            }),
+        }
+    }
+
+    (prefix, words)
+}
+
+fn substitute_phrases(words: Vec<Word>) -> Vec<Word> {
+    let mut result: Vec<Word> = Vec::with_capacity(words.len());
+    let mut index = 0;
+
+    while index < words.len() {
+        let phrase = PHRASES.iter()
+            .filter(|(phrase, _)| matches_phrase(&words[index..], phrase))
+            .max_by_key(|(phrase, _)| phrase.len());
+
+        match phrase {
+            Some((phrase, replacement)) => {
+                let separator = words[index + phrase.len() - 1].separator.to_owned();
+
+                for (position, replacement_word) in replacement.iter().enumerate() {
+                    let text = if position == 0 {
+                        match_case(&words[index].text, replacement_word)
+                    } else {
+                        (*replacement_word).to_owned()
+                    };
+                    let separator = if position == replacement.len() - 1 { separator.to_owned() } else { " ".to_owned() };
+
+                    result.push(Word {
+                        text,
+                        separator,
+                    });
+                }
+
+                index += phrase.len();
+            }
+            None => {
+                let word = &words[index];
+                result.push(Word {
+                    text: word.text.to_owned(),
+                    separator: word.separator.to_owned(),
+                });
+                index += 1;
+            }
+        }
+    }
+
+    result
+}
+
+fn matches_phrase(words: &[Word], phrase: &[&str]) -> bool {
+    if words.len() < phrase.len() {
+        return false;
+    }
+
+    phrase.iter().enumerate().all(|(position, phrase_word)| {
+        let word = &words[position];
+        let joined_by_space = position == phrase.len() - 1 || word.separator.chars().all(char::is_whitespace);
+        word.text.to_lowercase() == *phrase_word && joined_by_space
+    })
+}
+
+fn substitute_words(prefix: &str, words: Vec<Word>) -> Vec<Word> {
+    let mut starts_sentence = true;
+    let mut previous_word = String::new();
+    let mut previous_separator = prefix.to_owned();
+
+    words.into_iter().map(|word| {
+        if !previous_separator.trim().is_empty() {
+            starts_sentence = previous_separator.contains(['.', '!', '?']);
+        }
+
+        let lowercase = word.text.to_lowercase();
+        let replacement = if lowercase == "you" {
+            let is_subject = starts_sentence || CLAUSE_OPENERS.contains(&previous_word.as_str());
+            let is_followed_by_word = word.separator.chars().all(char::is_whitespace);
+            Some(if is_subject && is_followed_by_word { "thou" } else { "thee" })
+        } else {
+            WORDS.iter()
+                .find(|(original, _)| *original == lowercase)
+                .map(|(_, replacement)| *replacement)
+        };
+
+        starts_sentence = false;
+        previous_word = lowercase;
+        previous_separator = word.separator.to_owned();
+
+        Word {
+            text: replacement.map(|replacement| match_case(&word.text, replacement)).unwrap_or(word.text),
+            separator: word.separator,
+        }
+    }).collect()
+}
+
+fn conjugate_verbs(mut words: Vec<Word>) -> Vec<Word> {
+    for index in 1..words.len() {
+        let subject = match find_subject(&words, index) {
+            Some(subject) => subject,
+            None => continue,
+        };
+
+        let lowercase = words[index].text.to_lowercase();
+        let conjugated = match subject.as_str() {
+            "thou" => SECOND_PERSON_VERBS.iter()
+                .find(|(original, _)| *original == lowercase)
+                .map(|(_, conjugated)| (*conjugated).to_owned()),
+            _ => conjugate_third_person(&lowercase),
+        };
+
+        if let Some(conjugated) = conjugated {
+            words[index].text = match_case(&words[index].text, &conjugated);
+        }
+    }
+
+    words
+}
+
+/// Finds the pronoun a word is the verb of, looking past one adverb.
+fn find_subject(words: &[Word], index: usize) -> Option<String> {
+    let mut position = index;
+
+    for _ in 0..2 {
+        let previous = &words[position - 1];
+        if !previous.separator.chars().all(char::is_whitespace) {
+            return None;
+        }
+
+        let lowercase = previous.text.to_lowercase();
+        match lowercase.as_str() {
+            "thou" | "he" | "she" | "it" => return Some(lowercase),
+            adverb if (ADVERBS.contains(&adverb) || adverb.ends_with("ly")) && position > 1 => position -= 1,
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+fn conjugate_third_person(verb: &str) -> Option<String> {
+    if verb.len() <= 3 || !verb.ends_with('s') || verb.ends_with("ss") || verb.ends_with("us") || THIRD_PERSON_EXCEPTIONS.contains(&verb) {
+        return None;
+    }
+
+    if verb == "says" {
+        return Some("saith".to_owned());
+    }
+
+    if let Some(stem) = verb.strip_suffix("ies") {
+        return Some(format!("{}ieth", stem));
+    }
+
+    if let Some(stem) = verb.strip_suffix("es") {
+        if stem.ends_with("ch") || stem.ends_with("sh") || stem.ends_with('x') || stem.ends_with('z') || stem.ends_with('s') {
+            return Some(format!("{}eth", stem));
+        }
+    }
+
+    let stem = &verb[..verb.len() - 1];
+    if stem.ends_with('e') {
+        Some(format!("{}th", stem))
+    } else {
+        Some(format!("{}eth", stem))
+    }
+}
+
+fn match_case(original: &str, replacement: &str) -> String {
+    let starts_uppercase = original.chars().next().map(char::is_uppercase).unwrap_or(false);
+    let is_single_letter = original.chars().count() == 1;
+    let all_uppercase = !is_single_letter && original.chars().filter(|character| character.is_alphabetic()).all(char::is_uppercase);
+
+    if all_uppercase {
+        replacement.to_uppercase()
+    } else if starts_uppercase {
+        let mut characters = replacement.chars();
+        match characters.next() {
+            Some(first) => first.to_uppercase().chain(characters).collect(),
+            None => String::new(),
+        }
+    } else {
+        replacement.to_owned()
+    }
+}
+
+/// Picks the flourishes from the text itself so the output stays deterministic.
+fn add_flourishes(translated: String) -> String {
+    let seed = translated.bytes().fold(0usize, |seed, byte| seed.wrapping_mul(31).wrapping_add(byte as usize));
+
+    let opening = OPENING_FLOURISHES[seed % OPENING_FLOURISHES.len()];
+    let closing = CLOSING_FLOURISHES[(seed / OPENING_FLOURISHES.len()) % CLOSING_FLOURISHES.len()];
+
+    format!("{}{}{}", opening, translated.trim_end(), closing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_text_without_rules_untouched() {
+        let translator = LocalTranslator::new(false);
+
+        assert_eq!(translator.translate("A mouse, 40 cm tall!"), "A mouse, 40 cm tall!");
+    }
+
+    #[test]
+    fn should_preserve_case_of_substituted_words() {
+        let translator = LocalTranslator::new(false);
+
+        assert_eq!(translator.translate("Hello. YES, you are."), "Good morrow. AYE, thou art.");
+    }
+
+    #[test]
+    fn should_conjugate_third_person_verbs_past_an_adverb() {
+        let translator = LocalTranslator::new(false);
+
+        assert_eq!(translator.translate("It also stores electricity."), "It also storeth electricity.");
+        assert_eq!(translator.translate("She watches and he flies."), "She watcheth and he flieth.");
+    }
+
+    #[test]
+    fn should_add_the_same_flourishes_to_the_same_text() {
+        let translator = LocalTranslator::new(true);
+
+        let first = translator.translate("It keeps its tail raised.");
+        let second = translator.translate("It keeps its tail raised.");
+
+        assert_eq!(first, second);
+        assert!(first.contains("It keepeth its tail raised."));
+        assert_ne!(first, "It keepeth its tail raised.");
+    }
+}
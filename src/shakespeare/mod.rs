@@ -1,4 +1,9 @@
+pub mod api_secret;
+pub mod coalescing_translator;
+pub mod fallback_translator;
+pub mod local_translator;
 pub mod shakespeare_client;
 pub mod shakespeare_client_exception;
 pub mod shakespeare_translation_response;
-pub mod shakespeare_translation_request;
\ No newline at end of file
+pub mod shakespeare_translation_request;
+pub mod translator;
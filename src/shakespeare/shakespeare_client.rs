@@ -1,18 +1,54 @@
+use async_trait::async_trait;
+use log::error;
 use surf::{post, StatusCode};
 
+use crate::shakespeare::api_secret::ApiSecret;
 use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
 use crate::shakespeare::shakespeare_translation_request::ShakespeareTranslationRequest;
 use crate::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use crate::shakespeare::translator::Translator;
 
-pub async fn get_shakespearean_translation(url: &str, text: &str) -> Result<String, ShakespeareClientException> {
+pub const API_SECRET_HEADER: &str = "X-Funtranslations-Api-Secret";
+
+pub struct ShakespeareClient {
+    url: String,
+    api_secret: Option<ApiSecret>,
+}
+
+impl ShakespeareClient {
+    pub fn new(url: &str) -> Self {
+        ShakespeareClient {
+            url: url.to_owned(),
+            api_secret: None,
+        }
+    }
+
+    pub fn with_api_secret(mut self, api_secret: ApiSecret) -> Self {
+        self.api_secret = Some(api_secret);
+        self
+    }
+}
+
+#[async_trait]
+impl Translator for ShakespeareClient {
+    async fn get_shakespearean_translation(&self, text: &str) -> Result<String, ShakespeareClientException> {
+        get_shakespearean_translation(&self.url, self.api_secret.as_ref(), text).await
+    }
+}
+
+pub async fn get_shakespearean_translation(url: &str, api_secret: Option<&ApiSecret>, text: &str) -> Result<String, ShakespeareClientException> {
     let body = surf::Body::from_json(&ShakespeareTranslationRequest {
         text: text.to_owned()
     }).map_err(|_| {
         ShakespeareClientException::ShakespeareClientWentWrong
     })?;
 
-    let mut res = post(url)
-        .body(body)
+    let mut request = post(url).body(body);
+    if let Some(api_secret) = api_secret {
+        request = request.header(API_SECRET_HEADER, api_secret.expose());
+    }
+
+    let mut res = request
         .await.map_err(|_| {
         ShakespeareClientException::ShakespeareClientWentWrong
     })?;
@@ -27,6 +63,14 @@ pub async fn get_shakespearean_translation(url: &str, text: &str) -> Result<Stri
         StatusCode::NotFound => {
             Err(ShakespeareClientException::TranslationNotFound)
         }
+        StatusCode::Unauthorized | StatusCode::Forbidden => {
+            error!(
+                "Funtranslations rejected the request with {} ({} API secret sent); check the funtranslations settings",
+                res.status(),
+                if api_secret.is_some() { "an" } else { "no" }
+            );
+            Err(ShakespeareClientException::TranslatorMisconfigured)
+        }
         _ => Err(ShakespeareClientException::ShakespeareClientWentWrong)
     }
 }
@@ -35,7 +79,7 @@ pub async fn get_shakespearean_translation(url: &str, text: &str) -> Result<Stri
 #[allow(unused_imports)]
 mod tests {
     use wiremock::{Mock, MockServer, ResponseTemplate};
-    use wiremock::matchers::{method, path, path_regex};
+    use wiremock::matchers::{header, method, path, path_regex};
 
     use super::*;
 
@@ -51,7 +95,7 @@ mod tests {
 
         let pokemon = "ozer";
 
-        get_shakespearean_translation(&mock_server.uri(), pokemon).await.map_err(|error| {
+        get_shakespearean_translation(&mock_server.uri(), None, pokemon).await.map_err(|error| {
             assert_eq!(error, ShakespeareClientException::TranslationNotFound)
         });
     }
@@ -68,7 +112,7 @@ mod tests {
 
         let pokemon = "ozer";
 
-        get_shakespearean_translation(&mock_server.uri(), pokemon).await.map_err(|error| {
+        get_shakespearean_translation(&mock_server.uri(), None, pokemon).await.map_err(|error| {
             assert_eq!(error, ShakespeareClientException::ShakespeareClientWentWrong)
         });
     }
@@ -86,7 +130,39 @@ mod tests {
 
         let pokemon = "ozer";
 
-        let translated = get_shakespearean_translation(&mock_server.uri(), pokemon).await.unwrap();
+        let translated = get_shakespearean_translation(&mock_server.uri(), None, pokemon).await.unwrap();
+        assert_eq!(translated, "translated");
+    }
+
+    #[actix_rt::test]
+    async fn should_send_the_api_secret_header() {
+        let mock_server = MockServer::start().await;
+
+        let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("translation"));
+
+        Mock::given(method("POST"))
+            .and(header(API_SECRET_HEADER, "hunter2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(translation))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let translated = get_shakespearean_translation(&mock_server.uri(), Some(&ApiSecret::new("hunter2")), "ozer").await.unwrap();
         assert_eq!(translated, "translated");
     }
+
+    #[actix_rt::test]
+    async fn should_report_misconfiguration_if_request_is_unauthorized_or_forbidden() {
+        for status in &[StatusCode::Unauthorized, StatusCode::Forbidden] {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(*status))
+                .mount(&mock_server)
+                .await;
+
+            let error = get_shakespearean_translation(&mock_server.uri(), Some(&ApiSecret::new("wrong")), "ozer").await.unwrap_err();
+            assert_eq!(error, ShakespeareClientException::TranslatorMisconfigured);
+        }
+    }
 }
@@ -1,10 +1,14 @@
 use derive_more::{Display, Error};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Display, Error, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Display, Error, PartialEq, Serialize, Deserialize)]
 pub enum ShakespeareClientException {
     #[display(fmt = "Translation not found")]
     TranslationNotFound,
     #[display(fmt = "Unable to process the request")]
     ShakespeareClientWentWrong,
+    #[display(fmt = "The translation service rejected our credentials")]
+    TranslatorMisconfigured,
+    #[display(fmt = "Translation quota exhausted until {}", reset_at)]
+    QuotaExhausted { reset_at: u64 },
 }
\ No newline at end of file
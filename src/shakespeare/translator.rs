@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+
+use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+
+/// A translation and whether it is worth keeping. Stand-in translations from a
+/// fallback are served, but never cached or stored, so the remote translation
+/// replaces them once it is back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Translation {
+    pub text: String,
+    pub stand_in: bool,
+}
+
+#[async_trait]
+pub trait Translator: Send + Sync {
+    async fn get_shakespearean_translation(&self, text: &str) -> Result<String, ShakespeareClientException>;
+
+    async fn get_translation(&self, text: &str) -> Result<Translation, ShakespeareClientException> {
+        self.get_shakespearean_translation(text).await.map(|text| Translation {
+            text,
+            stand_in: false,
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+
+/// Deduplicates concurrent work by key: while a call for a key is in flight,
+/// later callers for the same key wait for it instead of starting their own.
+pub struct SingleFlight<T: Clone> {
+    in_flight: Mutex<InFlight<T>>,
+}
+
+struct InFlight<T: Clone> {
+    next_id: u64,
+    calls: HashMap<String, (u64, Shared<BoxFuture<'static, T>>)>,
+}
+
+impl<T: Clone> Default for SingleFlight<T> {
+    fn default() -> Self {
+        SingleFlight {
+            in_flight: Mutex::new(InFlight {
+                next_id: 0,
+                calls: HashMap::new(),
+            })
+        }
+    }
+}
+
+impl<T: Clone> SingleFlight<T> {
+    pub fn new() -> Self {
+        SingleFlight::default()
+    }
+
+    pub async fn run<F>(&self, key: &str, call: F) -> T
+        where F: FnOnce() -> BoxFuture<'static, T> {
+        let (id, future) = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+
+            match in_flight.calls.get(key) {
+                Some((id, future)) => (*id, future.clone()),
+                None => {
+                    let id = in_flight.next_id;
+                    in_flight.next_id += 1;
+
+                    let future = call().shared();
+                    in_flight.calls.insert(key.to_owned(), (id, future.clone()));
+                    (id, future)
+                }
+            }
+        };
+
+        let result = future.await;
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if matches!(in_flight.calls.get(key), Some((current_id, _)) if *current_id == id) {
+            in_flight.calls.remove(key);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::future::join_all;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn should_share_one_call_between_concurrent_callers_of_a_key() {
+        let single_flight = SingleFlight::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let results = join_all((0..5).map(|_| {
+            let calls = calls.clone();
+            single_flight.run("pikachu", move || async move {
+                actix_rt::time::sleep(std::time::Duration::from_millis(20)).await;
+                calls.fetch_add(1, Ordering::SeqCst)
+            }.boxed())
+        })).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(results.iter().all(|result| *result == 0));
+    }
+
+    #[actix_rt::test]
+    async fn should_start_a_new_call_once_the_previous_one_finished() {
+        let single_flight = SingleFlight::new();
+
+        let first = single_flight.run("pikachu", || async { 1 }.boxed()).await;
+        let second = single_flight.run("pikachu", || async { 2 }.boxed()).await;
+
+        assert_eq!(first, 1);
+        assert_eq!(second, 2);
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::negotiation::representation::escape_markup;
+use crate::poke::poke_species_response::PokeSpeciesResponse;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SpeciesMetadata {
+    pub genus: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub translated_genus: Option<String>,
+    pub generation: Option<String>,
+    pub is_legendary: bool,
+    pub is_mythical: bool,
+    pub color: Option<String>,
+    pub habitat: Option<String>,
+    pub evolution_chain_url: Option<String>,
+    pub names: BTreeMap<String, String>,
+}
+
+impl SpeciesMetadata {
+    pub fn from_poke_species_response(response: &PokeSpeciesResponse) -> Self {
+        let genus = response.genera.iter()
+            .find(|genus| genus.language.name == "en")
+            .map(|genus| genus.genus.to_owned());
+
+        let names = response.names.iter()
+            .map(|name| (name.language.name.to_owned(), name.name.to_owned()))
+            .collect();
+
+        SpeciesMetadata {
+            genus,
+            translated_genus: None,
+            generation: response.generation.as_ref().map(|generation| generation.name.to_owned()),
+            is_legendary: response.is_legendary,
+            is_mythical: response.is_mythical,
+            color: response.color.as_ref().map(|color| color.name.to_owned()),
+            habitat: response.habitat.as_ref().map(|habitat| habitat.name.to_owned()),
+            evolution_chain_url: response.evolution_chain.as_ref().map(|evolution_chain| evolution_chain.url.to_owned()),
+            names,
+        }
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<dl class=\"shakespearemon-metadata\">");
+
+        for (term, value) in self.fields() {
+            html.push_str(&format!("<dt>{}</dt><dd>{}</dd>", term, escape_markup(&value)));
+        }
+
+        html.push_str("</dl>");
+        html
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<metadata>");
+
+        for (element, value) in self.fields() {
+            xml.push_str(&format!("<{0}>{1}</{0}>", element, escape_markup(&value)));
+        }
+
+        xml.push_str("<names>");
+        for (language, name) in &self.names {
+            xml.push_str(&format!("<name language=\"{}\">{}</name>", escape_markup(language), escape_markup(name)));
+        }
+        xml.push_str("</names></metadata>");
+        xml
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        let optional_fields = vec![
+            ("genus", &self.genus),
+            ("translated_genus", &self.translated_genus),
+            ("generation", &self.generation),
+            ("color", &self.color),
+            ("habitat", &self.habitat),
+            ("evolution_chain_url", &self.evolution_chain_url),
+        ];
+
+        let mut fields: Vec<(&'static str, String)> = optional_fields.into_iter()
+            .filter_map(|(name, value)| value.as_ref().map(|value| (name, value.to_owned())))
+            .collect();
+
+        fields.push(("is_legendary", self.is_legendary.to_string()));
+        fields.push(("is_mythical", self.is_mythical.to_string()));
+        fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::poke::poke_species_response::{Genus, Name, NamedApiResource, TextFlavorEntryLanguage};
+
+    use super::*;
+
+    fn language(name: &str) -> TextFlavorEntryLanguage {
+        TextFlavorEntryLanguage {
+            name: name.to_owned()
+        }
+    }
+
+    #[test]
+    fn should_pick_english_genus_and_map_names_by_language() {
+        let mut response = PokeSpeciesResponse::new(25, "pikachu".to_owned(), Vec::new());
+        response.genera = vec![
+            Genus { genus: "Mausポケモン".to_owned(), language: language("ja") },
+            Genus { genus: "Mouse Pokémon".to_owned(), language: language("en") },
+        ];
+        response.names = vec![
+            Name { name: "Pikachu".to_owned(), language: language("en") },
+            Name { name: "ピカチュウ".to_owned(), language: language("ja") },
+        ];
+        response.color = Some(NamedApiResource { name: "yellow".to_owned(), url: String::new() });
+
+        let metadata = SpeciesMetadata::from_poke_species_response(&response);
+
+        assert_eq!(metadata.genus, Some("Mouse Pokémon".to_owned()));
+        assert_eq!(metadata.color, Some("yellow".to_owned()));
+        assert_eq!(metadata.habitat, None);
+        assert_eq!(metadata.names.get("ja"), Some(&"ピカチュウ".to_owned()));
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use actix_web::web;
+use rusqlite::{Connection, OptionalExtension, params, Row};
+
+use crate::auth::api_key::{api_key_id, ApiKey, generate_api_key, hash_api_key};
+use crate::store::migrations::run_migrations;
+use crate::store::translation_store::BUSY_TIMEOUT;
+use crate::store::translation_store_exception::TranslationStoreException;
+
+/// Keeps API keys created through the admin endpoints, in the same SQLite
+/// database as the translation store.
+pub struct ApiKeyStore {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl ApiKeyStore {
+    pub fn open(path: &str) -> Result<Self, TranslationStoreException> {
+        let mut connection = Connection::open(path).map_err(|_| {
+            TranslationStoreException::TranslationStoreUnavailable
+        })?;
+
+        connection.busy_timeout(BUSY_TIMEOUT)
+            .and_then(|_| run_migrations(&mut connection))
+            .map_err(|_| {
+                TranslationStoreException::TranslationStoreUnavailable
+            })?;
+
+        Ok(ApiKeyStore {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Stores a new key and returns it; the secret is not retrievable afterwards.
+    pub async fn create_api_key(&self, name: &str, hourly_limit: u32, daily_limit: u32) -> Result<(String, ApiKey), TranslationStoreException> {
+        let connection = self.connection.clone();
+        let key = generate_api_key();
+        let key_hash = hash_api_key(&key);
+        let api_key = ApiKey {
+            id: api_key_id(&key_hash),
+            name: name.to_owned(),
+            hourly_limit,
+            daily_limit,
+            created_at: Some(now()),
+            revoked_at: None,
+        };
+        let record = api_key.clone();
+
+        web::block(move || {
+            connection.lock().unwrap().execute(
+                "INSERT INTO api_keys (id, key_hash, name, hourly_limit, daily_limit, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![record.id, key_hash, record.name, record.hourly_limit, record.daily_limit, record.created_at],
+            )
+        }).await.map_err(|_| {
+            TranslationStoreException::TranslationStoreWentWrong
+        })?;
+
+        Ok((key, api_key))
+    }
+
+    pub async fn find_api_key(&self, key_hash: &str) -> Result<Option<ApiKey>, TranslationStoreException> {
+        let connection = self.connection.clone();
+        let key_hash = key_hash.to_owned();
+
+        web::block(move || {
+            connection.lock().unwrap()
+                .query_row(
+                    "SELECT id, name, hourly_limit, daily_limit, created_at, revoked_at FROM api_keys WHERE key_hash = ?1",
+                    params![key_hash],
+                    read_api_key,
+                )
+                .optional()
+        }).await.map_err(|_| {
+            TranslationStoreException::TranslationStoreWentWrong
+        })
+    }
+
+    pub async fn list_api_keys(&self) -> Result<Vec<ApiKey>, TranslationStoreException> {
+        let connection = self.connection.clone();
+
+        web::block(move || {
+            let connection = connection.lock().unwrap();
+            let mut statement = connection.prepare(
+                "SELECT id, name, hourly_limit, daily_limit, created_at, revoked_at FROM api_keys ORDER BY created_at, id"
+            )?;
+
+            let api_keys = statement.query_map(rusqlite::NO_PARAMS, read_api_key)?;
+            api_keys.collect::<rusqlite::Result<Vec<ApiKey>>>()
+        }).await.map_err(|_| {
+            TranslationStoreException::TranslationStoreWentWrong
+        })
+    }
+
+    /// Marks the key as revoked, returning whether an active key was found.
+    pub async fn revoke_api_key(&self, id: &str) -> Result<bool, TranslationStoreException> {
+        let connection = self.connection.clone();
+        let id = id.to_owned();
+
+        web::block(move || {
+            connection.lock().unwrap().execute(
+                "UPDATE api_keys SET revoked_at = ?2 WHERE id = ?1 AND revoked_at IS NULL",
+                params![id, now()],
+            )
+        }).await
+            .map(|updated| updated > 0)
+            .map_err(|_| {
+                TranslationStoreException::TranslationStoreWentWrong
+            })
+    }
+}
+
+fn read_api_key(row: &Row) -> rusqlite::Result<ApiKey> {
+    Ok(ApiKey {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        hourly_limit: row.get(2)?,
+        daily_limit: row.get(3)?,
+        created_at: row.get(4)?,
+        revoked_at: row.get(5)?,
+    })
+}
+
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn should_find_a_created_key_by_its_hash() {
+        let store = ApiKeyStore::open(":memory:").unwrap();
+
+        let (key, api_key) = store.create_api_key("homepage", 100, 1000).await.unwrap();
+
+        let found = store.find_api_key(&hash_api_key(&key)).await.unwrap();
+        assert_eq!(found, Some(api_key));
+        assert_eq!(store.find_api_key(&hash_api_key("unknown")).await.unwrap(), None);
+    }
+
+    #[actix_rt::test]
+    async fn should_revoke_a_key_once() {
+        let store = ApiKeyStore::open(":memory:").unwrap();
+        let (key, api_key) = store.create_api_key("homepage", 100, 1000).await.unwrap();
+
+        assert!(store.revoke_api_key(&api_key.id).await.unwrap());
+        assert!(!store.revoke_api_key(&api_key.id).await.unwrap());
+
+        let found = store.find_api_key(&hash_api_key(&key)).await.unwrap().unwrap();
+        assert!(found.is_revoked());
+        assert_eq!(store.list_api_keys().await.unwrap(), vec![found]);
+    }
+}
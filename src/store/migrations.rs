@@ -0,0 +1,58 @@
+use rusqlite::Connection;
+
+/// Schema changes, applied in order. The index of a migration plus one is the
+/// `user_version` the database ends up at once it has been applied.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE translations (
+        source_hash TEXT NOT NULL,
+        style TEXT NOT NULL,
+        translated TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        PRIMARY KEY (source_hash, style)
+    )",
+    "CREATE TABLE api_keys (
+        id TEXT NOT NULL PRIMARY KEY,
+        key_hash TEXT NOT NULL UNIQUE,
+        name TEXT NOT NULL,
+        hourly_limit INTEGER NOT NULL,
+        daily_limit INTEGER NOT NULL,
+        created_at INTEGER NOT NULL,
+        revoked_at INTEGER
+    )",
+];
+
+pub fn run_migrations(connection: &mut Connection) -> rusqlite::Result<()> {
+    let current_version: usize = connection.query_row("PRAGMA user_version", rusqlite::NO_PARAMS, |row| row.get::<_, i64>(0))? as usize;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current_version) {
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(migration)?;
+        transaction.execute_batch(&format!("PRAGMA user_version = {}", index + 1))?;
+        transaction.commit()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_bring_a_new_database_to_the_latest_version() {
+        let mut connection = Connection::open_in_memory().unwrap();
+
+        run_migrations(&mut connection).unwrap();
+
+        let version: i64 = connection.query_row("PRAGMA user_version", rusqlite::NO_PARAMS, |row| row.get(0)).unwrap();
+        assert_eq!(version as usize, MIGRATIONS.len());
+    }
+
+    #[test]
+    fn should_not_fail_when_migrations_run_twice() {
+        let mut connection = Connection::open_in_memory().unwrap();
+
+        run_migrations(&mut connection).unwrap();
+        run_migrations(&mut connection).unwrap();
+    }
+}
@@ -0,0 +1,5 @@
+pub mod api_key_store;
+pub mod migrations;
+pub mod translation_record;
+pub mod translation_store;
+pub mod translation_store_exception;
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslationRecord {
+    pub source_hash: String,
+    pub style: String,
+    pub translated: String,
+    pub created_at: i64,
+}
@@ -0,0 +1,234 @@
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Sender, sync_channel, SyncSender};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use actix_web::web;
+use log::error;
+use rusqlite::{Connection, OptionalExtension, params};
+use sha2::{Digest, Sha256};
+
+use crate::store::migrations::run_migrations;
+use crate::store::translation_record::TranslationRecord;
+use crate::store::translation_store_exception::TranslationStoreException;
+
+/// How long a connection waits for another one sharing the database file, such
+/// as the [`ApiKeyStore`](crate::store::api_key_store::ApiKeyStore)'s, to release its lock.
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+enum WriteCommand {
+    Save(TranslationRecord),
+    Flush(SyncSender<()>),
+}
+
+/// Keeps translations in SQLite so they outlive the process. Reads go through the
+/// blocking thread pool, writes are queued to a dedicated writer thread.
+pub struct TranslationStore {
+    connection: Arc<Mutex<Connection>>,
+    writer: Sender<WriteCommand>,
+}
+
+impl TranslationStore {
+    pub fn open(path: &str) -> Result<Self, TranslationStoreException> {
+        let mut connection = Connection::open(path).map_err(|_| {
+            TranslationStoreException::TranslationStoreUnavailable
+        })?;
+
+        connection.busy_timeout(BUSY_TIMEOUT)
+            .and_then(|_| run_migrations(&mut connection))
+            .map_err(|_| {
+                TranslationStoreException::TranslationStoreUnavailable
+            })?;
+
+        let connection = Arc::new(Mutex::new(connection));
+        let writer = spawn_writer(connection.clone());
+
+        Ok(TranslationStore {
+            connection,
+            writer,
+        })
+    }
+
+    pub async fn get_translation(&self, text: &str, style: &str) -> Result<Option<String>, TranslationStoreException> {
+        let connection = self.connection.clone();
+        let source_hash = hash_source_text(text);
+        let style = style.to_owned();
+
+        web::block(move || {
+            connection.lock().unwrap()
+                .query_row(
+                    "SELECT translated FROM translations WHERE source_hash = ?1 AND style = ?2",
+                    params![source_hash, style],
+                    |row| row.get(0),
+                )
+                .optional()
+        }).await.map_err(|_| {
+            TranslationStoreException::TranslationStoreWentWrong
+        })
+    }
+
+    /// Queues the translation to be written; callers do not wait for the write.
+    pub fn save_translation(&self, text: &str, style: &str, translated: &str) {
+        let record = TranslationRecord {
+            source_hash: hash_source_text(text),
+            style: style.to_owned(),
+            translated: translated.to_owned(),
+            created_at: now(),
+        };
+
+        if self.writer.send(WriteCommand::Save(record)).is_err() {
+            error!("Translation store writer has stopped, dropping translation");
+        }
+    }
+
+    /// Waits until every translation queued before the call has been written.
+    pub async fn flush(&self) -> Result<(), TranslationStoreException> {
+        let (done, wait) = sync_channel(1);
+
+        self.writer.send(WriteCommand::Flush(done)).map_err(|_| {
+            TranslationStoreException::TranslationStoreWentWrong
+        })?;
+
+        web::block(move || wait.recv()).await.map_err(|_| {
+            TranslationStoreException::TranslationStoreWentWrong
+        })
+    }
+
+    pub async fn list_translations(&self) -> Result<Vec<TranslationRecord>, TranslationStoreException> {
+        let connection = self.connection.clone();
+
+        web::block(move || {
+            let connection = connection.lock().unwrap();
+            let mut statement = connection.prepare(
+                "SELECT source_hash, style, translated, created_at FROM translations ORDER BY created_at, source_hash"
+            )?;
+
+            let records = statement.query_map(rusqlite::NO_PARAMS, |row| {
+                Ok(TranslationRecord {
+                    source_hash: row.get(0)?,
+                    style: row.get(1)?,
+                    translated: row.get(2)?,
+                    created_at: row.get(3)?,
+                })
+            })?;
+
+            records.collect::<rusqlite::Result<Vec<TranslationRecord>>>()
+        }).await.map_err(|_| {
+            TranslationStoreException::TranslationStoreWentWrong
+        })
+    }
+
+    /// Removes every stored translation and returns how many were removed.
+    pub async fn purge_translations(&self) -> Result<usize, TranslationStoreException> {
+        let connection = self.connection.clone();
+
+        web::block(move || {
+            connection.lock().unwrap().execute("DELETE FROM translations", rusqlite::NO_PARAMS)
+        }).await.map_err(|_| {
+            TranslationStoreException::TranslationStoreWentWrong
+        })
+    }
+}
+
+pub fn hash_source_text(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+fn spawn_writer(connection: Arc<Mutex<Connection>>) -> Sender<WriteCommand> {
+    let (writer, commands) = channel();
+
+    thread::spawn(move || {
+        for command in commands {
+            match command {
+                WriteCommand::Save(record) => {
+                    let result = connection.lock().unwrap().execute(
+                        "INSERT OR REPLACE INTO translations (source_hash, style, translated, created_at) VALUES (?1, ?2, ?3, ?4)",
+                        params![record.source_hash, record.style, record.translated, record.created_at],
+                    );
+
+                    if let Err(error) = result {
+                        error!("Unable to write translation to the store: {:?}", error);
+                    }
+                }
+                WriteCommand::Flush(done) => {
+                    let _ = done.send(());
+                }
+            }
+        }
+    });
+
+    writer
+}
+
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn should_return_none_for_unknown_text() {
+        let store = TranslationStore::open(":memory:").unwrap();
+
+        let result = store.get_translation("unknown", "shakespeare").await.unwrap();
+
+        assert_eq!(result, None);
+    }
+
+    #[actix_rt::test]
+    async fn should_wait_for_another_connection_to_release_its_lock() {
+        let path = std::env::temp_dir().join(format!("shakespearemon-test-busy-{}.sqlite", std::process::id()));
+        let path = path.to_str().unwrap();
+        let store = TranslationStore::open(path).unwrap();
+
+        let other = Connection::open(path).unwrap();
+        other.execute_batch("BEGIN IMMEDIATE").unwrap();
+        let releaser = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(200));
+            other.execute_batch("COMMIT").unwrap();
+        });
+
+        store.save_translation("text", "shakespeare", "translated");
+        store.flush().await.unwrap();
+        releaser.join().unwrap();
+
+        let result = store.get_translation("text", "shakespeare").await.unwrap();
+        let _ = std::fs::remove_file(path);
+        assert_eq!(result, Some("translated".to_owned()));
+    }
+
+    #[actix_rt::test]
+    async fn should_return_saved_translation_after_flush() {
+        let store = TranslationStore::open(":memory:").unwrap();
+
+        store.save_translation("text", "shakespeare", "translated");
+        store.flush().await.unwrap();
+
+        let result = store.get_translation("text", "shakespeare").await.unwrap();
+        assert_eq!(result, Some("translated".to_owned()));
+
+        let other_style = store.get_translation("text", "yoda").await.unwrap();
+        assert_eq!(other_style, None);
+    }
+
+    #[actix_rt::test]
+    async fn should_list_and_purge_translations() {
+        let store = TranslationStore::open(":memory:").unwrap();
+
+        store.save_translation("first", "shakespeare", "first translated");
+        store.save_translation("second", "shakespeare", "second translated");
+        store.flush().await.unwrap();
+
+        let records = store.list_translations().await.unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().any(|record| record.source_hash == hash_source_text("first")));
+
+        let purged = store.purge_translations().await.unwrap();
+        assert_eq!(purged, 2);
+        assert!(store.list_translations().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,10 @@
+use derive_more::{Display, Error};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Display, Error, PartialEq, Serialize, Deserialize)]
+pub enum TranslationStoreException {
+    #[display(fmt = "Unable to open the translation store")]
+    TranslationStoreUnavailable,
+    #[display(fmt = "Unable to process the request")]
+    TranslationStoreWentWrong,
+}
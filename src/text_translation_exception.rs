@@ -0,0 +1,14 @@
+use derive_more::{Display, Error};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Display, Error, PartialEq, Serialize, Deserialize)]
+pub enum TextTranslationException {
+    #[display(fmt = "Text must not be empty")]
+    EmptyText,
+    #[display(fmt = "Text must not be longer than {} characters", max_length)]
+    TextTooLong { max_length: usize },
+    #[display(fmt = "Style is not supported")]
+    UnsupportedStyle,
+    #[display(fmt = "Request body is not valid")]
+    InvalidRequest,
+}
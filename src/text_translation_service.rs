@@ -0,0 +1,130 @@
+use actix_web::{HttpRequest, HttpResponse, post, web};
+use actix_web::error::JsonPayloadError;
+use actix_web::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::negotiation::negotiated_exception::NegotiatedException;
+use crate::negotiation::representation::{escape_markup, Representation};
+use crate::negotiation::response_format::ResponseFormat;
+use crate::settings::Settings;
+use crate::text_translation_exception::TextTranslationException;
+use crate::translation_pipeline::{SHAKESPEARE_STYLE, TranslationPipeline};
+use crate::translation_service::ShakespearemonException;
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TextTranslationRequest {
+    pub text: String,
+    pub style: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TextTranslationResponse {
+    pub text: String,
+    pub style: String,
+    pub translated: String,
+}
+
+impl Representation for TextTranslationResponse {
+    fn to_plain_text(&self) -> String {
+        self.translated.to_owned()
+    }
+
+    fn to_html(&self) -> String {
+        format!(
+            "<blockquote class=\"shakespearemon-translation\"><p>{}</p></blockquote>",
+            escape_markup(&self.translated),
+        )
+    }
+
+    fn to_xml(&self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><translation><text>{}</text><style>{}</style><translated>{}</translated></translation>",
+            escape_markup(&self.text),
+            escape_markup(&self.style),
+            escape_markup(&self.translated),
+        )
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/translate",
+    tag = "translate",
+    request_body = TextTranslationRequest,
+    responses(
+        (status = 200, description = "The text in Shakespearean English", body = TextTranslationResponse),
+        (status = 400, description = "The text is empty, the style unsupported or the body malformed", body = ErrorResponse),
+        (status = 406, description = "None of the accepted media types can be produced", body = ErrorResponse),
+        (status = 413, description = "The text is longer than allowed", body = ErrorResponse),
+        (status = 429, description = "The translation quota is spent", body = ErrorResponse),
+        (status = 500, description = "An upstream service failed", body = ErrorResponse),
+        (status = 502, description = "The translator rejected our credentials", body = ErrorResponse),
+    )
+)]
+#[post("")]
+pub async fn translate_text(http_request: HttpRequest, data: web::Data<Settings>, translation_pipeline: TranslationPipeline, request: web::Json<TextTranslationRequest>) -> Result<HttpResponse, NegotiatedException> {
+    let format = ResponseFormat::from_request(&http_request).ok_or_else(NegotiatedException::not_acceptable)?;
+
+    let TextTranslationRequest { text, style } = request.into_inner();
+    let style = style.unwrap_or_else(|| SHAKESPEARE_STYLE.to_owned());
+
+    validate_text_translation_request(&text, &style, data.application.max_text_length)
+        .map_err(|error| {
+            NegotiatedException::new(ShakespearemonException::TextTranslationException(error), format)
+        })?;
+
+    let translated = translation_pipeline.translate(&text).await
+        .map_err(|error| {
+            NegotiatedException::new(error, format)
+        })?;
+
+    Ok(format.respond(StatusCode::OK, &TextTranslationResponse {
+        text,
+        style,
+        translated,
+    }))
+}
+
+/// Registers `/translate` with its own body limit, so other JSON routes keep
+/// the default limit and errors.
+pub fn configure_text_translation(max_text_length: usize) -> impl Fn(&mut web::ServiceConfig) {
+    move |config| {
+        config.service(web::scope("/translate")
+            .app_data(text_translation_json_config(max_text_length))
+            .service(translate_text));
+    }
+}
+
+/// Makes malformed and oversized bodies fail with the same errors as invalid text.
+pub fn text_translation_json_config(max_text_length: usize) -> web::JsonConfig {
+    // A character takes at most four bytes in UTF-8, plus room for the rest of the body.
+    let limit = max_text_length * 4 + 1024;
+
+    web::JsonConfig::default()
+        .limit(limit)
+        .error_handler(move |error, request| {
+            let error = match error {
+                JsonPayloadError::Overflow => TextTranslationException::TextTooLong { max_length: max_text_length },
+                _ => TextTranslationException::InvalidRequest,
+            };
+            let format = ResponseFormat::from_request(request).unwrap_or(ResponseFormat::Json);
+            NegotiatedException::new(ShakespearemonException::TextTranslationException(error), format).into()
+        })
+}
+
+pub fn validate_text_translation_request(text: &str, style: &str, max_length: usize) -> Result<(), TextTranslationException> {
+    if style != SHAKESPEARE_STYLE {
+        return Err(TextTranslationException::UnsupportedStyle);
+    }
+
+    if text.trim().is_empty() {
+        return Err(TextTranslationException::EmptyText);
+    }
+
+    if text.chars().count() > max_length {
+        return Err(TextTranslationException::TextTooLong { max_length });
+    }
+
+    Ok(())
+}
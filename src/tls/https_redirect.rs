@@ -0,0 +1,47 @@
+use actix_web::{HttpRequest, HttpResponse, web};
+use actix_web::http::header;
+
+/// The port the HTTPS listener is on, for building redirect locations.
+pub struct HttpsPort(pub u16);
+
+/// Sends every plain HTTP request to the same path on the HTTPS listener.
+/// Uses 308 so clients keep the method and body of non-GET requests.
+pub async fn redirect_to_https(request: HttpRequest, https_port: web::Data<HttpsPort>) -> HttpResponse {
+    let connection_info = request.connection_info();
+    let location = https_location(connection_info.host(), https_port.0, &request.uri().to_string());
+
+    HttpResponse::PermanentRedirect()
+        .header(header::LOCATION, location)
+        .finish()
+}
+
+fn https_location(host: &str, https_port: u16, path_and_query: &str) -> String {
+    let host = strip_port(host);
+
+    if https_port == 443 {
+        format!("https://{}{}", host, path_and_query)
+    } else {
+        format!("https://{}:{}{}", host, https_port, path_and_query)
+    }
+}
+
+fn strip_port(host: &str) -> &str {
+    // IPv6 literals keep their brackets; only a trailing `:port` is dropped.
+    match host.rfind(':') {
+        Some(index) if !host[index..].contains(']') => &host[..index],
+        _ => host
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_the_path_and_swap_the_port() {
+        assert_eq!(https_location("example.com:8080", 8443, "/pokemon/pikachu?include=media"), "https://example.com:8443/pokemon/pikachu?include=media");
+        assert_eq!(https_location("example.com", 443, "/"), "https://example.com/");
+        assert_eq!(https_location("[::1]:8080", 443, "/"), "https://[::1]/");
+        assert_eq!(https_location("[::1]", 8443, "/"), "https://[::1]:8443/");
+    }
+}
@@ -0,0 +1,3 @@
+pub mod https_redirect;
+pub mod strict_transport_security;
+pub mod tls_config;
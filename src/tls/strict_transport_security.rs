@@ -0,0 +1,26 @@
+use actix_web::http::header;
+use actix_web::middleware::DefaultHeaders;
+
+/// Adds `Strict-Transport-Security` to every response of the HTTPS listener.
+pub fn strict_transport_security(max_age_seconds: u64, include_subdomains: bool) -> DefaultHeaders {
+    DefaultHeaders::new().header(header::STRICT_TRANSPORT_SECURITY, hsts_header_value(max_age_seconds, include_subdomains))
+}
+
+pub fn hsts_header_value(max_age_seconds: u64, include_subdomains: bool) -> String {
+    if include_subdomains {
+        format!("max-age={}; includeSubDomains", max_age_seconds)
+    } else {
+        format!("max-age={}", max_age_seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_include_subdomains_when_asked() {
+        assert_eq!(hsts_header_value(31536000, false), "max-age=31536000");
+        assert_eq!(hsts_header_value(300, true), "max-age=300; includeSubDomains");
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs::File;
+use std::io::BufReader;
+
+use rustls::{NoClientAuth, ServerConfig};
+use rustls::internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys};
+
+/// Builds the rustls configuration from a PEM certificate chain and a PEM
+/// private key, either PKCS#8 or PKCS#1 (RSA).
+pub fn load_server_config(cert_path: &str, key_path: &str) -> Result<ServerConfig, String> {
+    let cert_chain = certs(&mut open(cert_path)?).map_err(|_| {
+        format!("Unable to read certificates from {}", cert_path)
+    })?;
+    if cert_chain.is_empty() {
+        return Err(format!("No certificates found in {}", cert_path));
+    }
+
+    let mut keys = pkcs8_private_keys(&mut open(key_path)?).map_err(|_| {
+        format!("Unable to read a private key from {}", key_path)
+    })?;
+    if keys.is_empty() {
+        keys = rsa_private_keys(&mut open(key_path)?).map_err(|_| {
+            format!("Unable to read a private key from {}", key_path)
+        })?;
+    }
+    let key = keys.into_iter().next().ok_or(format!("No private key found in {}", key_path))?;
+
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config.set_single_cert(cert_chain, key).map_err(|error| {
+        format!("Invalid certificate or key: {}", error)
+    })?;
+
+    Ok(config)
+}
+
+fn open(path: &str) -> Result<BufReader<File>, String> {
+    File::open(path).map(BufReader::new).map_err(|error| {
+        format!("Unable to open {}: {}", path, error)
+    })
+}
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use actix_web::{Error, FromRequest, HttpRequest, web};
+use actix_web::dev::Payload;
+use actix_web::error::ErrorInternalServerError;
+use futures::future::{err, ok, Ready};
+use log::error;
+
+use crate::cache::translation_cache::TranslationCache;
+use crate::shakespeare::translator::{Translation, Translator};
+use crate::store::translation_store::TranslationStore;
+use crate::translation_service::ShakespearemonException;
+
+pub const SHAKESPEARE_STYLE: &str = "shakespeare";
+
+/// Translates text through the cache, then the store, then the translator, filling
+/// whichever layers missed on the way back; stand-in translations fill neither.
+/// Handlers extract it from app data.
+#[derive(Clone)]
+pub struct TranslationPipeline {
+    translator: Arc<dyn Translator>,
+    translation_cache: Option<Arc<dyn TranslationCache>>,
+    translation_store: Option<Arc<TranslationStore>>,
+    served_stand_in: Arc<AtomicBool>,
+}
+
+impl TranslationPipeline {
+    pub fn new(translator: Arc<dyn Translator>) -> Self {
+        TranslationPipeline {
+            translator,
+            translation_cache: None,
+            translation_store: None,
+            served_stand_in: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn with_translation_cache(mut self, translation_cache: Arc<dyn TranslationCache>) -> Self {
+        self.translation_cache = Some(translation_cache);
+        self
+    }
+
+    pub fn with_translation_store(mut self, translation_store: Arc<TranslationStore>) -> Self {
+        self.translation_store = Some(translation_store);
+        self
+    }
+
+    /// Whether any translation served so far was a stand-in. Handlers extract a
+    /// pipeline per request, so for them this covers exactly their response.
+    pub fn served_stand_in(&self) -> bool {
+        self.served_stand_in.load(Ordering::Relaxed)
+    }
+
+    pub async fn translate(&self, text: &str) -> Result<String, ShakespearemonException> {
+        if let Some(cache) = &self.translation_cache {
+            if let Some(translation) = cache.get_translation(text, SHAKESPEARE_STYLE).await {
+                return Ok(translation);
+            }
+        }
+
+        let translation = self.get_stored_or_fresh_translation(text).await?;
+
+        if translation.stand_in {
+            self.served_stand_in.store(true, Ordering::Relaxed);
+        }
+
+        if let Some(cache) = self.translation_cache.as_ref().filter(|_| !translation.stand_in) {
+            cache.set_translation(text, SHAKESPEARE_STYLE, &translation.text).await;
+        }
+
+        Ok(translation.text)
+    }
+
+    async fn get_stored_or_fresh_translation(&self, text: &str) -> Result<Translation, ShakespearemonException> {
+        if let Some(store) = &self.translation_store {
+            match store.get_translation(text, SHAKESPEARE_STYLE).await {
+                Ok(Some(translation)) => return Ok(Translation {
+                    text: translation,
+                    stand_in: false,
+                }),
+                Ok(None) => {}
+                Err(error) => error!("Unable to read from the translation store: {:?}", error),
+            }
+        }
+
+        let translation = self.translator.get_translation(text).await
+            .map_err(|error| {
+                ShakespearemonException::ShakespeareClientException(error)
+            })?;
+
+        if let Some(store) = self.translation_store.as_ref().filter(|_| !translation.stand_in) {
+            store.save_translation(text, SHAKESPEARE_STYLE, &translation.text);
+        }
+
+        Ok(translation)
+    }
+}
+
+impl FromRequest for TranslationPipeline {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+    type Config = ();
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let translator = match request.app_data::<web::Data<dyn Translator>>() {
+            Some(translator) => Arc::clone(translator),
+            None => return err(ErrorInternalServerError("Translator is not configured")),
+        };
+
+        let mut pipeline = TranslationPipeline::new(translator);
+
+        if let Some(cache) = request.app_data::<web::Data<dyn TranslationCache>>() {
+            pipeline = pipeline.with_translation_cache(Arc::clone(cache));
+        }
+
+        if let Some(store) = request.app_data::<web::Data<TranslationStore>>() {
+            pipeline = pipeline.with_translation_store(Arc::clone(store));
+        }
+
+        ok(pipeline)
+    }
+}
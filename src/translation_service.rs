@@ -1,21 +1,47 @@
 extern crate derive_more;
 
-use actix_web::{get, HttpResponse, web};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use actix_web::{get, HttpRequest, HttpResponse, web};
 use actix_web::dev::HttpResponseBuilder;
 use actix_web::error::ResponseError;
 use actix_web::http::{header, StatusCode};
 use derive_more::{Display, Error};
+use futures::join;
+use log::warn;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-use crate::{poke, shakespeare};
+use crate::error_response::ErrorResponse;
+use crate::http_caching::{compute_etag, etag_source, respond_if_not_modified, respond_with_caching, respond_without_caching};
+use crate::jobs::batch_job_exception::BatchJobException;
+use crate::negotiation::negotiated_exception::NegotiatedException;
+use crate::negotiation::representation::{escape_markup, Representation};
+use crate::negotiation::response_format::ResponseFormat;
 use crate::poke::poke_client_exception::PokeClientException;
+use crate::poke::poke_species_response::PokeSpeciesResponse;
+use crate::poke::pokemon_source::PokemonSource;
+use crate::poke::species_source::{extract_english_flavor_text_from_poke_species_response, SpeciesSource};
+use crate::pokemon_media::PokemonMedia;
 use crate::settings::Settings;
 use crate::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use crate::species_metadata::SpeciesMetadata;
+use crate::store::translation_store_exception::TranslationStoreException;
+use crate::text_translation_exception::TextTranslationException;
+use crate::translation_pipeline::TranslationPipeline;
 
-#[derive(Debug, Error, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, Error, Serialize, Deserialize, Display)]
 pub enum ShakespearemonException {
     PokeClientException(PokeClientException),
     ShakespeareClientException(ShakespeareClientException),
+    TranslationStoreException(TranslationStoreException),
+    TextTranslationException(TextTranslationException),
+    BatchJobException(BatchJobException),
+    #[display(fmt = "Requested media type is not supported")]
+    NotAcceptable,
+    #[display(fmt = "Date must be formatted as YYYY-MM-DD")]
+    InvalidDate,
 }
 
 impl ResponseError for ShakespearemonException {
@@ -24,40 +50,278 @@ impl ResponseError for ShakespearemonException {
             ShakespearemonException::PokeClientException(PokeClientException::PokeClientWentWrong) => StatusCode::INTERNAL_SERVER_ERROR,
             ShakespearemonException::PokeClientException(PokeClientException::PokemonNotFound) => StatusCode::NOT_FOUND,
             ShakespearemonException::PokeClientException(PokeClientException::PokemonDescriptionNotFound) => StatusCode::NOT_FOUND,
+            ShakespearemonException::PokeClientException(PokeClientException::ResourceNotFound) => StatusCode::NOT_FOUND,
+            ShakespearemonException::PokeClientException(PokeClientException::ResourceDescriptionNotFound) => StatusCode::NOT_FOUND,
             ShakespearemonException::ShakespeareClientException(ShakespeareClientException::TranslationNotFound) => StatusCode::NOT_FOUND,
             ShakespearemonException::ShakespeareClientException(ShakespeareClientException::ShakespeareClientWentWrong) => StatusCode::INTERNAL_SERVER_ERROR,
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::TranslatorMisconfigured) => StatusCode::BAD_GATEWAY,
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::QuotaExhausted { .. }) => StatusCode::TOO_MANY_REQUESTS,
+            ShakespearemonException::TranslationStoreException(TranslationStoreException::TranslationStoreUnavailable) => StatusCode::SERVICE_UNAVAILABLE,
+            ShakespearemonException::TranslationStoreException(TranslationStoreException::TranslationStoreWentWrong) => StatusCode::INTERNAL_SERVER_ERROR,
+            ShakespearemonException::TextTranslationException(TextTranslationException::EmptyText) => StatusCode::BAD_REQUEST,
+            ShakespearemonException::TextTranslationException(TextTranslationException::UnsupportedStyle) => StatusCode::BAD_REQUEST,
+            ShakespearemonException::TextTranslationException(TextTranslationException::InvalidRequest) => StatusCode::BAD_REQUEST,
+            ShakespearemonException::TextTranslationException(TextTranslationException::TextTooLong { .. }) => StatusCode::PAYLOAD_TOO_LARGE,
+            ShakespearemonException::BatchJobException(BatchJobException::BatchJobNotFound) => StatusCode::NOT_FOUND,
+            ShakespearemonException::BatchJobException(BatchJobException::EmptyBatchJob) => StatusCode::BAD_REQUEST,
+            ShakespearemonException::BatchJobException(BatchJobException::TooManyNames { .. }) => StatusCode::PAYLOAD_TOO_LARGE,
+            ShakespearemonException::BatchJobException(BatchJobException::InvalidBatchJobRequest) => StatusCode::BAD_REQUEST,
+            ShakespearemonException::BatchJobException(BatchJobException::TooManyRunningJobs { .. }) => StatusCode::TOO_MANY_REQUESTS,
+            ShakespearemonException::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
+            ShakespearemonException::InvalidDate => StatusCode::BAD_REQUEST,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
-        HttpResponseBuilder::new(self.status_code())
-            .set_header(header::CONTENT_TYPE, "application/json; charset=utf-8")
-            .body(self.to_string())
+        self.error_response_as(ResponseFormat::Json)
     }
 }
 
-#[derive(Serialize, Deserialize)]
+impl ShakespearemonException {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ShakespearemonException::PokeClientException(PokeClientException::PokeClientWentWrong) => "poke_api_failed",
+            ShakespearemonException::PokeClientException(PokeClientException::PokemonNotFound) => "pokemon_not_found",
+            ShakespearemonException::PokeClientException(PokeClientException::PokemonDescriptionNotFound) => "description_not_found",
+            ShakespearemonException::PokeClientException(PokeClientException::ResourceNotFound) => "resource_not_found",
+            ShakespearemonException::PokeClientException(PokeClientException::ResourceDescriptionNotFound) => "resource_description_not_found",
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::TranslationNotFound) => "translation_not_found",
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::ShakespeareClientWentWrong) => "translator_failed",
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::TranslatorMisconfigured) => "translator_misconfigured",
+            ShakespearemonException::ShakespeareClientException(ShakespeareClientException::QuotaExhausted { .. }) => "quota_exhausted",
+            ShakespearemonException::TranslationStoreException(TranslationStoreException::TranslationStoreUnavailable) => "store_unavailable",
+            ShakespearemonException::TranslationStoreException(TranslationStoreException::TranslationStoreWentWrong) => "store_failed",
+            ShakespearemonException::TextTranslationException(TextTranslationException::EmptyText) => "empty_text",
+            ShakespearemonException::TextTranslationException(TextTranslationException::UnsupportedStyle) => "unsupported_style",
+            ShakespearemonException::TextTranslationException(TextTranslationException::InvalidRequest) => "invalid_request",
+            ShakespearemonException::TextTranslationException(TextTranslationException::TextTooLong { .. }) => "text_too_long",
+            ShakespearemonException::BatchJobException(BatchJobException::BatchJobNotFound) => "batch_job_not_found",
+            ShakespearemonException::BatchJobException(BatchJobException::EmptyBatchJob) => "empty_batch_job",
+            ShakespearemonException::BatchJobException(BatchJobException::TooManyNames { .. }) => "too_many_names",
+            ShakespearemonException::BatchJobException(BatchJobException::InvalidBatchJobRequest) => "invalid_request",
+            ShakespearemonException::BatchJobException(BatchJobException::TooManyRunningJobs { .. }) => "too_many_running_jobs",
+            ShakespearemonException::NotAcceptable => "not_acceptable",
+            ShakespearemonException::InvalidDate => "invalid_date",
+        }
+    }
+
+    pub fn error_response_as(&self, format: ResponseFormat) -> HttpResponse {
+        let mut response = HttpResponseBuilder::new(self.status_code());
+
+        if let ShakespearemonException::ShakespeareClientException(ShakespeareClientException::QuotaExhausted { reset_at }) = *self {
+            response.set_header(header::RETRY_AFTER, seconds_until(reset_at).to_string());
+        }
+
+        let message = self.to_string();
+        let body = match format {
+            ResponseFormat::Json => serde_json::to_string(&ErrorResponse {
+                error: self.code().to_owned(),
+                message,
+            }).unwrap_or_default(),
+            ResponseFormat::PlainText => message,
+            ResponseFormat::Html => format!("<div class=\"shakespearemon-error\"><p>{}</p></div>", escape_markup(&message)),
+            ResponseFormat::Xml => format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?><error><message>{}</message></error>", escape_markup(&message)),
+        };
+
+        response
+            .set_header(header::CONTENT_TYPE, format.content_type())
+            .body(body)
+    }
+}
+
+pub fn seconds_until(timestamp: u64) -> u64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    timestamp.saturating_sub(now)
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct ShakespearemonResponse {
     pub name: String,
     pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub metadata: Option<SpeciesMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub media: Option<PokemonMedia>,
 }
 
+#[derive(Default, Deserialize)]
+pub struct ShakespearemonQuery {
+    pub include: Option<String>,
+    #[serde(default)]
+    pub translate_genus: bool,
+}
+
+impl ShakespearemonQuery {
+    pub fn includes(&self, section: &str) -> bool {
+        self.include.as_deref()
+            .map(|include| include.split(',').any(|included| included.trim() == section))
+            .unwrap_or(false)
+    }
+
+    /// The options as a source for the response's `ETag`.
+    pub fn etag_source(&self) -> String {
+        format!("include={};translate_genus={}", self.include.as_deref().unwrap_or_default(), self.translate_genus)
+    }
+}
+
+impl Representation for ShakespearemonResponse {
+    fn to_plain_text(&self) -> String {
+        self.description.to_owned()
+    }
+
+    fn to_html(&self) -> String {
+        format!(
+            "<article class=\"shakespearemon-card\"><h2 class=\"shakespearemon-name\">{}</h2><p class=\"shakespearemon-description\">{}</p>{}{}</article>",
+            escape_markup(&self.name),
+            escape_markup(&self.description),
+            self.metadata.as_ref().map(SpeciesMetadata::to_html).unwrap_or_default(),
+            self.media.as_ref().map(PokemonMedia::to_html).unwrap_or_default(),
+        )
+    }
+
+    fn to_xml(&self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><shakespearemon><name>{}</name><description>{}</description>{}{}</shakespearemon>",
+            escape_markup(&self.name),
+            escape_markup(&self.description),
+            self.metadata.as_ref().map(SpeciesMetadata::to_xml).unwrap_or_default(),
+            self.media.as_ref().map(PokemonMedia::to_xml).unwrap_or_default(),
+        )
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/pokemon/{name}",
+    tag = "pokemon",
+    params(
+        ("name" = String, Path, description = "Species name or national dex number"),
+        ("include" = Option<String>, Query, description = "Comma-separated extras: `metadata`, `media`"),
+        ("translate_genus" = Option<bool>, Query, description = "Also translate the genus when metadata is included"),
+    ),
+    responses(
+        (status = 200, description = "The species description in Shakespearean English", body = ShakespearemonResponse),
+        (status = 304, description = "The client's copy, named by `If-None-Match`, is current"),
+        (status = 404, description = "The species is unknown or has no English description", body = ErrorResponse),
+        (status = 406, description = "None of the accepted media types can be produced", body = ErrorResponse),
+        (status = 429, description = "The translation quota is spent", body = ErrorResponse),
+        (status = 500, description = "An upstream service failed", body = ErrorResponse),
+        (status = 502, description = "The translator rejected our credentials", body = ErrorResponse),
+    )
+)]
 #[get("/pokemon/{name}")]
-pub async fn translate_pokemon_description_by_shakespeare(data: web::Data<Settings>, web::Path(name): web::Path<String>) -> Result<HttpResponse, ShakespearemonException> {
-    let pokemon_description = poke::poke_client::get_pokemon_description(&data.application.poke_api_base_url, &name).await
+pub async fn translate_pokemon_description_by_shakespeare(request: HttpRequest, data: web::Data<Settings>, species_source: web::Data<dyn SpeciesSource>, pokemon_source: Option<web::Data<dyn PokemonSource>>, translation_pipeline: TranslationPipeline, web::Path(name): web::Path<String>, web::Query(query): web::Query<ShakespearemonQuery>) -> Result<HttpResponse, NegotiatedException> {
+    let format = ResponseFormat::from_request(&request).ok_or_else(NegotiatedException::not_acceptable)?;
+
+    let pokemon_source = pokemon_source.as_deref().map(Arc::as_ref);
+    let max_age_seconds = data.application.cache_max_age_seconds;
+
+    let (poke_species_response, media) = get_species_with_media(species_source.as_ref(), pokemon_source, &name, &query).await
+        .map_err(|error| {
+            NegotiatedException::new(error, format)
+        })?;
+
+    let etag = compute_etag(format, &[&name, &etag_source(&poke_species_response), &etag_source(&media), &query.etag_source(), &data.application.translator_identity()]);
+    if let Some(not_modified) = respond_if_not_modified(&request, &etag, max_age_seconds) {
+        return Ok(not_modified);
+    }
+
+    let shakespearemon_response = describe_species(&translation_pipeline, poke_species_response, name, media, &query).await
+        .map_err(|error| {
+            NegotiatedException::new(error, format)
+        })?;
+
+    if translation_pipeline.served_stand_in() {
+        return Ok(respond_without_caching(format, &shakespearemon_response));
+    }
+
+    Ok(respond_with_caching(format, etag, &shakespearemon_response, max_age_seconds))
+}
+
+/// Fetches the species and, when asked for, its media; everything the response
+/// is translated from.
+async fn get_species_with_media(species_source: &dyn SpeciesSource, pokemon_source: Option<&dyn PokemonSource>, name: &str, query: &ShakespearemonQuery) -> Result<(PokeSpeciesResponse, Option<PokemonMedia>), ShakespearemonException> {
+    let pokemon_source = pokemon_source.filter(|_| query.includes("media"));
+
+    // The pokemon resource is fetched alongside the species; media is an
+    // extra, so failing to fetch it never fails the whole response.
+    let (poke_species_response, pokemon_response) = join!(
+        species_source.get_pokemon_species(name),
+        async {
+            match pokemon_source {
+                Some(pokemon_source) => Some(pokemon_source.get_pokemon(name).await),
+                None => None
+            }
+        }
+    );
+
+    let poke_species_response = poke_species_response
         .map_err(|error| {
             ShakespearemonException::PokeClientException(error)
         })?;
 
-    let translation = shakespeare::shakespeare_client::get_shakespearean_translation(&data.application.shakespeare_translator_api_base_url, &pokemon_description).await
+    let pokemon_response = match (pokemon_source, pokemon_response) {
+        // Some species (e.g. deoxys) have no pokemon of the same name, so
+        // retry with the id of the species' default form.
+        (Some(pokemon_source), Some(Err(PokeClientException::PokemonNotFound))) => {
+            Some(pokemon_source.get_pokemon(&poke_species_response.id.to_string()).await)
+        }
+        (_, pokemon_response) => pokemon_response
+    };
+
+    let media = match pokemon_response {
+        Some(Ok(pokemon_response)) => Some(PokemonMedia::from_pokemon_response(&pokemon_response)),
+        Some(Err(error)) => {
+            warn!("Pokemon media could not be fetched for {}: {:?}", name, error);
+            None
+        }
+        None => None
+    };
+
+    Ok((poke_species_response, media))
+}
+
+/// Fetches and translates a species without any extras.
+pub async fn describe_species_by_name(species_source: &dyn SpeciesSource, translation_pipeline: &TranslationPipeline, name: String) -> Result<ShakespearemonResponse, ShakespearemonException> {
+    let poke_species_response = species_source.get_pokemon_species(&name).await
         .map_err(|error| {
-            ShakespearemonException::ShakespeareClientException(error)
+            ShakespearemonException::PokeClientException(error)
         })?;
 
-    let shakespearemon_response = ShakespearemonResponse {
-        description: translation,
-        name,
+    describe_species(translation_pipeline, poke_species_response, name, None, &ShakespearemonQuery::default()).await
+}
+
+/// Translates an already fetched species into the response served for it.
+pub async fn describe_species(translation_pipeline: &TranslationPipeline, poke_species_response: PokeSpeciesResponse, name: String, media: Option<PokemonMedia>, query: &ShakespearemonQuery) -> Result<ShakespearemonResponse, ShakespearemonException> {
+    let mut metadata = if query.includes("metadata") {
+        Some(SpeciesMetadata::from_poke_species_response(&poke_species_response))
+    } else {
+        None
     };
 
-    Ok(HttpResponse::Ok().json(shakespearemon_response))
-}
\ No newline at end of file
+    let pokemon_description = extract_english_flavor_text_from_poke_species_response(poke_species_response)
+        .ok_or(ShakespearemonException::PokeClientException(PokeClientException::PokemonDescriptionNotFound))?;
+
+    let translation = translation_pipeline.translate(&pokemon_description).await?;
+
+    if query.translate_genus {
+        if let Some(metadata) = metadata.as_mut() {
+            if let Some(genus) = &metadata.genus {
+                // The genus is an optional extra; losing it must not cost the client the description.
+                match translation_pipeline.translate(genus).await {
+                    Ok(translated_genus) => metadata.translated_genus = Some(translated_genus),
+                    Err(error) => warn!("Genus could not be translated for {}: {:?}", name, error),
+                }
+            }
+        }
+    }
+
+    Ok(ShakespearemonResponse {
+        description: translation,
+        name,
+        metadata,
+        media,
+    })
+}
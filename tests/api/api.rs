@@ -4,19 +4,18 @@ use actix_web::test::read_body_json;
 use surf::StatusCode as SurfStatusCode;
 use wiremock::{MockServer, ResponseTemplate};
 
-use shakespearemon::settings::Settings;
 use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
 use shakespearemon::translation_service::ShakespearemonResponse;
 use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
 
-use crate::helpers::{generate_poke_species_response, get_application, mock_poke_client_request, mock_shakespeare_client_request, UndefinedResponse};
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+
+use crate::helpers::{as_species_source, generate_poke_species_response, get_settings, get_species_source, get_translator, mock_poke_client_request, mock_shakespeare_client_request, UndefinedResponse};
 
 #[actix_rt::test]
 async fn returns_500_if_poke_api_returns_undefined_response() {
     let mock_server = MockServer::start().await;
 
-    let application = get_application(mock_server.uri());
-
     let response = UndefinedResponse {
         message: "message".to_owned()
     };
@@ -24,9 +23,9 @@ async fn returns_500_if_poke_api_returns_undefined_response() {
     mock_poke_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(response), "/ozer".to_owned()).await;
 
     let mut app = test::init_service(App::new()
-        .data(Settings {
-            application
-        })
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
         .service(translate_pokemon_description_by_shakespeare)).await;
 
     let req = test::TestRequest::get()
@@ -40,14 +39,12 @@ async fn returns_500_if_poke_api_returns_undefined_response() {
 async fn returns_404_pokemon_named_not_found() {
     let mock_server = MockServer::start().await;
 
-    let application = get_application(mock_server.uri());
-
     mock_poke_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::NotFound), "/ozer".to_owned()).await;
 
     let mut app = test::init_service(App::new()
-        .data(Settings {
-            application
-        })
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
         .service(translate_pokemon_description_by_shakespeare)).await;
 
     let req = test::TestRequest::get()
@@ -61,14 +58,12 @@ async fn returns_404_pokemon_named_not_found() {
 async fn returns_500_if_poke_api_sends_too_many_requests() {
     let mock_server = MockServer::start().await;
 
-    let application = get_application(mock_server.uri());
-
     mock_poke_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::TooManyRequests), "/ozer".to_owned()).await;
 
     let mut app = test::init_service(App::new()
-        .data(Settings {
-            application
-        })
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
         .service(translate_pokemon_description_by_shakespeare)).await;
 
     let req = test::TestRequest::get()
@@ -82,8 +77,6 @@ async fn returns_500_if_poke_api_sends_too_many_requests() {
 async fn returns_500_if_shakespeare_translator_api_returns_undefined_response() {
     let mock_server = MockServer::start().await;
 
-    let application = get_application(mock_server.uri());
-
     mock_poke_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok), "/ozer".to_owned()).await;
 
     let undefined_response = UndefinedResponse {
@@ -93,9 +86,9 @@ async fn returns_500_if_shakespeare_translator_api_returns_undefined_response()
     mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(StatusCode::OK).set_body_json(undefined_response)).await;
 
     let mut app = test::init_service(App::new()
-        .data(Settings {
-            application
-        })
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
         .service(translate_pokemon_description_by_shakespeare)).await;
 
     let req = test::TestRequest::get()
@@ -109,8 +102,6 @@ async fn returns_500_if_shakespeare_translator_api_returns_undefined_response()
 async fn returns_500_if_shakespeare_translator_api_returns_too_many_requests() {
     let mock_server = MockServer::start().await;
 
-    let application = get_application(mock_server.uri());
-
     let poke_species_response = generate_poke_species_response("en".to_owned());
 
     mock_poke_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(poke_species_response), "/ozer".to_owned()).await;
@@ -118,9 +109,9 @@ async fn returns_500_if_shakespeare_translator_api_returns_too_many_requests() {
     mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::TooManyRequests)).await;
 
     let mut app = test::init_service(App::new()
-        .data(Settings {
-            application
-        })
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
         .service(translate_pokemon_description_by_shakespeare)).await;
 
     let req = test::TestRequest::get()
@@ -131,10 +122,31 @@ async fn returns_500_if_shakespeare_translator_api_returns_too_many_requests() {
 }
 
 #[actix_rt::test]
-async fn gets_translation_of_pokemon_by_shakespeare() {
+async fn returns_502_if_shakespeare_translator_api_rejects_our_credentials() {
     let mock_server = MockServer::start().await;
 
-    let application = get_application(mock_server.uri());
+    let poke_species_response = generate_poke_species_response("en".to_owned());
+
+    mock_poke_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(poke_species_response), "/ozer".to_owned()).await;
+
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Unauthorized)).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/ozer").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+}
+
+#[actix_rt::test]
+async fn gets_translation_of_pokemon_by_shakespeare() {
+    let mock_server = MockServer::start().await;
 
     let poke_species_response = generate_poke_species_response("en".to_owned());
     mock_poke_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(poke_species_response), "/pikachu".to_owned()).await;
@@ -143,9 +155,74 @@ async fn gets_translation_of_pokemon_by_shakespeare() {
     mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
 
     let mut app = test::init_service(App::new()
-        .data(Settings {
-            application
-        })
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let shakespearemon_response: ShakespearemonResponse = read_body_json(resp).await;
+    assert_eq!(shakespearemon_response.name, "pikachu");
+    assert_eq!(shakespearemon_response.description, "translated");
+}
+
+#[actix_rt::test]
+async fn returns_404_if_species_source_does_not_know_the_pokemon() {
+    let mock_server = MockServer::start().await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new()))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/ozer").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn returns_404_if_species_has_no_english_description() {
+    let mock_server = MockServer::start().await;
+
+    let species_source = InMemorySpeciesSource::new()
+        .with_flavor_text("pikachu", "Texte de saveur", "fr");
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(species_source))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn translates_english_description_from_species_source() {
+    let mock_server = MockServer::start().await;
+
+    let species_source = InMemorySpeciesSource::new()
+        .with_flavor_text("pikachu", "Texte de saveur", "fr")
+        .with_flavor_text("pikachu", "Flavor text", "en");
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("Flavor text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(species_source))
+        .app_data(get_translator(&mock_server.uri()))
         .service(translate_pokemon_description_by_shakespeare)).await;
 
     let req = test::TestRequest::get()
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use actix_http::Request;
+use actix_web::{App, Error, test, web};
+use actix_web::body::Body;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::{header, StatusCode};
+use actix_web::test::{read_body, read_body_json};
+use serde_json::{json, Value};
+
+use shakespearemon::admin_service::{create_api_key, CreateApiKeyResponse, list_api_keys, revoke_api_key};
+use shakespearemon::auth::api_key::ApiKey;
+use shakespearemon::auth::api_key_middleware::ApiKeyAuth;
+use shakespearemon::auth::api_key_registry::ApiKeyRegistry;
+use shakespearemon::error_response::ErrorResponse;
+use shakespearemon::graphql::graphql_service::execute_graphql;
+use shakespearemon::graphql::schema::build_schema;
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::settings::{Auth, ConfiguredApiKey};
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::store::api_key_store::ApiKeyStore;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, get_settings};
+
+const ADMIN_KEY: &str = "admin-secret";
+const HOMEPAGE_KEY: &str = "homepage-secret";
+
+fn get_auth() -> Auth {
+    Auth {
+        admin_key: ADMIN_KEY.to_owned(),
+        keys: vec![ConfiguredApiKey {
+            key: HOMEPAGE_KEY.to_owned(),
+            name: "homepage".to_owned(),
+            hourly_limit: 2,
+            daily_limit: 100,
+        }],
+        default_hourly_limit: 10,
+        default_daily_limit: 100,
+    }
+}
+
+async fn get_app() -> impl Service<Request = Request, Response = ServiceResponse<Body>, Error = Error> {
+    let api_key_store = ApiKeyStore::open(":memory:").unwrap();
+    let api_key_registry = Arc::new(ApiKeyRegistry::new(&get_auth()).with_api_key_store(Arc::new(api_key_store)));
+
+    test::init_service(App::new()
+        .data(get_settings("http://localhost".to_owned()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(as_translator(LocalTranslator::new(false)))
+        .app_data(web::Data::from(api_key_registry.clone()))
+        .wrap(ApiKeyAuth::new(api_key_registry))
+        .service(translate_pokemon_description_by_shakespeare)
+        .service(create_api_key)
+        .service(list_api_keys)
+        .service(revoke_api_key)).await
+}
+
+async fn get_graphql_app() -> impl Service<Request = Request, Response = ServiceResponse<Body>, Error = Error> {
+    let api_key_registry = Arc::new(ApiKeyRegistry::new(&get_auth()));
+
+    test::init_service(App::new()
+        .data(get_settings("http://localhost".to_owned()))
+        .data(build_schema(10))
+        .app_data(as_species_source(InMemorySpeciesSource::new()
+            .with_flavor_text("pikachu", "Flavor text", "en")
+            .with_flavor_text("raichu", "Flavor text", "en")))
+        .app_data(as_translator(LocalTranslator::new(false)))
+        .wrap(ApiKeyAuth::new(api_key_registry))
+        .service(execute_graphql)).await
+}
+
+fn get_pokemon(key: &str) -> Request {
+    test::TestRequest::get()
+        .uri("/pokemon/pikachu")
+        .header("X-Api-Key", key)
+        .to_request()
+}
+
+async fn assert_error(resp: ServiceResponse<Body>, status: StatusCode, code: &str) {
+    assert_eq!(resp.status(), status);
+    let error: ErrorResponse = read_body_json(resp).await;
+    assert_eq!(error.error, code);
+}
+
+#[actix_rt::test]
+async fn rejects_requests_without_a_key() {
+    let mut app = get_app().await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+    let resp = test::call_service(&mut app, req).await;
+
+    assert!(resp.headers().contains_key(header::WWW_AUTHENTICATE));
+    assert_error(resp, StatusCode::UNAUTHORIZED, "missing_api_key").await;
+}
+
+#[actix_rt::test]
+async fn rejects_unknown_keys() {
+    let mut app = get_app().await;
+
+    let resp = test::call_service(&mut app, get_pokemon("guessed")).await;
+
+    assert_error(resp, StatusCode::UNAUTHORIZED, "invalid_api_key").await;
+}
+
+#[actix_rt::test]
+async fn meters_configured_keys_and_reports_their_limits() {
+    let mut app = get_app().await;
+
+    let resp = test::call_service(&mut app, get_pokemon(HOMEPAGE_KEY)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("X-RateLimit-Limit").unwrap(), "2");
+    assert_eq!(resp.headers().get("X-RateLimit-Remaining").unwrap(), "1");
+    assert_eq!(resp.headers().get("X-RateLimit-Daily-Remaining").unwrap(), "99");
+
+    let resp = test::call_service(&mut app, get_pokemon(HOMEPAGE_KEY)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("X-RateLimit-Remaining").unwrap(), "0");
+
+    let resp = test::call_service(&mut app, get_pokemon(HOMEPAGE_KEY)).await;
+    assert!(resp.headers().contains_key(header::RETRY_AFTER));
+    assert_eq!(resp.headers().get("X-RateLimit-Remaining").unwrap(), "0");
+    assert_error(resp, StatusCode::TOO_MANY_REQUESTS, "rate_limited").await;
+}
+
+#[actix_rt::test]
+async fn keeps_admin_routes_for_the_admin_key() {
+    let mut app = get_app().await;
+
+    let req = test::TestRequest::get()
+        .uri("/admin/api-keys")
+        .header("X-Api-Key", HOMEPAGE_KEY)
+        .to_request();
+    let resp = test::call_service(&mut app, req).await;
+
+    assert_error(resp, StatusCode::FORBIDDEN, "admin_key_required").await;
+}
+
+#[actix_rt::test]
+async fn creates_and_revokes_keys_through_admin_endpoints() {
+    let mut app = get_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/admin/api-keys")
+        .header("X-Api-Key", ADMIN_KEY)
+        .set_json(&json!({ "name": "mobile", "hourly_limit": 5 }))
+        .to_request();
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let created: CreateApiKeyResponse = read_body_json(resp).await;
+    assert_eq!(created.api_key.name, "mobile");
+    assert_eq!(created.api_key.hourly_limit, 5);
+    assert_eq!(created.api_key.daily_limit, 100);
+
+    let resp = test::call_service(&mut app, get_pokemon(&created.key)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("X-RateLimit-Limit").unwrap(), "5");
+
+    let req = test::TestRequest::get()
+        .uri("/admin/api-keys")
+        .header("X-Api-Key", ADMIN_KEY)
+        .to_request();
+    let body = read_body(test::call_service(&mut app, req).await).await;
+    assert!(!String::from_utf8_lossy(&body).contains(&created.key));
+    let api_keys: Vec<ApiKey> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(api_keys.iter().map(|api_key| api_key.name.as_str()).collect::<Vec<&str>>(), vec!["homepage", "mobile"]);
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/admin/api-keys/{}", created.api_key.id))
+        .header("X-Api-Key", ADMIN_KEY)
+        .to_request();
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = test::call_service(&mut app, get_pokemon(&created.key)).await;
+    assert_error(resp, StatusCode::FORBIDDEN, "revoked_api_key").await;
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/admin/api-keys/{}", created.api_key.id))
+        .header("X-Api-Key", ADMIN_KEY)
+        .to_request();
+    let resp = test::call_service(&mut app, req).await;
+    assert_error(resp, StatusCode::NOT_FOUND, "api_key_not_found").await;
+}
+
+#[test]
+fn charges_graphql_batches_one_request_per_name() {
+    actix_web::rt::System::new("api-keys").block_on(async {
+        let mut app = get_graphql_app().await;
+        let query = |names: &str| test::TestRequest::post()
+            .uri("/graphql")
+            .header("X-Api-Key", HOMEPAGE_KEY)
+            .set_json(&json!({ "query": format!("{{ pokemons(names: [{}]) {{ name }} }}", names) }))
+            .to_request();
+
+        // The request pays for its first name on the way in, so one of the two calls is left.
+        let response: Value = read_body_json(test::call_service(&mut app, query(r#""pikachu", "raichu", "pikachu""#)).await).await;
+        assert_eq!(response["errors"][0]["extensions"]["code"], "rate_limited");
+
+        let response: Value = read_body_json(test::call_service(&mut app, query(r#""raichu""#)).await).await;
+        assert_eq!(response["data"]["pokemons"][0]["name"], "raichu");
+
+        let resp = test::call_service(&mut app, query(r#""pikachu""#)).await;
+        assert_error(resp, StatusCode::TOO_MANY_REQUESTS, "rate_limited").await;
+    });
+}
@@ -0,0 +1,203 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use actix_http::Request;
+use actix_web::{App, Error, test, web};
+use actix_web::body::Body;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::{header, Method, StatusCode};
+use actix_web::test::{read_body, read_body_json};
+
+use shakespearemon::api_versioning::{API_V1_PREFIX, configure_public_api, Deprecated};
+use shakespearemon::error_response::ErrorResponse;
+use shakespearemon::graphql::graphql_service::{execute_graphql, get_graphql_playground};
+use shakespearemon::graphql::schema::build_schema;
+use shakespearemon::jobs::batch_job_registry::BatchJobRegistry;
+use shakespearemon::jobs::batch_job_service::configure_batch_jobs;
+use shakespearemon::openapi::{get_openapi_spec, get_swagger_ui, openapi_spec};
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+
+use crate::helpers::{as_species_source, as_translator, get_settings};
+
+async fn get_app() -> impl Service<Request = Request, Response = ServiceResponse<Body>, Error = Error> {
+    let settings = get_settings("http://localhost".to_owned());
+    let max_text_length = settings.application.max_text_length;
+
+    test::init_service(App::new()
+        .data(settings)
+        .data(build_schema(10))
+        .data(BatchJobRegistry::new(Duration::from_secs(60)))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(as_translator(LocalTranslator::new(false)))
+        .service(web::scope(API_V1_PREFIX).configure(configure_public_api(max_text_length)).configure(configure_batch_jobs))
+        .service(get_openapi_spec)
+        .service(get_swagger_ui)
+        .service(execute_graphql)
+        .service(get_graphql_playground)
+        .service(web::scope("").wrap(Deprecated).configure(configure_public_api(max_text_length)))).await
+}
+
+/// Every operation `main` serves to API clients. The admin API and the
+/// documentation itself are left out of the document on purpose.
+const SERVED_OPERATIONS: &[(&str, &str)] = &[
+    ("get", "/v1/pokemon/daily"),
+    ("get", "/v1/pokemon/{name}"),
+    ("get", "/v1/ability/{name}"),
+    ("get", "/v1/move/{name}"),
+    ("post", "/v1/translate"),
+    ("get", "/pokemon/daily"),
+    ("get", "/pokemon/{name}"),
+    ("get", "/ability/{name}"),
+    ("get", "/move/{name}"),
+    ("post", "/translate"),
+    ("post", "/v1/jobs"),
+    ("get", "/v1/jobs/{id}"),
+    ("get", "/v1/jobs/{id}/events"),
+    ("get", "/v1/jobs/{id}/ws"),
+    ("post", "/graphql"),
+    ("get", "/graphql"),
+];
+
+fn spec_file() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("openapi.json")
+}
+
+#[actix_rt::test]
+async fn serves_the_versioned_route_without_deprecation() {
+    let mut app = get_app().await;
+
+    let req = test::TestRequest::get().uri("/v1/pokemon/pikachu").to_request();
+    let resp = test::call_service(&mut app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers().get("deprecation").is_none());
+}
+
+#[actix_rt::test]
+async fn serves_the_unversioned_route_as_a_deprecated_alias() {
+    let mut app = get_app().await;
+
+    let req = test::TestRequest::get().uri("/pokemon/pikachu").to_request();
+    let resp = test::call_service(&mut app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("deprecation").unwrap(), "true");
+    assert_eq!(resp.headers().get(header::LINK).unwrap(), "</v1/pokemon/pikachu>; rel=\"successor-version\"");
+}
+
+#[actix_rt::test]
+async fn answers_errors_with_an_error_response_under_v1() {
+    let mut app = get_app().await;
+
+    let req = test::TestRequest::get().uri("/v1/pokemon/missingno").to_request();
+    let body: ErrorResponse = read_body_json(test::call_service(&mut app, req).await).await;
+
+    assert_eq!(body.error, "pokemon_not_found");
+    assert_eq!(body.message, "Pokemon Not Found");
+}
+
+#[actix_rt::test]
+async fn keeps_bare_error_messages_on_the_deprecated_alias() {
+    let mut app = get_app().await;
+
+    let req = test::TestRequest::get().uri("/pokemon/missingno").to_request();
+    let resp = test::call_service(&mut app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "text/plain; charset=utf-8");
+    assert_eq!(read_body(resp).await, "Pokemon Not Found");
+}
+
+#[actix_rt::test]
+async fn does_not_mark_unknown_paths_as_deprecated() {
+    let mut app = get_app().await;
+
+    let req = test::TestRequest::get().uri("/berries/oran").to_request();
+    let resp = test::call_service(&mut app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert!(resp.headers().get("deprecation").is_none());
+}
+
+#[actix_rt::test]
+async fn serves_the_openapi_document_and_swagger_ui() {
+    let mut app = get_app().await;
+
+    let req = test::TestRequest::get().uri("/openapi.json").to_request();
+    let spec: serde_json::Value = read_body_json(test::call_service(&mut app, req).await).await;
+
+    assert_eq!(spec["openapi"], "3.0.3");
+    assert_eq!(spec["paths"]["/pokemon/{name}"]["get"]["deprecated"], true);
+    assert!(spec["paths"]["/v1/pokemon/{name}"]["get"]["deprecated"].is_null());
+    assert!(spec["components"]["schemas"]["ShakespearemonResponse"].is_object());
+    assert!(spec["components"]["schemas"]["ErrorResponse"].is_object());
+
+    let req = test::TestRequest::get().uri("/docs").to_request();
+    let body = read_body(test::call_service(&mut app, req).await).await;
+
+    assert!(String::from_utf8(body.to_vec()).unwrap().contains("url: \"/openapi.json\""));
+}
+
+#[test]
+fn routes_every_documented_operation() {
+    actix_web::rt::System::new("api_versioning").block_on(async {
+        let mut app = get_app().await;
+        let spec = openapi_spec();
+
+        for (path, path_item) in &spec.paths.paths {
+            for operation in path_item.operations.keys() {
+                let method = match serde_json::to_value(operation).unwrap().as_str().unwrap() {
+                    "get" => Method::GET,
+                    "post" => Method::POST,
+                    other => panic!("Unexpected method {} for {}", other, path),
+                };
+                let uri = path.replace("{name}", "pikachu").replace("{id}", "unknown");
+
+                let req = test::TestRequest::with_uri(&uri).method(method.clone()).to_request();
+                let resp = test::call_service(&mut app, req).await;
+
+                assert!(resp.request().match_pattern().is_some(), "{} {} is documented but not routed", method, path);
+            }
+        }
+    });
+}
+
+#[test]
+fn documents_every_served_operation() {
+    actix_web::rt::System::new("api_versioning").block_on(async {
+        let mut app = get_app().await;
+        let spec = serde_json::to_value(openapi_spec()).unwrap();
+
+        for (method, path) in SERVED_OPERATIONS {
+            let uri = path.replace("{name}", "pikachu").replace("{id}", "unknown");
+            let req = test::TestRequest::with_uri(&uri).method(method.to_uppercase().parse().unwrap()).to_request();
+            let resp = test::call_service(&mut app, req).await;
+
+            assert!(resp.request().match_pattern().is_some(), "{} {} is listed but not routed", method, path);
+            assert!(spec["paths"][path][method].is_object(), "{} {} is served but not documented", method, path);
+        }
+
+        let documented = spec["paths"].as_object().unwrap().values()
+            .map(|path_item| path_item.as_object().unwrap().len())
+            .sum::<usize>();
+        assert_eq!(documented, SERVED_OPERATIONS.len(), "an operation is documented but missing from SERVED_OPERATIONS");
+    });
+}
+
+/// Fails when handlers or response types change without `openapi.json` being
+/// regenerated. Run with `UPDATE_OPENAPI_SPEC=1` to rewrite it.
+#[test]
+fn checked_in_openapi_document_matches_the_handlers() {
+    let generated = openapi_spec().to_pretty_json().unwrap() + "\n";
+
+    if std::env::var_os("UPDATE_OPENAPI_SPEC").is_some() {
+        std::fs::write(spec_file(), &generated).unwrap();
+    }
+
+    let checked_in = std::fs::read_to_string(spec_file()).unwrap_or_default();
+    assert!(
+        checked_in == generated,
+        "openapi.json is out of date; regenerate it with `UPDATE_OPENAPI_SPEC=1 cargo test checked_in_openapi_document`"
+    );
+}
@@ -0,0 +1,253 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use actix_http::Request;
+use actix_web::{App, Error, HttpServer, test, web};
+use actix_web::body::Body;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::{header, StatusCode};
+use actix_web::test::{read_body, read_body_json};
+use async_trait::async_trait;
+use awc::Client;
+use awc::ws::Frame;
+use futures::StreamExt;
+use serde_json::json;
+
+use shakespearemon::jobs::batch_job::{BatchJob, BatchJobEvent, BatchJobState};
+use shakespearemon::jobs::batch_job_registry::BatchJobRegistry;
+use shakespearemon::jobs::batch_job_service::configure_batch_jobs;
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use shakespearemon::shakespeare::translator::Translator;
+
+use crate::helpers::{as_species_source, as_translator, get_settings};
+
+/// Spends its quota on the first call; the quota is back by the time it is retried.
+#[derive(Default)]
+struct QuotaOnceTranslator {
+    exhausted: AtomicBool,
+}
+
+#[async_trait]
+impl Translator for QuotaOnceTranslator {
+    async fn get_shakespearean_translation(&self, text: &str) -> Result<String, ShakespeareClientException> {
+        if !self.exhausted.swap(true, Ordering::SeqCst) {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+            return Err(ShakespeareClientException::QuotaExhausted { reset_at: now });
+        }
+        LocalTranslator::new(false).get_shakespearean_translation(text).await
+    }
+}
+
+fn get_species_source() -> InMemorySpeciesSource {
+    InMemorySpeciesSource::new()
+        .with_flavor_text("pikachu", "You are my friend", "en")
+        .with_flavor_text("raichu", "Are you my friend", "en")
+        .with_species("missingno")
+}
+
+async fn get_app<T: Translator + 'static>(translator: T) -> impl Service<Request = Request, Response = ServiceResponse<Body>, Error = Error> {
+    test::init_service(App::new()
+        .data(get_settings("http://localhost".to_owned()))
+        .data(BatchJobRegistry::new(Duration::from_secs(60)))
+        .app_data(as_species_source(get_species_source()))
+        .app_data(as_translator(translator))
+        .service(web::scope("/v1").configure(configure_batch_jobs))).await
+}
+
+async fn create_job<S>(app: &mut S, names: &[&str]) -> BatchJob
+    where S: Service<Request = Request, Response = ServiceResponse<Body>, Error = Error> {
+    let req = test::TestRequest::post()
+        .uri("/v1/jobs")
+        .set_json(&json!({ "names": names }))
+        .to_request();
+
+    let resp = test::call_service(app, req).await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+    let job: BatchJob = read_body_json(resp).await;
+    assert_eq!(job.state, BatchJobState::Running);
+    job
+}
+
+/// Reads an SSE body as `(id, event, data)` triples.
+fn parse_server_sent_events(body: &[u8]) -> Vec<(usize, String, BatchJobEvent)> {
+    std::str::from_utf8(body).unwrap()
+        .split("\n\n")
+        .filter(|event| !event.is_empty())
+        .map(|event| {
+            let field = |name: &str| event.lines()
+                .find_map(|line| line.strip_prefix(&format!("{}: ", name)))
+                .unwrap()
+                .to_owned();
+            (field("id").parse().unwrap(), field("event"), serde_json::from_str(&field("data")).unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn streams_each_result_and_a_summary_over_server_sent_events() {
+    actix_web::rt::System::new("batch-jobs").block_on(async {
+        let mut app = get_app(LocalTranslator::new(false)).await;
+        let job = create_job(&mut app, &["pikachu", "missingno", "raichu"]).await;
+
+        let req = test::TestRequest::get().uri(&format!("/v1/jobs/{}/events", job.id)).to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "text/event-stream");
+
+        let events = parse_server_sent_events(&read_body(resp).await);
+
+        let kinds: Vec<_> = events.iter().map(|(id, kind, _)| (*id, kind.as_str())).collect();
+        assert_eq!(kinds, vec![(0, "result"), (1, "result"), (2, "result"), (3, "summary")]);
+        match &events[3].2 {
+            BatchJobEvent::Summary(summary) => {
+                assert_eq!(summary.total, 3);
+                assert_eq!(summary.succeeded, 2);
+                assert_eq!(summary.failed, 1);
+            }
+            event => panic!("expected a summary, got {:?}", event),
+        }
+    });
+}
+
+#[test]
+fn keeps_results_to_fetch_after_the_client_disconnects() {
+    actix_web::rt::System::new("batch-jobs").block_on(async {
+        let mut app = get_app(LocalTranslator::new(false)).await;
+        let job = create_job(&mut app, &["pikachu", "missingno"]).await;
+
+        // Waits for the job through its events, then drops the stream.
+        let req = test::TestRequest::get().uri(&format!("/v1/jobs/{}/events", job.id)).to_request();
+        read_body(test::call_service(&mut app, req).await).await;
+
+        let req = test::TestRequest::get().uri(&format!("/v1/jobs/{}", job.id)).to_request();
+        let job: BatchJob = test::read_response_json(&mut app, req).await;
+
+        assert_eq!(job.state, BatchJobState::Completed);
+        assert_eq!(job.results[0].description, Some("Thou art my friend".to_owned()));
+        assert_eq!(job.results[1].error.as_ref().unwrap().error, "description_not_found");
+        assert_eq!(job.summary.unwrap().failed, 1);
+    });
+}
+
+#[test]
+fn resumes_server_sent_events_after_the_last_event_id() {
+    actix_web::rt::System::new("batch-jobs").block_on(async {
+        let mut app = get_app(LocalTranslator::new(false)).await;
+        let job = create_job(&mut app, &["pikachu", "raichu"]).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/v1/jobs/{}/events", job.id))
+            .header("Last-Event-ID", "0")
+            .to_request();
+        let events = parse_server_sent_events(&read_body(test::call_service(&mut app, req).await).await);
+
+        let ids: Vec<_> = events.iter().map(|(id, _, _)| *id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    });
+}
+
+#[test]
+fn waits_for_the_quota_and_retries_the_species() {
+    actix_web::rt::System::new("batch-jobs").block_on(async {
+        let mut app = get_app(QuotaOnceTranslator::default()).await;
+        let job = create_job(&mut app, &["pikachu"]).await;
+
+        let req = test::TestRequest::get().uri(&format!("/v1/jobs/{}/events", job.id)).to_request();
+        let events = parse_server_sent_events(&read_body(test::call_service(&mut app, req).await).await);
+
+        let kinds: Vec<_> = events.iter().map(|(_, kind, _)| kind.as_str()).collect();
+        assert_eq!(kinds, vec!["quota_wait", "result", "summary"]);
+        assert!(matches!(&events[1].2, BatchJobEvent::Result(result) if result.description.as_deref() == Some("Thou art my friend")));
+    });
+}
+
+#[actix_rt::test]
+async fn rejects_jobs_without_names_or_with_too_many() {
+    let mut app = get_app(LocalTranslator::new(false)).await;
+
+    let req = test::TestRequest::post().uri("/v1/jobs").set_json(&json!({ "names": [] })).to_request();
+    assert_eq!(test::call_service(&mut app, req).await.status(), StatusCode::BAD_REQUEST);
+
+    let names = vec!["pikachu"; 1026];
+    let req = test::TestRequest::post().uri("/v1/jobs").set_json(&json!({ "names": names })).to_request();
+    assert_eq!(test::call_service(&mut app, req).await.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let req = test::TestRequest::post().uri("/v1/jobs").set_json(&json!({ "name": "pikachu" })).to_request();
+    assert_eq!(test::call_service(&mut app, req).await.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn returns_404_for_unknown_jobs() {
+    let mut app = get_app(LocalTranslator::new(false)).await;
+
+    let req = test::TestRequest::get().uri("/v1/jobs/unknown").to_request();
+    assert_eq!(test::call_service(&mut app, req).await.status(), StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::get().uri("/v1/jobs/unknown/events").to_request();
+    assert_eq!(test::call_service(&mut app, req).await.status(), StatusCode::NOT_FOUND);
+}
+
+/// Each message is an event plus its `sequence`.
+async fn read_socket_events(url: &str) -> Vec<(usize, BatchJobEvent)> {
+    let (_, mut socket) = Client::default().ws(url).connect().await.unwrap();
+
+    let mut events = Vec::new();
+    while let Some(frame) = socket.next().await {
+        match frame.unwrap() {
+            Frame::Text(text) => {
+                let message: serde_json::Value = serde_json::from_slice(&text).unwrap();
+                let sequence = message["sequence"].as_u64().unwrap() as usize;
+                events.push((sequence, serde_json::from_value::<BatchJobEvent>(message).unwrap()));
+            }
+            Frame::Close(_) => break,
+            _ => {}
+        }
+    }
+    events
+}
+
+#[test]
+fn streams_events_over_a_websocket() {
+    actix_web::rt::System::new("batch-jobs").block_on(async {
+        let registry = Arc::new(BatchJobRegistry::new(Duration::from_secs(60)));
+        let app_registry = web::Data::from(registry.clone());
+
+        let server = HttpServer::new(move || {
+            App::new()
+                .data(get_settings("http://localhost".to_owned()))
+                .app_data(app_registry.clone())
+                .app_data(as_species_source(get_species_source()))
+                .app_data(as_translator(LocalTranslator::new(false)))
+                .service(web::scope("/v1").configure(configure_batch_jobs))
+        })
+            .workers(1)
+            .disable_signals()
+            .bind("127.0.0.1:0")
+            .unwrap();
+        let addr = server.addrs()[0];
+        let server = server.run();
+
+        let mut response = Client::default().post(format!("http://{}/v1/jobs", addr))
+            .send_json(&json!({ "names": ["pikachu", "raichu"] }))
+            .await
+            .unwrap();
+        let job: BatchJob = response.json().await.unwrap();
+
+        let events = read_socket_events(&format!("ws://{}/v1/jobs/{}/ws", addr, job.id)).await;
+
+        assert_eq!(events.iter().map(|(sequence, _)| *sequence).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(events[2].1.kind(), "summary");
+        assert_eq!(registry.get(&job.id).unwrap().state, BatchJobState::Completed);
+
+        let events = read_socket_events(&format!("ws://{}/v1/jobs/{}/ws?from=2", addr, job.id)).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, 2);
+        assert_eq!(events[0].1.kind(), "summary");
+
+        server.stop(true).await;
+    });
+}
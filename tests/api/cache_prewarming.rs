@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::{App, test, web};
+use actix_web::http::StatusCode;
+use actix_web::test::read_body_json;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::method;
+
+use shakespearemon::admin_service::get_prewarm_progress;
+use shakespearemon::cache::in_memory_translation_cache::InMemoryTranslationCache;
+use shakespearemon::cache::translation_cache::TranslationCache;
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::poke::species_source::SpeciesSource;
+use shakespearemon::prewarm::cache_prewarmer::CachePrewarmer;
+use shakespearemon::prewarm::prewarm_progress::{PrewarmProgress, PrewarmState};
+use shakespearemon::quota::quota_translator::QuotaTranslator;
+use shakespearemon::quota::translation_quota::TranslationQuota;
+use shakespearemon::shakespeare::shakespeare_client::ShakespeareClient;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::shakespeare::translator::Translator;
+use shakespearemon::translation_pipeline::TranslationPipeline;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{get_settings, get_translator};
+
+async fn mock_translator(mock_server: &MockServer, expected_calls: u64) {
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation))
+        .expect(expected_calls)
+        .mount(mock_server)
+        .await;
+}
+
+fn get_popular_species() -> Arc<dyn SpeciesSource> {
+    Arc::new(InMemorySpeciesSource::new()
+        .with_flavor_text("bulbasaur", "A strange seed was planted on its back at birth.", "en")
+        .with_flavor_text("charmander", "The flame at the tip of its tail makes a sound.", "en")
+        .with_flavor_text("squirtle", "It shelters itself in its shell.", "en")
+        .with_flavor_text("pikachu", "It keeps its tail raised to monitor its surroundings.", "en"))
+}
+
+fn species(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[actix_rt::test]
+async fn serves_prewarmed_species_from_the_cache() {
+    let mock_server = MockServer::start().await;
+    mock_translator(&mock_server, 2).await;
+
+    let species_source = get_popular_species();
+    let translation_cache: Arc<dyn TranslationCache> = Arc::new(InMemoryTranslationCache::new("test", Duration::from_secs(60)));
+    let translation_pipeline = TranslationPipeline::new(Arc::new(ShakespeareClient::new(&mock_server.uri())))
+        .with_translation_cache(translation_cache.clone());
+
+    let cache_prewarmer = CachePrewarmer::new(species_source.clone(), translation_pipeline, species(&["bulbasaur", "pikachu"]));
+    let progress = cache_prewarmer.run().await;
+
+    assert_eq!(progress.state, PrewarmState::Completed);
+    assert_eq!(progress.warmed, 2);
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(web::Data::from(species_source))
+        .app_data(get_translator(&mock_server.uri()))
+        .app_data(web::Data::from(translation_cache))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    for name in &["bulbasaur", "pikachu"] {
+        let req = test::TestRequest::get()
+            .uri(&format!("/pokemon/{}", name)).to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
+
+#[actix_rt::test]
+async fn stops_early_when_the_quota_reaches_the_reserve() {
+    let mock_server = MockServer::start().await;
+    mock_translator(&mock_server, 2).await;
+
+    let translation_quota = Arc::new(TranslationQuota::new(3, 60));
+    let translator: Arc<dyn Translator> = Arc::new(QuotaTranslator::new(Arc::new(ShakespeareClient::new(&mock_server.uri())), translation_quota.clone()));
+
+    let cache_prewarmer = CachePrewarmer::new(get_popular_species(), TranslationPipeline::new(translator), species(&["bulbasaur", "charmander", "squirtle", "pikachu"]))
+        .with_translation_quota(translation_quota.clone(), 1);
+    let progress = cache_prewarmer.run().await;
+
+    assert_eq!(progress.state, PrewarmState::StoppedOnLowQuota);
+    assert_eq!(progress.total, 4);
+    assert_eq!(progress.warmed, 2);
+    assert_eq!(translation_quota.status().hourly_remaining, 1);
+}
+
+#[actix_rt::test]
+async fn counts_failures_and_carries_on() {
+    let mock_server = MockServer::start().await;
+    mock_translator(&mock_server, 1).await;
+
+    let cache_prewarmer = CachePrewarmer::new(get_popular_species(), TranslationPipeline::new(Arc::new(ShakespeareClient::new(&mock_server.uri()))), species(&["missingno", "pikachu"]));
+    let progress = cache_prewarmer.run().await;
+
+    assert_eq!(progress.state, PrewarmState::Completed);
+    assert_eq!(progress.failed, 1);
+    assert_eq!(progress.warmed, 1);
+}
+
+#[actix_rt::test]
+async fn reports_progress_through_the_admin_endpoint() {
+    let mock_server = MockServer::start().await;
+    mock_translator(&mock_server, 1).await;
+
+    let cache_prewarmer = Arc::new(CachePrewarmer::new(get_popular_species(), TranslationPipeline::new(Arc::new(ShakespeareClient::new(&mock_server.uri()))), species(&["pikachu"])));
+
+    let mut app = test::init_service(App::new()
+        .app_data(web::Data::from(cache_prewarmer.clone()))
+        .service(get_prewarm_progress)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/admin/prewarm").to_request();
+    let progress: PrewarmProgress = read_body_json(test::call_service(&mut app, req).await).await;
+    assert_eq!(progress.state, PrewarmState::Idle);
+    assert_eq!(progress.total, 1);
+
+    cache_prewarmer.run().await;
+
+    let req = test::TestRequest::get()
+        .uri("/admin/prewarm").to_request();
+    let progress: PrewarmProgress = read_body_json(test::call_service(&mut app, req).await).await;
+    assert_eq!(progress.state, PrewarmState::Completed);
+    assert_eq!(progress.warmed, 1);
+    assert!(progress.finished_at.is_some());
+}
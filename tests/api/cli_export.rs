@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{MockServer, ResponseTemplate};
+
+use shakespearemon::cli::export::{export_species, ExportRecord, ExportSummary};
+use shakespearemon::poke::poke_client::PokeClient;
+use shakespearemon::poke::poke_species_response::{PokeSpeciesResponse, TextFlavorEntry};
+use shakespearemon::quota::quota_translator::QuotaTranslator;
+use shakespearemon::quota::translation_quota::TranslationQuota;
+use shakespearemon::shakespeare::shakespeare_client::ShakespeareClient;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::translation_pipeline::TranslationPipeline;
+
+use crate::helpers::{mock_poke_client_request, mock_shakespeare_client_request};
+
+async fn mock_species(mock_server: &MockServer, id: u16, name: &str) {
+    let response = PokeSpeciesResponse::new(id, name.to_owned(), vec![TextFlavorEntry::new(format!("{} flavor text", name), "en".to_owned())]);
+
+    mock_poke_client_request(mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(response), format!("/{}", id)).await;
+}
+
+async fn mock_translator(mock_server: &MockServer) {
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+}
+
+fn record(id: u16, name: &str) -> ExportRecord {
+    ExportRecord {
+        id,
+        name: name.to_owned(),
+        description: "translated".to_owned(),
+    }
+}
+
+#[actix_rt::test]
+async fn exports_the_id_range_and_reports_missing_species() {
+    let mock_server = MockServer::start().await;
+    mock_species(&mock_server, 1, "bulbasaur").await;
+    mock_species(&mock_server, 3, "venusaur").await;
+    mock_translator(&mock_server).await;
+
+    let species_source = PokeClient::new(&mock_server.uri());
+    let translation_pipeline = TranslationPipeline::new(Arc::new(ShakespeareClient::new(&mock_server.uri())));
+
+    let (records, summary) = export_species(&species_source, &translation_pipeline, 1, 3).await;
+
+    assert_eq!(records, vec![record(1, "bulbasaur"), record(3, "venusaur")]);
+    assert_eq!(summary, ExportSummary { failed: vec![2], stopped_on_quota: false });
+}
+
+#[actix_rt::test]
+async fn stops_the_export_once_the_quota_is_spent() {
+    let mock_server = MockServer::start().await;
+    mock_species(&mock_server, 1, "bulbasaur").await;
+    mock_species(&mock_server, 2, "ivysaur").await;
+    mock_translator(&mock_server).await;
+
+    let species_source = PokeClient::new(&mock_server.uri());
+    let translator = QuotaTranslator::new(Arc::new(ShakespeareClient::new(&mock_server.uri())), Arc::new(TranslationQuota::new(1, 60)));
+    let translation_pipeline = TranslationPipeline::new(Arc::new(translator));
+
+    let (records, summary) = export_species(&species_source, &translation_pipeline, 1, 2).await;
+
+    assert_eq!(records, vec![record(1, "bulbasaur")]);
+    assert!(summary.stopped_on_quota);
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::{App, test};
+use actix_web::dev::Service;
+use actix_web::http::StatusCode;
+use futures::future::join_all;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::{method, path};
+
+use shakespearemon::poke::coalescing_species_source::CoalescingSpeciesSource;
+use shakespearemon::poke::poke_client::PokeClient;
+use shakespearemon::shakespeare::coalescing_translator::CoalescingTranslator;
+use shakespearemon::shakespeare::shakespeare_client::ShakespeareClient;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, generate_poke_species_response, get_settings};
+
+#[actix_rt::test]
+async fn concurrent_requests_share_one_poke_api_and_translator_call() {
+    let mock_server = MockServer::start().await;
+
+    let poke_species_response = generate_poke_species_response("en".to_owned());
+    Mock::given(method("GET"))
+        .and(path("/pikachu"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(poke_species_response).set_delay(Duration::from_millis(200)))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("Flavor text"), String::from("shakespeare"));
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation).set_delay(Duration::from_millis(200)))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let species_source = CoalescingSpeciesSource::new(Arc::new(PokeClient::new(&mock_server.uri())));
+    let translator = CoalescingTranslator::new(Arc::new(ShakespeareClient::new(&mock_server.uri())));
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(species_source))
+        .app_data(as_translator(translator))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let responses = (0..5)
+        .map(|_| app.call(test::TestRequest::get().uri("/pokemon/pikachu").to_request()))
+        .collect::<Vec<_>>();
+
+    for response in join_all(responses).await {
+        assert_eq!(response.unwrap().status(), StatusCode::OK);
+    }
+}
+
+#[actix_rt::test]
+async fn concurrent_requests_for_different_species_share_one_translator_call_for_the_same_text() {
+    let mock_server = MockServer::start().await;
+
+    for name in &["/pikachu", "/raichu"] {
+        let poke_species_response = generate_poke_species_response("en".to_owned());
+        Mock::given(method("GET"))
+            .and(path(*name))
+            .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(poke_species_response))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("Flavor text"), String::from("shakespeare"));
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation).set_delay(Duration::from_millis(300)))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let translator = CoalescingTranslator::new(Arc::new(ShakespeareClient::new(&mock_server.uri())));
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(PokeClient::new(&mock_server.uri())))
+        .app_data(as_translator(translator))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let responses = vec![
+        app.call(test::TestRequest::get().uri("/pokemon/pikachu").to_request()),
+        app.call(test::TestRequest::get().uri("/pokemon/raichu").to_request()),
+    ];
+
+    for response in join_all(responses).await {
+        assert_eq!(response.unwrap().status(), StatusCode::OK);
+    }
+}
+
@@ -0,0 +1,94 @@
+use actix_web::{App, test};
+use actix_web::http::{header, StatusCode};
+use actix_web::test::read_body;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{MockServer, ResponseTemplate};
+
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, get_settings, get_translator, mock_shakespeare_client_request};
+
+async fn get_pokemon_with_accept(name: &str, accept: &str) -> (StatusCode, String, String) {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("Thou & I"), String::from("text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "You & I", "en")))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/pokemon/{}", name))
+        .header(header::ACCEPT, accept)
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    let status = resp.status();
+    let content_type = resp.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap().to_owned();
+    let body = String::from_utf8(read_body(resp).await.to_vec()).unwrap();
+
+    (status, content_type, body)
+}
+
+#[actix_rt::test]
+async fn returns_json_by_default() {
+    let (status, content_type, body) = get_pokemon_with_accept("pikachu", "*/*").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/json; charset=utf-8");
+    assert_eq!(body, r#"{"name":"pikachu","description":"Thou & I"}"#);
+}
+
+#[actix_rt::test]
+async fn returns_plain_text_description() {
+    let (status, content_type, body) = get_pokemon_with_accept("pikachu", "text/plain").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/plain; charset=utf-8");
+    assert_eq!(body, "Thou & I");
+}
+
+#[actix_rt::test]
+async fn returns_html_card() {
+    let (status, content_type, body) = get_pokemon_with_accept("pikachu", "text/html, application/json;q=0.9").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/html; charset=utf-8");
+    assert_eq!(body, r#"<article class="shakespearemon-card"><h2 class="shakespearemon-name">pikachu</h2><p class="shakespearemon-description">Thou &amp; I</p></article>"#);
+}
+
+#[actix_rt::test]
+async fn returns_xml() {
+    let (status, content_type, body) = get_pokemon_with_accept("pikachu", "application/xml").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/xml; charset=utf-8");
+    assert_eq!(body, r#"<?xml version="1.0" encoding="UTF-8"?><shakespearemon><name>pikachu</name><description>Thou &amp; I</description></shakespearemon>"#);
+}
+
+#[actix_rt::test]
+async fn returns_406_for_unsupported_media_type() {
+    let (status, _, _) = get_pokemon_with_accept("pikachu", "image/png").await;
+
+    assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+}
+
+#[actix_rt::test]
+async fn returns_errors_in_the_negotiated_format() {
+    let (status, content_type, body) = get_pokemon_with_accept("ozer", "application/xml").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(content_type, "application/xml; charset=utf-8");
+    assert_eq!(body, r#"<?xml version="1.0" encoding="UTF-8"?><error><message>Pokemon Not Found</message></error>"#);
+
+    let (status, content_type, body) = get_pokemon_with_accept("ozer", "text/plain").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(content_type, "text/plain; charset=utf-8");
+    assert_eq!(body, "Pokemon Not Found");
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use actix_http::Request;
+use actix_web::{App, Error, test};
+use actix_web::body::Body;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::{header, Method, StatusCode};
+
+use shakespearemon::auth::api_key_middleware::ApiKeyAuth;
+use shakespearemon::auth::api_key_registry::ApiKeyRegistry;
+use shakespearemon::cors_policy::cors_middleware;
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::settings::{Auth, Cors};
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, get_settings};
+
+fn get_cors() -> Cors {
+    Cors {
+        allowed_origins: vec!["https://shakespearemon.dev".to_owned(), "https://*.shakespearemon.dev".to_owned()],
+        allowed_methods: vec!["GET".to_owned(), "POST".to_owned()],
+        allowed_headers: vec!["content-type".to_owned(), "x-api-key".to_owned()],
+        exposed_headers: vec!["retry-after".to_owned()],
+        max_age_seconds: Some(600),
+        supports_credentials: false,
+    }
+}
+
+async fn get_app() -> impl Service<Request = Request, Response = ServiceResponse<Body>, Error = Error> {
+    let auth = Auth {
+        admin_key: "admin-secret".to_owned(),
+        keys: Vec::new(),
+        default_hourly_limit: 10,
+        default_daily_limit: 100,
+    };
+
+    test::init_service(App::new()
+        .data(get_settings("http://localhost".to_owned()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(as_translator(LocalTranslator::new(false)))
+        .wrap(ApiKeyAuth::new(Arc::new(ApiKeyRegistry::new(&auth))))
+        .wrap(cors_middleware(&get_cors()))
+        .service(translate_pokemon_description_by_shakespeare)).await
+}
+
+fn preflight(origin: &str, method: &str) -> Request {
+    test::TestRequest::with_uri("/pokemon/pikachu")
+        .method(Method::OPTIONS)
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, method)
+        .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "x-api-key")
+        .to_request()
+}
+
+#[actix_rt::test]
+async fn answers_preflight_for_allowed_origins_without_an_api_key() {
+    let mut app = get_app().await;
+
+    let response = test::call_service(&mut app, preflight("https://shakespearemon.dev", "GET")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(), "https://shakespearemon.dev");
+    assert_eq!(headers.get(header::ACCESS_CONTROL_MAX_AGE).unwrap(), "600");
+    assert!(headers.get(header::ACCESS_CONTROL_ALLOW_METHODS).unwrap().to_str().unwrap().contains("GET"));
+    assert!(headers.get(header::ACCESS_CONTROL_ALLOW_HEADERS).unwrap().to_str().unwrap().contains("x-api-key"));
+}
+
+#[actix_rt::test]
+async fn answers_preflight_for_wildcard_subdomains() {
+    let mut app = get_app().await;
+
+    let response = test::call_service(&mut app, preflight("https://app.shakespearemon.dev", "POST")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(), "https://app.shakespearemon.dev");
+}
+
+#[actix_rt::test]
+async fn rejects_preflight_from_unknown_origins_and_methods() {
+    let mut app = get_app().await;
+
+    let unknown_origin = test::call_service(&mut app, preflight("https://evilshakespearemon.dev", "GET")).await;
+    let unknown_method = test::call_service(&mut app, preflight("https://shakespearemon.dev", "DELETE")).await;
+
+    assert_eq!(unknown_origin.status(), StatusCode::BAD_REQUEST);
+    assert!(unknown_origin.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    assert_eq!(unknown_method.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn adds_cors_headers_to_actual_requests() {
+    let mut app = get_app().await;
+
+    let request = test::TestRequest::get()
+        .uri("/pokemon/pikachu")
+        .header(header::ORIGIN, "https://app.shakespearemon.dev")
+        .header("X-Api-Key", "admin-secret")
+        .to_request();
+    let response = test::call_service(&mut app, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(), "https://app.shakespearemon.dev");
+    assert_eq!(response.headers().get(header::ACCESS_CONTROL_EXPOSE_HEADERS).unwrap(), "retry-after");
+}
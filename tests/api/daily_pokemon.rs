@@ -0,0 +1,141 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::{App, test, web};
+use actix_web::http::{header, StatusCode};
+use actix_web::test::read_body_json;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::{method, path_regex};
+
+use shakespearemon::cache::in_memory_translation_cache::InMemoryTranslationCache;
+use shakespearemon::cache::translation_cache::TranslationCache;
+use shakespearemon::daily::daily_prewarmer::DailyPrewarmer;
+use shakespearemon::daily::daily_selection::{parse_date, select_species_id};
+use shakespearemon::daily_pokemon_service::get_pokemon_of_the_day;
+use shakespearemon::poke::poke_client::PokeClient;
+use shakespearemon::settings::Daily;
+use shakespearemon::shakespeare::shakespeare_client::ShakespeareClient;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::translation_pipeline::TranslationPipeline;
+use shakespearemon::translation_service::{ShakespearemonResponse, translate_pokemon_description_by_shakespeare};
+
+use crate::helpers::{generate_poke_species_response, get_settings, get_species_source, get_translator, mock_poke_client_request};
+
+async fn mock_translator(mock_server: &MockServer, expected_calls: u64) {
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation))
+        .expect(expected_calls)
+        .mount(mock_server)
+        .await;
+}
+
+async fn mock_species_of_the_day(mock_server: &MockServer, date: &str) {
+    let species_id = select_species_id(parse_date(date).unwrap(), Daily::default().seed, Daily::default().species_count);
+    let response = ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(generate_poke_species_response("en".to_owned()));
+
+    mock_poke_client_request(mock_server, response, format!("/{}", species_id)).await;
+}
+
+#[actix_rt::test]
+async fn features_the_species_selected_for_the_requested_date() {
+    let mock_server = MockServer::start().await;
+    mock_species_of_the_day(&mock_server, "2021-03-14").await;
+    mock_translator(&mock_server, 1).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(get_pokemon_of_the_day)
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/daily?date=2021-03-14").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get(header::CACHE_CONTROL).unwrap(), "public, max-age=86400");
+
+    let shakespearemon_response: ShakespearemonResponse = read_body_json(resp).await;
+    assert_eq!(shakespearemon_response.name, "pikachu");
+    assert_eq!(shakespearemon_response.description, "translated");
+}
+
+#[actix_rt::test]
+async fn expires_todays_pick_by_midnight() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex("^/[0-9]+$"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(generate_poke_species_response("en".to_owned())))
+        .mount(&mock_server)
+        .await;
+    mock_translator(&mock_server, 1).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(get_pokemon_of_the_day)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/daily").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let cache_control = resp.headers().get(header::CACHE_CONTROL).unwrap().to_str().unwrap();
+    let max_age: u64 = cache_control.trim_start_matches("public, max-age=").parse().unwrap();
+    assert!(max_age <= 86400);
+}
+
+#[actix_rt::test]
+async fn rejects_a_malformed_date() {
+    let mock_server = MockServer::start().await;
+    mock_translator(&mock_server, 0).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(get_pokemon_of_the_day)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/daily?date=14-03-2021").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn serves_the_prewarmed_translation_from_the_cache() {
+    let mock_server = MockServer::start().await;
+    mock_species_of_the_day(&mock_server, "2021-03-14").await;
+    mock_translator(&mock_server, 1).await;
+
+    let translation_cache: Arc<dyn TranslationCache> = Arc::new(InMemoryTranslationCache::new("test", Duration::from_secs(60)));
+
+    let translation_pipeline = TranslationPipeline::new(Arc::new(ShakespeareClient::new(&mock_server.uri())))
+        .with_translation_cache(translation_cache.clone());
+    let prewarmer = DailyPrewarmer::new(Arc::new(PokeClient::new(&mock_server.uri())), translation_pipeline, &Daily::default());
+    prewarmer.prewarm(parse_date("2021-03-14").unwrap()).await.unwrap();
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .app_data(web::Data::from(translation_cache))
+        .service(get_pokemon_of_the_day)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/daily?date=2021-03-14").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let shakespearemon_response: ShakespearemonResponse = read_body_json(resp).await;
+    assert_eq!(shakespearemon_response.description, "translated");
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::{App, HttpServer, web};
+use actix_web::http::{header, StatusCode};
+use actix_web::rt::Arbiter;
+use actix_web::rt::time::delay_for;
+use async_trait::async_trait;
+use awc::Client;
+use futures::join;
+
+use shakespearemon::jobs::background_tasks::BackgroundTasks;
+use shakespearemon::jobs::batch_job::{BatchJob, BatchJobState};
+use shakespearemon::jobs::batch_job_registry::BatchJobRegistry;
+use shakespearemon::jobs::batch_job_service::{BatchJobRequest, configure_batch_jobs};
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use shakespearemon::shakespeare::translator::Translator;
+use shakespearemon::store::translation_store::TranslationStore;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, get_settings};
+
+struct SlowTranslator;
+
+#[async_trait]
+impl Translator for SlowTranslator {
+    async fn get_shakespearean_translation(&self, _text: &str) -> Result<String, ShakespeareClientException> {
+        delay_for(Duration::from_millis(500)).await;
+        Ok("translated".to_owned())
+    }
+}
+
+#[test]
+fn drains_in_flight_requests_and_keeps_their_store_writes() {
+    actix_web::rt::System::new("graceful-shutdown").block_on(async {
+        let translation_store = Arc::new(TranslationStore::open(":memory:").unwrap());
+        let app_translation_store = web::Data::from(translation_store.clone());
+
+        let server = HttpServer::new(move || {
+            App::new()
+                .data(get_settings("http://localhost".to_owned()))
+                .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+                .app_data(as_translator(SlowTranslator))
+                .app_data(app_translation_store.clone())
+                .service(translate_pokemon_description_by_shakespeare)
+        })
+            .workers(1)
+            .shutdown_timeout(2)
+            .disable_signals()
+            .bind("127.0.0.1:0")
+            .unwrap();
+        let addr = server.addrs()[0];
+        let server = server.run();
+
+        let request = async {
+            Client::default().get(format!("http://{}/pokemon/pikachu", addr)).send().await
+        };
+        let stop = async {
+            delay_for(Duration::from_millis(100)).await;
+            server.stop(true).await;
+        };
+        let (response, _) = join!(request, stop);
+
+        assert_eq!(response.unwrap().status(), StatusCode::OK);
+
+        translation_store.flush().await.unwrap();
+        let records = translation_store.list_translations().await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].translated, "translated");
+    });
+}
+
+#[test]
+fn finishes_running_batch_jobs_after_the_server_stops() {
+    actix_web::rt::System::new("graceful-shutdown").block_on(async {
+        let registry = Arc::new(BatchJobRegistry::new(Duration::from_secs(60)));
+        let app_registry = web::Data::from(registry.clone());
+        let batch_jobs = BackgroundTasks::new().with_arbiter(Arbiter::new());
+        let app_batch_jobs = web::Data::new(batch_jobs.clone());
+
+        let server = HttpServer::new(move || {
+            App::new()
+                .data(get_settings("http://localhost".to_owned()))
+                .app_data(as_species_source(InMemorySpeciesSource::new()
+                    .with_flavor_text("pikachu", "Flavor text", "en")
+                    .with_flavor_text("raichu", "More flavor text", "en")))
+                .app_data(as_translator(SlowTranslator))
+                .app_data(app_registry.clone())
+                .app_data(app_batch_jobs.clone())
+                .service(web::scope("/v1").configure(configure_batch_jobs))
+        })
+            .workers(1)
+            .shutdown_timeout(2)
+            .disable_signals()
+            .bind("127.0.0.1:0")
+            .unwrap();
+        let addr = server.addrs()[0];
+        let server = server.run();
+
+        // Without a kept-alive connection the worker, and anything spawned on it, stops at once.
+        let mut response = Client::default().post(format!("http://{}/v1/jobs", addr))
+            .set_header(header::CONNECTION, "close")
+            .send_json(&BatchJobRequest { names: vec!["pikachu".to_owned(), "raichu".to_owned()] })
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let job: BatchJob = response.json().await.unwrap();
+
+        server.stop(true).await;
+
+        assert!(batch_jobs.wait(Duration::from_secs(5)));
+        let job = registry.get(&job.id).unwrap();
+        assert_eq!(job.state, BatchJobState::Completed);
+        assert_eq!(job.results.len(), 2);
+    });
+}
@@ -0,0 +1,152 @@
+use actix_http::Request;
+use actix_web::{App, Error, test};
+use actix_web::body::Body;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::test::read_body_json;
+use serde_json::{json, Value};
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::{method, path};
+
+use shakespearemon::graphql::graphql_service::execute_graphql;
+use shakespearemon::graphql::schema::build_schema;
+use shakespearemon::poke::poke_species_response::{PokeSpeciesResponse, TextFlavorEntry};
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+
+use crate::helpers::{get_settings, get_species_source, get_translator};
+
+async fn mock_species(mock_server: &MockServer, name: &str, flavor_text: &str, expected_calls: u64) {
+    let poke_species_response = PokeSpeciesResponse::new(25, name.to_owned(), vec![TextFlavorEntry::new(flavor_text.to_owned(), "en".to_owned())]);
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}", name)))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(poke_species_response))
+        .expect(expected_calls)
+        .mount(mock_server)
+        .await;
+}
+
+async fn mock_translator(mock_server: &MockServer, expected_calls: u64) {
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("Flavor text"), String::from("shakespeare"));
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation))
+        .expect(expected_calls)
+        .mount(mock_server)
+        .await;
+}
+
+async fn get_app(mock_server: &MockServer) -> impl Service<Request = Request, Response = ServiceResponse<Body>, Error = Error> {
+    test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .data(build_schema(get_settings(mock_server.uri()).jobs.max_names))
+        .app_data(get_species_source(&mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(execute_graphql)).await
+}
+
+async fn execute_query(mock_server: &MockServer, query: &str) -> Value {
+    let mut app = get_app(mock_server).await;
+
+    let req = test::TestRequest::post()
+        .uri("/graphql")
+        .set_json(&json!({ "query": query }))
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    read_body_json(resp).await
+}
+
+#[test]
+fn resolves_only_the_selected_fields() {
+    actix_web::rt::System::new("graphql").block_on(async {
+        let mock_server = MockServer::start().await;
+        mock_species(&mock_server, "pikachu", "Flavor text", 1).await;
+        mock_translator(&mock_server, 1).await;
+
+        let response = execute_query(&mock_server, "{ pokemon(name: \"pikachu\") { name original translated } }").await;
+
+        assert_eq!(response["data"]["pokemon"], json!({
+            "name": "pikachu",
+            "original": "Flavor text",
+            "translated": "translated",
+        }));
+    });
+}
+
+#[test]
+fn does_not_call_the_translator_unless_translated_is_selected() {
+    actix_web::rt::System::new("graphql").block_on(async {
+        let mock_server = MockServer::start().await;
+        mock_species(&mock_server, "pikachu", "Flavor text", 1).await;
+        mock_translator(&mock_server, 0).await;
+
+        let response = execute_query(&mock_server, "{ pokemon(name: \"pikachu\") { original metadata { isLegendary } } }").await;
+
+        assert_eq!(response["data"]["pokemon"]["original"], "Flavor text");
+        assert_eq!(response["data"]["pokemon"]["metadata"]["isLegendary"], false);
+    });
+}
+
+#[test]
+fn batches_repeated_species_and_shared_descriptions() {
+    actix_web::rt::System::new("graphql").block_on(async {
+        let mock_server = MockServer::start().await;
+        mock_species(&mock_server, "pikachu", "Flavor text", 1).await;
+        mock_species(&mock_server, "raichu", "Flavor text", 1).await;
+        mock_translator(&mock_server, 1).await;
+
+        let query = "{ pokemons(names: [\"pikachu\", \"raichu\", \"pikachu\"]) { name translated } }";
+        let response = execute_query(&mock_server, query).await;
+
+        let pokemons = response["data"]["pokemons"].as_array().unwrap();
+        assert_eq!(pokemons.len(), 3);
+        assert!(pokemons.iter().all(|pokemon| pokemon["translated"] == "translated"));
+    });
+}
+
+#[test]
+fn reports_unknown_species_with_the_rest_error_code() {
+    actix_web::rt::System::new("graphql").block_on(async {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(SurfStatusCode::NotFound))
+            .mount(&mock_server)
+            .await;
+
+        let response = execute_query(&mock_server, "{ pokemon(name: \"missingno\") { name } }").await;
+
+        assert_eq!(response["data"]["pokemon"], Value::Null);
+        assert_eq!(response["errors"][0]["extensions"]["code"], "pokemon_not_found");
+    });
+}
+
+#[test]
+fn rejects_unsupported_styles() {
+    actix_web::rt::System::new("graphql").block_on(async {
+        let mock_server = MockServer::start().await;
+        mock_species(&mock_server, "pikachu", "Flavor text", 1).await;
+        mock_translator(&mock_server, 0).await;
+
+        let response = execute_query(&mock_server, "{ pokemon(name: \"pikachu\") { translated(style: \"pirate\") } }").await;
+
+        assert_eq!(response["errors"][0]["extensions"]["code"], "unsupported_style");
+    });
+}
+
+#[test]
+fn rejects_more_names_than_a_batch_job_takes() {
+    actix_web::rt::System::new("graphql").block_on(async {
+        let mock_server = MockServer::start().await;
+        mock_translator(&mock_server, 0).await;
+
+        let names = vec!["\"pikachu\""; 1026].join(", ");
+        let response = execute_query(&mock_server, &format!("{{ pokemons(names: [{}]) {{ name }} }}", names)).await;
+
+        assert_eq!(response["data"], Value::Null);
+        assert_eq!(response["errors"][0]["extensions"]["code"], "too_many_names");
+    });
+}
@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use futures::future::pending;
+use futures::StreamExt;
+use tokio::net::TcpListener;
+use tonic::{Code, Request};
+use tonic::transport::Channel;
+
+use shakespearemon::auth::api_key_registry::ApiKeyRegistry;
+use shakespearemon::grpc::grpc_guard::GrpcGuard;
+use shakespearemon::grpc::grpc_server::serve_grpc;
+use shakespearemon::grpc::grpc_service::ShakespearemonGrpcService;
+use shakespearemon::grpc::grpc_status::ERROR_CODE_METADATA;
+use shakespearemon::grpc::proto::{TranslateBatchRequest, TranslatePokemonRequest, TranslateRequest};
+use shakespearemon::grpc::proto::batch_item::Result as BatchResult;
+use shakespearemon::grpc::proto::shakespearemon_client::ShakespearemonClient;
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::quota::quota_translator::QuotaTranslator;
+use shakespearemon::rate_limit::ip_rate_limiter::IpRateLimiter;
+use shakespearemon::settings::{Auth, ConfiguredApiKey};
+use shakespearemon::quota::translation_quota::TranslationQuota;
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::shakespeare::translator::Translator;
+use shakespearemon::translation_pipeline::TranslationPipeline;
+
+fn get_species_source() -> InMemorySpeciesSource {
+    InMemorySpeciesSource::new()
+        .with_flavor_text("pikachu", "You are my friend", "en")
+        .with_flavor_text("raichu", "Are you my friend", "en")
+        .with_species("missingno")
+}
+
+const MAX_NAMES: usize = 10;
+
+fn get_service(translator: Arc<dyn Translator>, max_names: usize) -> ShakespearemonGrpcService {
+    ShakespearemonGrpcService::new(Arc::new(get_species_source()), TranslationPipeline::new(translator), 1000, max_names)
+}
+
+async fn get_client(translator: Arc<dyn Translator>) -> ShakespearemonClient<Channel> {
+    serve(get_service(translator, MAX_NAMES)).await
+}
+
+async fn get_guarded_client(translator: Arc<dyn Translator>, guard: GrpcGuard) -> ShakespearemonClient<Channel> {
+    serve(get_service(translator, MAX_NAMES).with_guard(guard)).await
+}
+
+/// Serves the gRPC API on a loopback port within the test and connects a client to it.
+async fn serve(service: ShakespearemonGrpcService) -> ShakespearemonClient<Channel> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    actix_rt::spawn(async move {
+        serve_grpc(listener, service, pending()).await.unwrap();
+    });
+
+    ShakespearemonClient::connect(format!("http://{}", addr)).await.unwrap()
+}
+
+#[actix_rt::test]
+async fn translates_text() {
+    let mut client = get_client(Arc::new(LocalTranslator::new(false))).await;
+
+    let response = client.translate(TranslateRequest {
+        text: "You are my friend".to_owned(),
+        style: String::new(),
+    }).await.unwrap().into_inner();
+
+    assert_eq!(response.style, "shakespeare");
+    assert_eq!(response.translated, "Thou art my friend");
+}
+
+#[actix_rt::test]
+async fn rejects_empty_text_as_an_invalid_argument() {
+    let mut client = get_client(Arc::new(LocalTranslator::new(false))).await;
+
+    let status = client.translate(TranslateRequest {
+        text: "   ".to_owned(),
+        style: String::new(),
+    }).await.unwrap_err();
+
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.metadata().get(ERROR_CODE_METADATA).unwrap(), "empty_text");
+}
+
+#[actix_rt::test]
+async fn translates_a_pokemon() {
+    let mut client = get_client(Arc::new(LocalTranslator::new(false))).await;
+
+    let response = client.translate_pokemon(TranslatePokemonRequest {
+        name: "pikachu".to_owned(),
+    }).await.unwrap().into_inner();
+
+    assert_eq!(response.name, "pikachu");
+    assert_eq!(response.description, "Thou art my friend");
+}
+
+#[actix_rt::test]
+async fn maps_unknown_pokemon_to_not_found() {
+    let mut client = get_client(Arc::new(LocalTranslator::new(false))).await;
+
+    let status = client.translate_pokemon(TranslatePokemonRequest {
+        name: "mew".to_owned(),
+    }).await.unwrap_err();
+
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(status.metadata().get(ERROR_CODE_METADATA).unwrap(), "pokemon_not_found");
+}
+
+#[actix_rt::test]
+async fn streams_batch_items_in_order_with_per_species_errors() {
+    let mut client = get_client(Arc::new(LocalTranslator::new(false))).await;
+
+    let stream = client.translate_batch(TranslateBatchRequest {
+        names: vec!["pikachu".to_owned(), "missingno".to_owned(), "raichu".to_owned()],
+    }).await.unwrap().into_inner();
+
+    let items: Vec<_> = stream.map(|item| item.unwrap()).collect().await;
+
+    let names: Vec<_> = items.iter().map(|item| item.name.as_str()).collect();
+    assert_eq!(names, vec!["pikachu", "missingno", "raichu"]);
+    assert_eq!(items[0].result, Some(BatchResult::Description("Thou art my friend".to_owned())));
+    assert!(matches!(&items[1].result, Some(BatchResult::Error(error)) if error.code == "description_not_found"));
+    assert_eq!(items[2].result, Some(BatchResult::Description("Art thou my friend".to_owned())));
+}
+
+#[actix_rt::test]
+async fn ends_the_batch_stream_when_the_quota_is_spent() {
+    let translation_quota = Arc::new(TranslationQuota::new(1, 10));
+    let mut client = get_client(Arc::new(QuotaTranslator::new(Arc::new(LocalTranslator::new(false)), translation_quota))).await;
+
+    let mut stream = client.translate_batch(TranslateBatchRequest {
+        names: vec!["pikachu".to_owned(), "raichu".to_owned()],
+    }).await.unwrap().into_inner();
+
+    assert!(stream.next().await.unwrap().is_ok());
+    let item = stream.next().await.unwrap().unwrap();
+    assert_eq!(item.name, "raichu");
+    assert!(matches!(&item.result, Some(BatchResult::Error(error)) if error.code == "quota_exhausted"));
+    assert!(stream.next().await.is_none());
+}
+
+#[actix_rt::test]
+async fn rejects_batches_with_too_many_names() {
+    let mut client = serve(get_service(Arc::new(LocalTranslator::new(false)), 2)).await;
+
+    let status = client.translate_batch(TranslateBatchRequest {
+        names: vec!["pikachu".to_owned(); 3],
+    }).await.unwrap_err();
+
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.metadata().get(ERROR_CODE_METADATA).unwrap(), "too_many_names");
+}
+
+#[actix_rt::test]
+async fn requires_an_api_key_when_authentication_is_configured() {
+    let auth = Auth {
+        admin_key: "admin-secret".to_owned(),
+        keys: vec![ConfiguredApiKey {
+            key: "backend-secret".to_owned(),
+            name: "backend".to_owned(),
+            hourly_limit: 10,
+            daily_limit: 100,
+        }],
+        default_hourly_limit: 10,
+        default_daily_limit: 100,
+    };
+    let guard = GrpcGuard::default().with_api_key_registry(Arc::new(ApiKeyRegistry::new(&auth)));
+    let mut client = get_guarded_client(Arc::new(LocalTranslator::new(false)), guard).await;
+
+    let status = client.translate_pokemon(TranslatePokemonRequest {
+        name: "pikachu".to_owned(),
+    }).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert_eq!(status.metadata().get(ERROR_CODE_METADATA).unwrap(), "missing_api_key");
+
+    let mut request = Request::new(TranslatePokemonRequest {
+        name: "pikachu".to_owned(),
+    });
+    request.metadata_mut().insert("x-api-key", "backend-secret".parse().unwrap());
+    let response = client.translate_pokemon(request).await.unwrap().into_inner();
+    assert_eq!(response.description, "Thou art my friend");
+}
+
+#[actix_rt::test]
+async fn charges_batches_one_request_per_name() {
+    let auth = Auth {
+        admin_key: "admin-secret".to_owned(),
+        keys: vec![ConfiguredApiKey {
+            key: "backend-secret".to_owned(),
+            name: "backend".to_owned(),
+            hourly_limit: 2,
+            daily_limit: 100,
+        }],
+        default_hourly_limit: 10,
+        default_daily_limit: 100,
+    };
+    let guard = GrpcGuard::default().with_api_key_registry(Arc::new(ApiKeyRegistry::new(&auth)));
+    let mut client = get_guarded_client(Arc::new(LocalTranslator::new(false)), guard).await;
+    let batch = |names: &[&str]| {
+        let mut request = Request::new(TranslateBatchRequest {
+            names: names.iter().map(|name| name.to_string()).collect(),
+        });
+        request.metadata_mut().insert("x-api-key", "backend-secret".parse().unwrap());
+        request
+    };
+
+    let status = client.translate_batch(batch(&["pikachu", "raichu", "pikachu"])).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    assert_eq!(status.metadata().get(ERROR_CODE_METADATA).unwrap(), "rate_limited");
+
+    let items: Vec<_> = client.translate_batch(batch(&["pikachu", "raichu"])).await.unwrap().into_inner().collect().await;
+    assert_eq!(items.len(), 2);
+
+    let status = client.translate_batch(batch(&["pikachu"])).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+}
+
+#[actix_rt::test]
+async fn limits_calls_per_peer_address() {
+    let guard = GrpcGuard::default().with_ip_rate_limiter(Arc::new(IpRateLimiter::new(1, 1)));
+    let mut client = get_guarded_client(Arc::new(LocalTranslator::new(false)), guard).await;
+
+    let translate = || TranslatePokemonRequest {
+        name: "pikachu".to_owned(),
+    };
+    assert!(client.translate_pokemon(translate()).await.is_ok());
+
+    let status = client.translate_pokemon(translate()).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    assert_eq!(status.metadata().get(ERROR_CODE_METADATA).unwrap(), "rate_limited");
+}
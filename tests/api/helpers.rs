@@ -1,9 +1,21 @@
+use std::sync::Arc;
+
+use actix_web::web;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use wiremock::{Mock, MockServer, ResponseTemplate};
 use wiremock::matchers::{method, path};
 
+use shakespearemon::poke::poke_client::PokeClient;
 use shakespearemon::poke::poke_species_response::{PokeSpeciesResponse, TextFlavorEntry};
-use shakespearemon::settings::Application;
+use shakespearemon::poke::pokemon_client::PokemonClient;
+use shakespearemon::poke::pokemon_source::PokemonSource;
+use shakespearemon::poke::resource_client::ResourceClient;
+use shakespearemon::poke::resource_source::ResourceSource;
+use shakespearemon::poke::species_source::SpeciesSource;
+use shakespearemon::settings::{Application, Daily, Funtranslations, Jobs, Server, Settings, TranslatorEngine};
+use shakespearemon::shakespeare::shakespeare_client::ShakespeareClient;
+use shakespearemon::shakespeare::translator::Translator;
 
 #[derive(Serialize)]
 pub struct UndefinedResponse {
@@ -23,21 +35,75 @@ pub fn get_application(uri: String) -> Application {
         host: "127.0.0.1".to_owned(),
         port: 8080,
         poke_api_base_url: uri.clone(),
+        poke_api_pokemon_base_url: format!("{}/pokemon", uri),
+        poke_api_ability_base_url: format!("{}/ability", uri),
+        poke_api_move_base_url: format!("{}/move", uri),
         shakespeare_translator_api_base_url: uri,
+        translation_store_path: None,
+        translator_engine: TranslatorEngine::Remote,
+        local_translator_flourishes: false,
+        max_text_length: 1000,
+        cache_max_age_seconds: 86400,
+    }
+}
+
+pub fn get_settings(uri: String) -> Settings {
+    Settings {
+        application: get_application(uri),
+        server: Server::default(),
+        daily: Daily::default(),
+        funtranslations: Funtranslations::default(),
+        jobs: Jobs::default(),
+        cache: None,
+        quota: None,
+        prewarm: None,
+        auth: None,
+        rate_limit: None,
+        tls: None,
+        cors: None,
+        grpc: None,
     }
 }
 
+pub fn get_species_source(uri: &str) -> web::Data<dyn SpeciesSource> {
+    as_species_source(PokeClient::new(uri))
+}
+
+pub fn as_species_source<S: SpeciesSource + 'static>(species_source: S) -> web::Data<dyn SpeciesSource> {
+    let species_source: Arc<dyn SpeciesSource> = Arc::new(species_source);
+    web::Data::from(species_source)
+}
+
+pub fn get_pokemon_source(uri: &str) -> web::Data<dyn PokemonSource> {
+    let pokemon_source: Arc<dyn PokemonSource> = Arc::new(PokemonClient::new(uri));
+    web::Data::from(pokemon_source)
+}
+
+pub fn get_resource_source<R: DeserializeOwned + Send + 'static>(uri: &str) -> web::Data<dyn ResourceSource<R>> {
+    let resource_source: Arc<dyn ResourceSource<R>> = Arc::new(ResourceClient::new(uri));
+    web::Data::from(resource_source)
+}
+
+pub fn get_translator(uri: &str) -> web::Data<dyn Translator> {
+    as_translator(ShakespeareClient::new(uri))
+}
+
+pub fn as_translator<T: Translator + 'static>(translator: T) -> web::Data<dyn Translator> {
+    let translator: Arc<dyn Translator> = Arc::new(translator);
+    web::Data::from(translator)
+}
+
 pub async fn mock_poke_client_request(mock_server: &MockServer, responder: ResponseTemplate, pathname: String) {
     Mock::given(method("GET"))
         .and(path(pathname))
         .respond_with(responder)
-        .mount(&mock_server)
+        .mount(mock_server)
         .await;
 }
 
 pub async fn mock_shakespeare_client_request(mock_server: &MockServer, responder: ResponseTemplate) {
     Mock::given(method("POST"))
         .respond_with(responder)
-        .mount(&mock_server)
+        .mount(mock_server)
         .await;
 }
\ No newline at end of file
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use actix_web::{App, test};
+use actix_web::http::{header, StatusCode};
+use actix_web::test::read_body;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::method;
+
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::settings::TranslatorEngine;
+use shakespearemon::shakespeare::fallback_translator::FallbackTranslator;
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, get_settings, get_translator, mock_shakespeare_client_request};
+
+#[actix_rt::test]
+async fn sets_etag_and_cache_control_and_answers_conditional_get_with_304() {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let mut settings = get_settings(mock_server.uri());
+    settings.application.cache_max_age_seconds = 600;
+
+    let mut app = test::init_service(App::new()
+        .data(settings)
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get(header::CACHE_CONTROL).unwrap(), "public, max-age=600");
+
+    let etag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_owned();
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu")
+        .header(header::IF_NONE_MATCH, etag.as_str())
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(resp.headers().get(header::ETAG).unwrap().to_str().unwrap(), etag);
+    assert!(read_body(resp).await.is_empty());
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu")
+        .header(header::IF_NONE_MATCH, "\"stale\"")
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[actix_rt::test]
+async fn uses_a_different_etag_for_each_representation() {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    let json_etag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_owned();
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu")
+        .header(header::ACCEPT, "text/plain")
+        .header(header::IF_NONE_MATCH, json_etag.as_str())
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_ne!(resp.headers().get(header::ETAG).unwrap().to_str().unwrap(), json_etag);
+}
+
+#[actix_rt::test]
+async fn answers_conditional_get_without_calling_the_translator() {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    let etag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_owned();
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu")
+        .header(header::IF_NONE_MATCH, etag.as_str())
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+}
+
+#[actix_rt::test]
+async fn neither_caches_nor_validates_stand_in_translations() {
+    let mock_server = MockServer::start().await;
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::InternalServerError)).await;
+
+    let remote_translator = Arc::clone(&get_translator(&mock_server.uri()));
+    let mut settings = get_settings(mock_server.uri());
+    settings.application.translator_engine = TranslatorEngine::RemoteWithLocalFallback;
+
+    let mut app = test::init_service(App::new()
+        .data(settings)
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "You are my friend", "en")))
+        .app_data(as_translator(FallbackTranslator::new(remote_translator, Arc::new(LocalTranslator::new(false)))))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get(header::CACHE_CONTROL).unwrap(), "no-store");
+    assert!(resp.headers().get(header::ETAG).is_none());
+}
+
+#[actix_rt::test]
+async fn uses_a_different_etag_for_each_translator_engine() {
+    let mut etags = Vec::new();
+
+    for translator_engine in [TranslatorEngine::Remote, TranslatorEngine::Local] {
+        let mut settings = get_settings("http://localhost".to_owned());
+        settings.application.translator_engine = translator_engine;
+
+        let mut app = test::init_service(App::new()
+            .data(settings)
+            .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+            .app_data(as_translator(LocalTranslator::new(false)))
+            .service(translate_pokemon_description_by_shakespeare)).await;
+
+        let req = test::TestRequest::get()
+            .uri("/pokemon/pikachu").to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+        etags.push(resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_owned());
+    }
+
+    assert_ne!(etags[0], etags[1]);
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::{App, test};
+use actix_web::http::StatusCode;
+use actix_web::test::read_body_json;
+use serde::Deserialize;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{MockServer, ResponseTemplate};
+
+use shakespearemon::cache::in_memory_translation_cache::InMemoryTranslationCache;
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::shakespeare::fallback_translator::FallbackTranslator;
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::shakespeare::shakespeare_client::ShakespeareClient;
+use shakespearemon::store::translation_store::TranslationStore;
+use shakespearemon::translation_pipeline::{SHAKESPEARE_STYLE, TranslationPipeline};
+use shakespearemon::translation_service::ShakespearemonResponse;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, get_settings, mock_shakespeare_client_request};
+
+#[derive(Deserialize)]
+struct GoldenCase {
+    text: String,
+    flourishes: bool,
+    translated: String,
+}
+
+fn golden_cases() -> Vec<GoldenCase> {
+    serde_json::from_str(include_str!("../golden/local_translator.json")).unwrap()
+}
+
+#[test]
+fn local_translator_matches_golden_translations() {
+    for case in golden_cases() {
+        let translator = LocalTranslator::new(case.flourishes);
+
+        assert_eq!(translator.translate(&case.text), case.translated, "translating {:?}", case.text);
+    }
+}
+
+
+#[actix_rt::test]
+async fn falls_back_to_local_translation_if_remote_translator_fails() {
+    let mock_server = MockServer::start().await;
+
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::TooManyRequests)).await;
+
+    let species_source = InMemorySpeciesSource::new()
+        .with_flavor_text("pikachu", "It keeps its tail raised.", "en");
+
+    let translator = FallbackTranslator::new(Arc::new(ShakespeareClient::new(&mock_server.uri())), Arc::new(LocalTranslator::new(false)));
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(species_source))
+        .app_data(as_translator(translator))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let shakespearemon_response: ShakespearemonResponse = read_body_json(resp).await;
+    assert_eq!(shakespearemon_response.description, "It keepeth its tail raised.");
+}
+
+#[actix_rt::test]
+async fn neither_caches_nor_stores_fallback_translations() {
+    let mock_server = MockServer::start().await;
+
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::TooManyRequests)).await;
+
+    let translator = FallbackTranslator::new(Arc::new(ShakespeareClient::new(&mock_server.uri())), Arc::new(LocalTranslator::new(false)));
+    let translation_cache = Arc::new(InMemoryTranslationCache::new("test", Duration::from_secs(60)));
+    let translation_store = Arc::new(TranslationStore::open(":memory:").unwrap());
+    let translation_pipeline = TranslationPipeline::new(Arc::new(translator))
+        .with_translation_cache(translation_cache.clone())
+        .with_translation_store(translation_store.clone());
+
+    let translation = translation_pipeline.translate("It keeps its tail raised.").await.unwrap();
+    translation_store.flush().await.unwrap();
+
+    assert_eq!(translation, "It keepeth its tail raised.");
+    assert!(translation_cache.is_empty());
+    assert_eq!(translation_store.get_translation("It keeps its tail raised.", SHAKESPEARE_STYLE).await.unwrap(), None);
+}
@@ -1,2 +1,25 @@
 mod helpers;
-mod api;
\ No newline at end of file
+mod api;
+mod api_versioning;
+mod api_keys;
+mod batch_jobs;
+mod cache_prewarming;
+mod cli_export;
+mod coalescing;
+mod daily_pokemon;
+mod graceful_shutdown;
+mod content_negotiation;
+mod cors;
+mod graphql;
+mod grpc;
+mod http_caching;
+mod local_translator;
+mod pokemon_media;
+mod quota;
+mod rate_limiting;
+mod resource_translation;
+mod species_metadata;
+mod text_translation;
+mod tls;
+mod translation_cache;
+mod translation_store;
@@ -0,0 +1,113 @@
+use actix_web::{App, test};
+use actix_web::http::StatusCode;
+use actix_web::test::read_body_json;
+use serde_json::json;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::{method, path};
+
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::translation_service::ShakespearemonResponse;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, generate_poke_species_response, get_pokemon_source, get_settings, get_translator, mock_shakespeare_client_request};
+
+fn generate_pikachu_pokemon_response() -> serde_json::Value {
+    json!({
+        "id": 25,
+        "name": "pikachu",
+        "sprites": {
+            "front_default": "https://img.example/25.png",
+            "back_default": "https://img.example/back/25.png",
+            "front_shiny": null,
+            "back_shiny": null,
+            "other": {
+                "official-artwork": { "front_default": "https://img.example/artwork/25.png" }
+            }
+        },
+        "types": [{ "slot": 1, "type": { "name": "electric", "url": "" } }],
+        "abilities": [
+            { "slot": 3, "is_hidden": true, "ability": { "name": "lightning-rod", "url": "" } },
+            { "slot": 1, "is_hidden": false, "ability": { "name": "static", "url": "" } }
+        ]
+    })
+}
+
+async fn mock_pokemon_request(mock_server: &MockServer, name_or_id: &str, response: ResponseTemplate, expected_calls: u64) {
+    Mock::given(method("GET"))
+        .and(path(format!("/pokemon/{}", name_or_id)))
+        .respond_with(response)
+        .expect(expected_calls)
+        .mount(mock_server)
+        .await;
+}
+
+async fn get_pokemon(mock_server: &MockServer, uri: &str) -> ShakespearemonResponse {
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_species_response(generate_poke_species_response("en".to_owned()))))
+        .app_data(get_pokemon_source(&format!("{}/pokemon", mock_server.uri())))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri(uri).to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    read_body_json(resp).await
+}
+
+#[actix_rt::test]
+async fn does_not_fetch_media_unless_requested() {
+    let mock_server = MockServer::start().await;
+    mock_pokemon_request(&mock_server, "pikachu", ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(generate_pikachu_pokemon_response()), 0).await;
+
+    let shakespearemon_response = get_pokemon(&mock_server, "/pokemon/pikachu").await;
+
+    assert!(shakespearemon_response.media.is_none());
+}
+
+#[actix_rt::test]
+async fn includes_sprites_artwork_types_and_abilities_when_requested() {
+    let mock_server = MockServer::start().await;
+    mock_pokemon_request(&mock_server, "pikachu", ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(generate_pikachu_pokemon_response()), 1).await;
+
+    let shakespearemon_response = get_pokemon(&mock_server, "/pokemon/pikachu?include=metadata,media").await;
+
+    assert_eq!(shakespearemon_response.description, "translated");
+    let media = shakespearemon_response.media.unwrap();
+    assert_eq!(media.sprites.front_default, Some("https://img.example/25.png".to_owned()));
+    assert_eq!(media.sprites.back_default, Some("https://img.example/back/25.png".to_owned()));
+    assert_eq!(media.sprites.front_shiny, None);
+    assert_eq!(media.official_artwork, Some("https://img.example/artwork/25.png".to_owned()));
+    assert_eq!(media.types, vec!["electric".to_owned()]);
+    assert_eq!(media.abilities, vec!["static".to_owned(), "lightning-rod".to_owned()]);
+}
+
+#[actix_rt::test]
+async fn omits_media_when_the_pokemon_endpoint_fails() {
+    let mock_server = MockServer::start().await;
+    mock_pokemon_request(&mock_server, "pikachu", ResponseTemplate::new(SurfStatusCode::InternalServerError), 1).await;
+
+    let shakespearemon_response = get_pokemon(&mock_server, "/pokemon/pikachu?include=media").await;
+
+    assert_eq!(shakespearemon_response.description, "translated");
+    assert!(shakespearemon_response.media.is_none());
+}
+
+#[actix_rt::test]
+async fn falls_back_to_the_species_id_when_no_pokemon_shares_its_name() {
+    let mock_server = MockServer::start().await;
+    mock_pokemon_request(&mock_server, "pikachu", ResponseTemplate::new(SurfStatusCode::NotFound), 1).await;
+    mock_pokemon_request(&mock_server, "16", ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(generate_pikachu_pokemon_response()), 1).await;
+
+    let shakespearemon_response = get_pokemon(&mock_server, "/pokemon/pikachu?include=media").await;
+
+    assert_eq!(shakespearemon_response.media.unwrap().types, vec!["electric".to_owned()]);
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use actix_web::http::{header, StatusCode};
+use actix_web::test::read_body_json;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::method;
+
+use shakespearemon::admin_service::get_translation_quota;
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::quota::quota_translator::QuotaTranslator;
+use shakespearemon::quota::translation_quota::{QuotaStatus, TranslationQuota};
+use shakespearemon::shakespeare::shakespeare_client::ShakespeareClient;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, get_settings};
+
+#[actix_rt::test]
+async fn returns_429_without_calling_the_translator_once_quota_is_exhausted() {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let species_source = InMemorySpeciesSource::new()
+        .with_flavor_text("pikachu", "Pikachu text", "en")
+        .with_flavor_text("raichu", "Raichu text", "en");
+
+    let translation_quota = web::Data::new(TranslationQuota::new(1, 10));
+    let translator = QuotaTranslator::new(Arc::new(ShakespeareClient::new(&mock_server.uri())), translation_quota.clone().into_inner());
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(species_source))
+        .app_data(as_translator(translator))
+        .app_data(translation_quota)
+        .service(translate_pokemon_description_by_shakespeare)
+        .service(get_translation_quota)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/raichu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(resp.headers().contains_key(header::RETRY_AFTER));
+
+    let req = test::TestRequest::get()
+        .uri("/admin/quota").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let quota_status: QuotaStatus = read_body_json(resp).await;
+    assert_eq!(quota_status.hourly_limit, 1);
+    assert_eq!(quota_status.hourly_remaining, 0);
+    assert_eq!(quota_status.daily_remaining, 9);
+}
@@ -0,0 +1,129 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use actix_http::Request;
+use actix_web::{App, Error, test};
+use actix_web::test::read_body_json;
+use actix_web::body::Body;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::{header, StatusCode};
+
+use shakespearemon::error_response::ErrorResponse;
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::rate_limit::ip_network::IpNetwork;
+use shakespearemon::rate_limit::ip_rate_limiter::IpRateLimiter;
+use shakespearemon::rate_limit::rate_limit_middleware::IpRateLimit;
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, get_settings};
+
+const BURST: usize = 3;
+
+async fn get_app() -> impl Service<Request = Request, Response = ServiceResponse<Body>, Error = Error> {
+    let ip_rate_limiter = IpRateLimiter::new(1, BURST as u32)
+        .with_trusted_proxies(vec![IpNetwork::parse("10.0.0.0/8").unwrap()])
+        .with_allowlist(vec![IpNetwork::parse("192.168.0.0/16").unwrap()]);
+
+    test::init_service(App::new()
+        .data(get_settings("http://localhost".to_owned()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(as_translator(LocalTranslator::new(false)))
+        .wrap(IpRateLimit::new(Arc::new(ip_rate_limiter)))
+        .service(translate_pokemon_description_by_shakespeare)).await
+}
+
+fn get_pokemon(peer: &str, forwarded_for: Option<&str>) -> Request {
+    let request = test::TestRequest::get()
+        .uri("/pokemon/pikachu")
+        .peer_addr(peer.parse::<SocketAddr>().unwrap());
+
+    match forwarded_for {
+        Some(forwarded_for) => request.header("X-Forwarded-For", forwarded_for).to_request(),
+        None => request.to_request()
+    }
+}
+
+async fn burst<S>(app: &mut S, peer: &str, forwarded_for: Option<&str>) -> Vec<StatusCode>
+    where S: Service<Request = Request, Response = ServiceResponse<Body>, Error = Error>
+{
+    let mut statuses = Vec::new();
+    for _ in 0..=BURST {
+        statuses.push(test::call_service(app, get_pokemon(peer, forwarded_for)).await.status());
+    }
+    statuses
+}
+
+#[actix_rt::test]
+async fn should_limit_a_client_bursting_past_its_bucket() {
+    let mut app = get_app().await;
+
+    let statuses = burst(&mut app, "203.0.113.9:4000", None).await;
+    let response = test::call_service(&mut app, get_pokemon("203.0.113.9:4001", None)).await;
+
+    assert_eq!(statuses[..BURST], [StatusCode::OK; BURST]);
+    assert_eq!(statuses[BURST], StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "60");
+    let body: ErrorResponse = read_body_json(response).await;
+    assert_eq!(body.error, "rate_limited");
+}
+
+#[actix_rt::test]
+async fn should_keep_separate_buckets_per_client() {
+    let mut app = get_app().await;
+
+    burst(&mut app, "203.0.113.9:4000", None).await;
+    let response = test::call_service(&mut app, get_pokemon("198.51.100.1:4000", None)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[actix_rt::test]
+async fn should_never_limit_allowlisted_clients() {
+    let mut app = get_app().await;
+
+    let statuses = burst(&mut app, "192.168.1.20:4000", None).await;
+
+    assert!(statuses.iter().all(|status| *status == StatusCode::OK));
+}
+
+#[actix_rt::test]
+async fn should_limit_clients_behind_a_trusted_proxy_individually() {
+    let mut app = get_app().await;
+
+    let statuses = burst(&mut app, "10.0.0.2:4000", Some("203.0.113.9")).await;
+    let response = test::call_service(&mut app, get_pokemon("10.0.0.2:4000", Some("198.51.100.1"))).await;
+
+    assert_eq!(statuses[BURST], StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[actix_rt::test]
+async fn should_ignore_forwarded_for_from_untrusted_peers() {
+    let mut app = get_app().await;
+
+    burst(&mut app, "203.0.113.9:4000", Some("198.51.100.1")).await;
+    let response = test::call_service(&mut app, get_pokemon("203.0.113.9:4000", Some("192.168.1.20"))).await;
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[actix_rt::test]
+async fn should_read_every_forwarded_for_line_a_trusted_proxy_passes_on() {
+    let mut app = get_app().await;
+
+    let mut statuses = Vec::new();
+    for attempt in 0..=BURST {
+        // The client's own line comes first; the proxy appends the address it saw.
+        let request = test::TestRequest::get()
+            .uri("/pokemon/pikachu")
+            .peer_addr("10.0.0.2:4000".parse::<SocketAddr>().unwrap())
+            .header("X-Forwarded-For", format!("198.51.100.{}", attempt))
+            .header("X-Forwarded-For", "203.0.113.9")
+            .to_request();
+        statuses.push(test::call_service(&mut app, request).await.status());
+    }
+
+    assert_eq!(statuses[BURST], StatusCode::TOO_MANY_REQUESTS);
+}
@@ -0,0 +1,170 @@
+use actix_web::{App, test};
+use actix_web::http::StatusCode;
+use actix_web::test::read_body_json;
+use serde_json::json;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::{body_string_contains, method, path};
+
+use shakespearemon::error_response::ErrorResponse;
+use shakespearemon::poke::ability_response::AbilityResponse;
+use shakespearemon::poke::move_response::MoveResponse;
+use shakespearemon::resource_translation_service::{ResourceTranslationResponse, translate_ability_description, translate_move_description};
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+
+use crate::helpers::{get_resource_source, get_settings, get_translator};
+
+async fn mock_resource_request(mock_server: &MockServer, resource_path: &str, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path(resource_path))
+        .respond_with(response)
+        .mount(mock_server)
+        .await;
+}
+
+async fn mock_translation_of(mock_server: &MockServer, text: &str, translated: &str) {
+    let translation = ShakespeareTranslationResponse::new(translated.to_owned(), text.to_owned(), String::from("shakespeare"));
+
+    Mock::given(method("POST"))
+        .and(body_string_contains(text))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+}
+
+fn generate_static_ability_response() -> serde_json::Value {
+    json!({
+        "id": 9,
+        "name": "static",
+        "flavor_text_entries": [
+            { "flavor_text": "Kann bei Berührung paralysieren.", "language": { "name": "de", "url": "" } },
+            { "flavor_text": "May cause paralysis if touched.", "language": { "name": "en", "url": "" } }
+        ],
+        "effect_entries": [
+            { "effect": "Whenever a move makes contact with this Pokémon, the move's user has a 30% chance of being paralyzed.", "short_effect": "Has a 30% chance of paralyzing attacking Pokémon on contact.", "language": { "name": "en", "url": "" } }
+        ]
+    })
+}
+
+fn generate_flamethrower_move_response() -> serde_json::Value {
+    json!({
+        "id": 53,
+        "name": "flamethrower",
+        "effect_chance": 10,
+        "flavor_text_entries": [],
+        "effect_entries": [
+            { "effect": "Inflicts regular damage. Has a $effect_chance% chance to burn the target.", "short_effect": "Has a $effect_chance% chance to burn the target.", "language": { "name": "en", "url": "" } }
+        ]
+    })
+}
+
+async fn call(mock_server: &MockServer, uri: &str) -> actix_web::dev::ServiceResponse {
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_resource_source::<AbilityResponse>(&format!("{}/ability", mock_server.uri())))
+        .app_data(get_resource_source::<MoveResponse>(&format!("{}/move", mock_server.uri())))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_ability_description)
+        .service(translate_move_description)).await;
+
+    let req = test::TestRequest::get()
+        .uri(uri).to_request();
+
+    test::call_service(&mut app, req).await
+}
+
+#[actix_rt::test]
+async fn translates_the_english_ability_flavor_text() {
+    let mock_server = MockServer::start().await;
+    mock_resource_request(&mock_server, "/ability/static", ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(generate_static_ability_response())).await;
+    mock_translation_of(&mock_server, "May cause paralysis", "Mayhap causeth paralysis if touch'd.").await;
+
+    let resp = call(&mock_server, "/ability/static").await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resource_translation_response: ResourceTranslationResponse = read_body_json(resp).await;
+    assert_eq!(resource_translation_response.name, "static");
+    assert_eq!(resource_translation_response.description, "Mayhap causeth paralysis if touch'd.");
+    assert_eq!(resource_translation_response.effect, None);
+}
+
+#[actix_rt::test]
+async fn translates_the_ability_effect_when_requested() {
+    let mock_server = MockServer::start().await;
+    mock_resource_request(&mock_server, "/ability/static", ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(generate_static_ability_response())).await;
+    mock_translation_of(&mock_server, "May cause paralysis", "Mayhap causeth paralysis if touch'd.").await;
+    mock_translation_of(&mock_server, "Has a 30% chance", "Hath a 30% chance.").await;
+
+    let resp = call(&mock_server, "/ability/static?include=effect").await;
+
+    let resource_translation_response: ResourceTranslationResponse = read_body_json(resp).await;
+    assert_eq!(resource_translation_response.effect, Some("Hath a 30% chance.".to_owned()));
+}
+
+#[actix_rt::test]
+async fn leaves_the_effect_out_when_it_cannot_be_translated() {
+    let mock_server = MockServer::start().await;
+    mock_resource_request(&mock_server, "/ability/static", ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(generate_static_ability_response())).await;
+    mock_translation_of(&mock_server, "May cause paralysis", "Mayhap causeth paralysis if touch'd.").await;
+    Mock::given(method("POST"))
+        .and(body_string_contains("Has a 30% chance"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::InternalServerError))
+        .mount(&mock_server)
+        .await;
+
+    let resp = call(&mock_server, "/ability/static?include=effect").await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resource_translation_response: ResourceTranslationResponse = read_body_json(resp).await;
+    assert_eq!(resource_translation_response.description, "Mayhap causeth paralysis if touch'd.");
+    assert_eq!(resource_translation_response.effect, None);
+}
+
+#[actix_rt::test]
+async fn falls_back_to_the_move_effect_without_flavor_text() {
+    let mock_server = MockServer::start().await;
+    mock_resource_request(&mock_server, "/move/flamethrower", ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(generate_flamethrower_move_response())).await;
+    mock_translation_of(&mock_server, "Has a 10% chance to burn the target.", "Hath a 10% chance to burneth the target.").await;
+
+    let resp = call(&mock_server, "/move/flamethrower").await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resource_translation_response: ResourceTranslationResponse = read_body_json(resp).await;
+    assert_eq!(resource_translation_response.name, "flamethrower");
+    assert_eq!(resource_translation_response.description, "Hath a 10% chance to burneth the target.");
+}
+
+#[actix_rt::test]
+async fn returns_not_found_for_an_unknown_move() {
+    let mock_server = MockServer::start().await;
+    mock_resource_request(&mock_server, "/move/ozer", ResponseTemplate::new(SurfStatusCode::NotFound)).await;
+
+    let resp = call(&mock_server, "/move/ozer").await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let error: ErrorResponse = read_body_json(resp).await;
+    assert_eq!(error.error, "resource_not_found");
+    assert_eq!(error.message, "Ability or Move Not Found");
+}
+
+#[actix_rt::test]
+async fn returns_not_found_for_an_ability_without_an_english_description() {
+    let mock_server = MockServer::start().await;
+    let untranslated_ability = json!({
+        "id": 9,
+        "name": "static",
+        "flavor_text_entries": [
+            { "flavor_text": "Kann bei Berührung paralysieren.", "language": { "name": "de", "url": "" } }
+        ],
+        "effect_entries": []
+    });
+    mock_resource_request(&mock_server, "/ability/static", ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(untranslated_ability)).await;
+
+    let resp = call(&mock_server, "/ability/static").await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let error: ErrorResponse = read_body_json(resp).await;
+    assert_eq!(error.error, "resource_description_not_found");
+    assert_eq!(error.message, "Ability or Move Description Not Found");
+}
@@ -0,0 +1,130 @@
+use actix_web::{App, test};
+use actix_web::http::StatusCode;
+use actix_web::test::read_body_json;
+use async_trait::async_trait;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{MockServer, ResponseTemplate};
+
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::poke::poke_species_response::{ApiResource, Genus, Name, NamedApiResource, PokeSpeciesResponse, TextFlavorEntry, TextFlavorEntryLanguage};
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::shakespeare::shakespeare_client_exception::ShakespeareClientException;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::shakespeare::translator::Translator;
+use shakespearemon::translation_service::ShakespearemonResponse;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, get_settings, get_translator, mock_shakespeare_client_request};
+
+/// Translates everything except the genus.
+struct GenusFailingTranslator;
+
+#[async_trait]
+impl Translator for GenusFailingTranslator {
+    async fn get_shakespearean_translation(&self, text: &str) -> Result<String, ShakespeareClientException> {
+        if text == "Mouse Pokémon" {
+            return Err(ShakespeareClientException::ShakespeareClientWentWrong);
+        }
+        LocalTranslator::new(false).get_shakespearean_translation(text).await
+    }
+}
+
+fn language(name: &str) -> TextFlavorEntryLanguage {
+    TextFlavorEntryLanguage {
+        name: name.to_owned()
+    }
+}
+
+fn named_api_resource(name: &str) -> Option<NamedApiResource> {
+    Some(NamedApiResource {
+        name: name.to_owned(),
+        url: String::new(),
+    })
+}
+
+fn generate_pikachu_species_response() -> PokeSpeciesResponse {
+    let mut response = PokeSpeciesResponse::new(25, "pikachu".to_owned(), vec![TextFlavorEntry::new("Flavor text".to_owned(), "en".to_owned())]);
+    response.genera = vec![Genus { genus: "Mouse Pokémon".to_owned(), language: language("en") }];
+    response.generation = named_api_resource("generation-i");
+    response.color = named_api_resource("yellow");
+    response.habitat = named_api_resource("forest");
+    response.evolution_chain = Some(ApiResource { url: "https://pokeapi.co/api/v2/evolution-chain/10/".to_owned() });
+    response.names = vec![
+        Name { name: "Pikachu".to_owned(), language: language("en") },
+        Name { name: "ピカチュウ".to_owned(), language: language("ja") },
+    ];
+    response
+}
+
+async fn get_pokemon(uri: &str) -> ShakespearemonResponse {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_species_response(generate_pikachu_species_response())))
+        .app_data(get_translator(&mock_server.uri()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri(uri).to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    read_body_json(resp).await
+}
+
+#[actix_rt::test]
+async fn leaves_metadata_out_unless_requested() {
+    let shakespearemon_response = get_pokemon("/pokemon/pikachu").await;
+
+    assert!(shakespearemon_response.metadata.is_none());
+}
+
+#[actix_rt::test]
+async fn includes_metadata_when_requested() {
+    let shakespearemon_response = get_pokemon("/pokemon/pikachu?include=metadata").await;
+
+    let metadata = shakespearemon_response.metadata.unwrap();
+    assert_eq!(metadata.genus, Some("Mouse Pokémon".to_owned()));
+    assert_eq!(metadata.translated_genus, None);
+    assert_eq!(metadata.generation, Some("generation-i".to_owned()));
+    assert!(!metadata.is_legendary);
+    assert!(!metadata.is_mythical);
+    assert_eq!(metadata.color, Some("yellow".to_owned()));
+    assert_eq!(metadata.habitat, Some("forest".to_owned()));
+    assert_eq!(metadata.evolution_chain_url, Some("https://pokeapi.co/api/v2/evolution-chain/10/".to_owned()));
+    assert_eq!(metadata.names.get("ja"), Some(&"ピカチュウ".to_owned()));
+}
+
+#[actix_rt::test]
+async fn translates_genus_when_requested() {
+    let shakespearemon_response = get_pokemon("/pokemon/pikachu?include=metadata&translate_genus=true").await;
+
+    let metadata = shakespearemon_response.metadata.unwrap();
+    assert_eq!(metadata.genus, Some("Mouse Pokémon".to_owned()));
+    assert_eq!(metadata.translated_genus, Some("translated".to_owned()));
+}
+
+#[actix_rt::test]
+async fn leaves_translated_genus_out_when_it_cannot_be_translated() {
+    let mut app = test::init_service(App::new()
+        .data(get_settings("http://localhost".to_owned()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_species_response(generate_pikachu_species_response())))
+        .app_data(as_translator(GenusFailingTranslator))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu?include=metadata&translate_genus=true").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let shakespearemon_response: ShakespearemonResponse = read_body_json(resp).await;
+    let metadata = shakespearemon_response.metadata.unwrap();
+    assert_eq!(metadata.genus, Some("Mouse Pokémon".to_owned()));
+    assert_eq!(metadata.translated_genus, None);
+}
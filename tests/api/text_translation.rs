@@ -0,0 +1,135 @@
+use actix_web::{App, test};
+use actix_web::http::StatusCode;
+use actix_web::test::read_body_json;
+use serde_json::json;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::method;
+
+use shakespearemon::error_response::ErrorResponse;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::text_translation_service::{configure_text_translation, TextTranslationResponse};
+
+use crate::helpers::{get_settings, get_translator, mock_shakespeare_client_request};
+
+#[actix_rt::test]
+async fn translates_user_supplied_text() {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .configure(configure_text_translation(1000))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/translate")
+        .set_json(&json!({"text": "You are my friend", "style": "shakespeare"}))
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let text_translation_response: TextTranslationResponse = read_body_json(resp).await;
+    assert_eq!(text_translation_response.text, "You are my friend");
+    assert_eq!(text_translation_response.style, "shakespeare");
+    assert_eq!(text_translation_response.translated, "translated");
+}
+
+#[actix_rt::test]
+async fn returns_400_for_empty_text_without_calling_the_translator() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::Ok))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .configure(configure_text_translation(1000))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/translate")
+        .set_json(&json!({"text": "   "}))
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn returns_400_for_unsupported_style() {
+    let mock_server = MockServer::start().await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .configure(configure_text_translation(1000))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/translate")
+        .set_json(&json!({"text": "Hello", "style": "pirate"}))
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[actix_rt::test]
+async fn returns_413_for_text_longer_than_the_configured_maximum() {
+    let mock_server = MockServer::start().await;
+
+    let mut settings = get_settings(mock_server.uri());
+    settings.application.max_text_length = 10;
+
+    let mut app = test::init_service(App::new()
+        .data(settings)
+        .app_data(get_translator(&mock_server.uri()))
+        .configure(configure_text_translation(10))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/translate")
+        .set_json(&json!({"text": "This text is too long"}))
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[actix_rt::test]
+async fn returns_errors_in_the_same_envelope_for_oversized_and_malformed_bodies() {
+    let mock_server = MockServer::start().await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(get_translator(&mock_server.uri()))
+        .configure(configure_text_translation(10))).await;
+
+    let req = test::TestRequest::post()
+        .uri("/translate")
+        .set_json(&json!({"text": "x".repeat(2000)}))
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let error: ErrorResponse = read_body_json(resp).await;
+    assert_eq!(error.error, "text_too_long");
+    assert_eq!(error.message, "Text must not be longer than 10 characters");
+
+    let req = test::TestRequest::post()
+        .uri("/translate")
+        .header("content-type", "application/json")
+        .set_payload("{\"text\":")
+        .to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let error: ErrorResponse = read_body_json(resp).await;
+    assert_eq!(error.error, "invalid_request");
+    assert_eq!(error.message, "Request body is not valid");
+}
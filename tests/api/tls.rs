@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::{App, test, web};
+use actix_web::http::{header, StatusCode};
+use actix_web::middleware::Condition;
+use awc::{Client, Connector};
+use rcgen::{BasicConstraints, Certificate, CertificateParams, DnType, IsCa};
+
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::shakespeare::local_translator::LocalTranslator;
+use shakespearemon::tls::https_redirect::{HttpsPort, redirect_to_https};
+use shakespearemon::tls::strict_transport_security::strict_transport_security;
+use shakespearemon::tls::tls_config::load_server_config;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, as_translator, get_settings};
+
+struct TestCertificate {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    ca_cert_der: Vec<u8>,
+}
+
+/// Issues a `localhost` certificate from a throwaway CA, since webpki does not
+/// accept a self-signed leaf as its own trust anchor.
+fn generate_certificate(name: &str) -> TestCertificate {
+    let mut ca_params = CertificateParams::new(Vec::new());
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    ca_params.distinguished_name.push(DnType::CommonName, "Shakespearemon test CA");
+    let ca = Certificate::from_params(ca_params).unwrap();
+
+    let certificate = Certificate::from_params(CertificateParams::new(vec!["localhost".to_owned()])).unwrap();
+
+    let directory = std::env::temp_dir();
+    let cert_path = directory.join(format!("shakespearemon-{}-{}-cert.pem", name, std::process::id()));
+    let key_path = directory.join(format!("shakespearemon-{}-{}-key.pem", name, std::process::id()));
+
+    std::fs::write(&cert_path, certificate.serialize_pem_with_signer(&ca).unwrap()).unwrap();
+    std::fs::write(&key_path, certificate.serialize_private_key_pem()).unwrap();
+
+    TestCertificate {
+        cert_path,
+        key_path,
+        ca_cert_der: ca.serialize_der().unwrap(),
+    }
+}
+
+fn trusting_client(ca_cert_der: Vec<u8>) -> Client {
+    let mut client_config = rustls::ClientConfig::new();
+    client_config.root_store.add(&rustls::Certificate(ca_cert_der)).unwrap();
+
+    Client::builder()
+        .connector(Connector::new().rustls(Arc::new(client_config)).finish())
+        .timeout(Duration::from_secs(10))
+        .finish()
+}
+
+#[test]
+fn serves_translations_over_https_with_hsts() {
+    let certificate = generate_certificate("https");
+    let server_config = load_server_config(certificate.cert_path.to_str().unwrap(), certificate.key_path.to_str().unwrap()).unwrap();
+
+    actix_web::rt::System::new("tls").block_on(async move {
+        let server = test::start_with(test::config().rustls(server_config), || {
+            App::new()
+                .data(get_settings("http://localhost".to_owned()))
+                .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+                .app_data(as_translator(LocalTranslator::new(false)))
+                .wrap(Condition::new(true, strict_transport_security(31536000, true)))
+                .service(translate_pokemon_description_by_shakespeare)
+        });
+
+        let response = trusting_client(certificate.ca_cert_der)
+            .get(server.url("/pokemon/pikachu"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(header::STRICT_TRANSPORT_SECURITY).unwrap(), "max-age=31536000; includeSubDomains");
+    });
+}
+
+#[test]
+fn rejects_unreadable_certificates_and_keys() {
+    let certificate = generate_certificate("invalid");
+    let cert_path = certificate.cert_path.to_str().unwrap();
+
+    assert!(load_server_config("/does/not/exist.pem", cert_path).is_err());
+    assert!(load_server_config(cert_path, cert_path).is_err());
+}
+
+#[actix_rt::test]
+async fn redirects_plain_http_requests_to_https() {
+    let mut app = test::init_service(App::new()
+        .data(HttpsPort(8443))
+        .default_service(web::to(redirect_to_https))).await;
+
+    let request = test::TestRequest::post()
+        .uri("/pokemon/pikachu?include=media")
+        .header(header::HOST, "example.com:8080")
+        .to_request();
+    let response = test::call_service(&mut app, request).await;
+
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(response.headers().get(header::LOCATION).unwrap(), "https://example.com:8443/pokemon/pikachu?include=media");
+}
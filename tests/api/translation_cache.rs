@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::{App, test, web};
+use actix_web::http::StatusCode;
+use actix_web::test::read_body_json;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::method;
+
+use shakespearemon::cache::in_memory_translation_cache::InMemoryTranslationCache;
+use shakespearemon::cache::redis_translation_cache::RedisTranslationCache;
+use shakespearemon::cache::translation_cache::TranslationCache;
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::translation_service::ShakespearemonResponse;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, get_settings, get_translator, mock_shakespeare_client_request};
+
+fn as_translation_cache(translation_cache: Arc<dyn TranslationCache>) -> web::Data<dyn TranslationCache> {
+    web::Data::from(translation_cache)
+}
+
+#[actix_rt::test]
+async fn serves_cached_translation_without_calling_the_translator() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::TooManyRequests))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let translation_cache = Arc::new(InMemoryTranslationCache::new("test", Duration::from_secs(60)));
+    translation_cache.set_translation("Flavor text", "shakespeare", "cached").await;
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(get_translator(&mock_server.uri()))
+        .app_data(as_translation_cache(translation_cache))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let shakespearemon_response: ShakespearemonResponse = read_body_json(resp).await;
+    assert_eq!(shakespearemon_response.description, "cached");
+}
+
+#[actix_rt::test]
+async fn caches_fresh_translation() {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("Flavor text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let translation_cache = Arc::new(InMemoryTranslationCache::new("test", Duration::from_secs(60)));
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(get_translator(&mock_server.uri()))
+        .app_data(as_translation_cache(translation_cache.clone()))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    assert_eq!(translation_cache.get_translation("Flavor text", "shakespeare").await, Some("translated".to_owned()));
+}
+
+#[actix_rt::test]
+async fn translates_when_redis_is_unreachable() {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("Flavor text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let translation_cache = Arc::new(RedisTranslationCache::new("redis://127.0.0.1:1/", "test", Duration::from_secs(60)).unwrap());
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(get_translator(&mock_server.uri()))
+        .app_data(as_translation_cache(translation_cache))
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let shakespearemon_response: ShakespearemonResponse = read_body_json(resp).await;
+    assert_eq!(shakespearemon_response.description, "translated");
+}
@@ -0,0 +1,106 @@
+use actix_web::{App, test, web};
+use actix_web::http::StatusCode;
+use actix_web::test::read_body_json;
+use surf::StatusCode as SurfStatusCode;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::method;
+
+use shakespearemon::admin_service::{list_stored_translations, purge_stored_translations, PurgeResponse};
+use shakespearemon::poke::in_memory_species_source::InMemorySpeciesSource;
+use shakespearemon::shakespeare::shakespeare_translation_response::ShakespeareTranslationResponse;
+use shakespearemon::store::translation_record::TranslationRecord;
+use shakespearemon::store::translation_store::{hash_source_text, TranslationStore};
+use shakespearemon::translation_service::ShakespearemonResponse;
+use shakespearemon::translation_service::translate_pokemon_description_by_shakespeare;
+
+use crate::helpers::{as_species_source, get_settings, get_translator, mock_shakespeare_client_request};
+
+#[actix_rt::test]
+async fn serves_stored_translation_without_calling_the_translator() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(SurfStatusCode::TooManyRequests))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let translation_store = web::Data::new(TranslationStore::open(":memory:").unwrap());
+    translation_store.save_translation("Flavor text", "shakespeare", "stored");
+    translation_store.flush().await.unwrap();
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(get_translator(&mock_server.uri()))
+        .app_data(translation_store)
+        .service(translate_pokemon_description_by_shakespeare)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let shakespearemon_response: ShakespearemonResponse = read_body_json(resp).await;
+    assert_eq!(shakespearemon_response.description, "stored");
+}
+
+#[actix_rt::test]
+async fn stores_fresh_translation_and_lists_it_on_admin_endpoint() {
+    let mock_server = MockServer::start().await;
+
+    let translation = ShakespeareTranslationResponse::new(String::from("translated"), String::from("Flavor text"), String::from("shakespeare"));
+    mock_shakespeare_client_request(&mock_server, ResponseTemplate::new(SurfStatusCode::Ok).set_body_json(translation)).await;
+
+    let translation_store = web::Data::new(TranslationStore::open(":memory:").unwrap());
+
+    let mut app = test::init_service(App::new()
+        .data(get_settings(mock_server.uri()))
+        .app_data(as_species_source(InMemorySpeciesSource::new().with_flavor_text("pikachu", "Flavor text", "en")))
+        .app_data(get_translator(&mock_server.uri()))
+        .app_data(translation_store.clone())
+        .service(translate_pokemon_description_by_shakespeare)
+        .service(list_stored_translations)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/pokemon/pikachu").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    translation_store.flush().await.unwrap();
+
+    let req = test::TestRequest::get()
+        .uri("/admin/translations").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let records: Vec<TranslationRecord> = read_body_json(resp).await;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].source_hash, hash_source_text("Flavor text"));
+    assert_eq!(records[0].style, "shakespeare");
+    assert_eq!(records[0].translated, "translated");
+}
+
+#[actix_rt::test]
+async fn purges_stored_translations_on_admin_endpoint() {
+    let translation_store = web::Data::new(TranslationStore::open(":memory:").unwrap());
+    translation_store.save_translation("Flavor text", "shakespeare", "stored");
+    translation_store.flush().await.unwrap();
+
+    let mut app = test::init_service(App::new()
+        .app_data(translation_store.clone())
+        .service(purge_stored_translations)).await;
+
+    let req = test::TestRequest::delete()
+        .uri("/admin/translations").to_request();
+
+    let resp = test::call_service(&mut app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let purge_response: PurgeResponse = read_body_json(resp).await;
+    assert_eq!(purge_response.purged, 1);
+    assert!(translation_store.list_translations().await.unwrap().is_empty());
+}